@@ -1,32 +1,56 @@
-use std::process::Command;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+
+/// Read a fixed-size token information class from the current process token
 #[cfg(windows)]
-use std::os::windows::process::CommandExt;
+fn query_process_token<T: Default>(
+    class: windows::Win32::Security::TOKEN_INFORMATION_CLASS,
+) -> Option<T> {
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Security::{GetTokenInformation, TOKEN_QUERY};
+    use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
 
+    let mut token = HANDLE::default();
+    let mut info = T::default();
+    let mut returned = 0u32;
+
+    // SAFETY: the token handle is only used after OpenProcessToken succeeds and is closed
+    // before returning; `info` is a buffer of exactly `size_of::<T>()` bytes
+    unsafe {
+        OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).ok()?;
+        let queried = GetTokenInformation(
+            token,
+            class,
+            Some(&mut info as *mut T as *mut _),
+            std::mem::size_of::<T>() as u32,
+            &mut returned,
+        );
+        let _ = CloseHandle(token);
+        queried.ok().map(|_| info)
+    }
+}
+
+/// Read the elevation flag from the current process token
 #[cfg(windows)]
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+fn token_is_elevated() -> bool {
+    use windows::Win32::Security::{TokenElevation, TOKEN_ELEVATION};
+
+    query_process_token::<TOKEN_ELEVATION>(TokenElevation)
+        .is_some_and(|elevation| elevation.TokenIsElevated != 0)
+}
 
 /// Check if the application is running with administrator privileges
 #[tauri::command]
 pub fn is_admin() -> bool {
     #[cfg(windows)]
     {
-        // Use PowerShell to check admin status
-        let output = Command::new("powershell")
-            .creation_flags(CREATE_NO_WINDOW)
-            .args([
-                "-NoProfile",
-                "-Command",
-                "([Security.Principal.WindowsPrincipal] [Security.Principal.WindowsIdentity]::GetCurrent()).IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator)"
-            ])
-            .output();
-
-        match output {
-            Ok(o) => {
-                let result = String::from_utf8_lossy(&o.stdout);
-                result.trim().eq_ignore_ascii_case("true")
-            }
-            Err(_) => false,
-        }
+        // Elevation can't change after launch, so it's checked once per process
+        static IS_ADMIN: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+        *IS_ADMIN.get_or_init(token_is_elevated)
     }
 
     #[cfg(not(windows))]
@@ -35,38 +59,456 @@ pub fn is_admin() -> bool {
     }
 }
 
-/// Request elevation (restart as admin)
+/// Whether the current user could restart the app elevated
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ElevationCapability {
+    /// Already running as administrator
+    AlreadyElevated,
+    /// A UAC prompt can elevate the app
+    Available,
+    /// UAC is turned off (`EnableLUA` = 0), so no prompt can be shown
+    UacDisabled,
+    /// The user isn't a member of the Administrators group
+    NotAdministrator,
+}
+
+/// Decide the elevation capability from the token and UAC state
+/// `split_token` means the user is an admin running with a filtered (limited) token
+fn classify_elevation(
+    elevated: bool,
+    split_token: bool,
+    admin_member: bool,
+    uac_enabled: bool,
+) -> ElevationCapability {
+    if elevated {
+        ElevationCapability::AlreadyElevated
+    } else if !uac_enabled {
+        ElevationCapability::UacDisabled
+    } else if split_token || admin_member {
+        ElevationCapability::Available
+    } else {
+        ElevationCapability::NotAdministrator
+    }
+}
+
+/// Read `EnableLUA`; UAC is on unless the value is present and zero
+#[cfg(windows)]
+fn uac_enabled() -> bool {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SOFTWARE\Microsoft\Windows\CurrentVersion\Policies\System")
+        .and_then(|key| key.get_value::<u32, _>("EnableLUA"))
+        .map(|value| value != 0)
+        .unwrap_or(true)
+}
+
+/// Whether the current token is a member of the built-in Administrators group
+#[cfg(windows)]
+fn token_in_administrators() -> bool {
+    use windows::Win32::Foundation::{BOOL, HANDLE};
+    use windows::Win32::Security::{
+        CheckTokenMembership, CreateWellKnownSid, WinBuiltinAdministratorsSid, PSID,
+    };
+
+    // SECURITY_MAX_SID_SIZE
+    let mut sid = [0u8; 68];
+    let mut size = sid.len() as u32;
+    let psid = PSID(sid.as_mut_ptr() as *mut _);
+    let mut member = BOOL::default();
+
+    // SAFETY: `sid` outlives both calls and `size` holds its length
+    unsafe {
+        CreateWellKnownSid(
+            WinBuiltinAdministratorsSid,
+            PSID::default(),
+            psid,
+            &mut size,
+        )
+        .is_ok()
+            && CheckTokenMembership(HANDLE::default(), psid, &mut member).is_ok()
+            && member.as_bool()
+    }
+}
+
+/// Check whether elevation is possible, so the UI can hide "Run as admin" when it isn't
 #[tauri::command]
-pub fn request_elevation() -> Result<String, String> {
+pub fn can_elevate() -> ElevationCapability {
+    #[cfg(windows)]
+    {
+        use windows::Win32::Security::{
+            TokenElevationType, TokenElevationTypeLimited, TOKEN_ELEVATION_TYPE,
+        };
+
+        let split_token = query_process_token::<TOKEN_ELEVATION_TYPE>(TokenElevationType)
+            == Some(TokenElevationTypeLimited);
+        classify_elevation(
+            is_admin(),
+            split_token,
+            token_in_administrators(),
+            uac_enabled(),
+        )
+    }
+
+    #[cfg(not(windows))]
+    {
+        ElevationCapability::NotAdministrator
+    }
+}
+
+/// Event emitted just before the app exits to restart elevated
+const ELEVATION_RESTARTING_EVENT: &str = "elevation-restarting";
+
+/// Default pause between emitting the restart event and exiting
+const DEFAULT_RESTART_DELAY_MS: u64 = 1500;
+const MAX_RESTART_DELAY_MS: u64 = 10_000;
+
+/// Payload of the `elevation-restarting` event
+#[derive(Debug, Serialize, Clone)]
+pub struct ElevationRestarting {
+    pub delay_ms: u64,
+}
+
+/// Turn a failed `Start-Process -Verb RunAs` into a readable error
+fn elevation_error(message: &str, context: &str) -> String {
+    if message.contains("canceled by the user") || message.contains("cancelled by the user") {
+        "Elevation was cancelled at the UAC prompt".to_string()
+    } else {
+        format!("{}: {}", context, message)
+    }
+}
+
+/// Request elevation (restart as admin)
+/// Emits `elevation-restarting` and exits `delay_ms` (default 1500) later from a background
+/// thread, so the UI keeps rendering meanwhile; if the UAC prompt is cancelled the current
+/// instance keeps running and an error is returned
+#[tauri::command(async)]
+pub fn request_elevation(app: AppHandle, delay_ms: Option<u64>) -> Result<String, AppError> {
     #[cfg(windows)]
     {
         let exe_path = std::env::current_exe()
             .map_err(|e| format!("Failed to get executable path: {}", e))?;
 
-        let output = Command::new("powershell")
-            .creation_flags(CREATE_NO_WINDOW)
-            .args([
-                "-NoProfile",
-                "-Command",
-                &format!(
-                    "Start-Process -FilePath '{}' -Verb RunAs",
-                    exe_path.display()
-                ),
-            ])
-            .output()
-            .map_err(|e| format!("Failed to request elevation: {}", e))?;
-
-        if output.status.success() {
-            // Exit current instance
+        let script = format!(
+            "Start-Process -FilePath '{}' -Verb RunAs -ErrorAction Stop",
+            exe_path.display().to_string().replace('\'', "''")
+        );
+        crate::ps::run_powershell(&script)
+            .map_err(|e| elevation_error(&e.to_string(), "Elevation request failed"))?;
+
+        let delay_ms = delay_ms
+            .unwrap_or(DEFAULT_RESTART_DELAY_MS)
+            .min(MAX_RESTART_DELAY_MS);
+        if let Err(e) = app.emit(ELEVATION_RESTARTING_EVENT, ElevationRestarting { delay_ms }) {
+            warn!("Failed to emit {}: {}", ELEVATION_RESTARTING_EVENT, e);
+        }
+
+        // Exit current instance once the frontend had time to show the message
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(delay_ms));
             std::process::exit(0);
-        } else {
-            let error = String::from_utf8_lossy(&output.stderr);
-            Err(format!("Elevation request failed: {}", error))
+        });
+
+        Ok(format!("Restarting as administrator in {} ms", delay_ms))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (app, delay_ms);
+        Err("This feature is only available on Windows".into())
+    }
+}
+
+/// Privileged operations `run_elevated` can perform
+/// The command line is built from these on the Rust side, never taken from the caller.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ElevatedAction {
+    /// Turn a firewall profile ("domain", "private", "public" or "all") on or off
+    SetFirewallProfile { profile: String, enabled: bool },
+    /// Set static IPv4 DNS servers on an adapter; an empty list switches back to DHCP
+    SetDns { adapter: String, servers: Vec<String> },
+    FlushDns,
+    ReleaseIp { adapter: String },
+    RenewIp { adapter: String },
+    ResetWinsock,
+    ResetTcpIp,
+}
+
+/// Most DNS servers a `SetDns` action accepts
+const MAX_ELEVATED_DNS_SERVERS: usize = 3;
+
+impl ElevatedAction {
+    /// Adapter the action targets, checked for existence before prompting
+    fn adapter(&self) -> Option<&str> {
+        match self {
+            ElevatedAction::SetDns { adapter, .. }
+            | ElevatedAction::ReleaseIp { adapter }
+            | ElevatedAction::RenewIp { adapter } => Some(adapter),
+            _ => None,
+        }
+    }
+
+    /// Validate the parameters and return the commands to run, in order
+    fn commands(&self) -> Result<Vec<Vec<String>>, String> {
+        let args = |parts: &[&str]| parts.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        if let Some(adapter) = self.adapter() {
+            crate::network::validate_adapter_name(adapter)?;
         }
+
+        Ok(match self {
+            ElevatedAction::SetFirewallProfile { profile, enabled } => {
+                let profile = if profile.trim().eq_ignore_ascii_case("all") {
+                    "allprofiles".to_string()
+                } else {
+                    format!("{}profile", crate::firewall::parse_profile(profile)?.to_lowercase())
+                };
+                let state = if *enabled { "on" } else { "off" };
+                vec![args(&["netsh", "advfirewall", "set", &profile, "state", state])]
+            }
+            ElevatedAction::SetDns { adapter, servers } => {
+                if servers.len() > MAX_ELEVATED_DNS_SERVERS {
+                    return Err(format!("At most {} DNS servers are supported", MAX_ELEVATED_DNS_SERVERS));
+                }
+                let servers = servers
+                    .iter()
+                    .map(|s| {
+                        s.trim()
+                            .parse::<std::net::Ipv4Addr>()
+                            .map(|ip| ip.to_string())
+                            .map_err(|_| format!("Invalid DNS server: {}", s))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let base = ["netsh", "interface", "ipv4"];
+                match servers.split_first() {
+                    None => vec![args(&[&base[..], &["set", "dnsservers", adapter, "dhcp"]].concat())],
+                    Some((primary, rest)) => {
+                        let mut commands = vec![args(
+                            &[&base[..], &["set", "dnsservers", adapter, "static", primary, "primary", "validate=no"]]
+                                .concat(),
+                        )];
+                        for (i, server) in rest.iter().enumerate() {
+                            let index = format!("index={}", i + 2);
+                            commands.push(args(
+                                &[&base[..], &["add", "dnsservers", adapter, server, &index, "validate=no"]].concat(),
+                            ));
+                        }
+                        commands
+                    }
+                }
+            }
+            ElevatedAction::FlushDns => vec![args(&["ipconfig", "/flushdns"])],
+            ElevatedAction::ReleaseIp { adapter } => vec![args(&["ipconfig", "/release", adapter])],
+            ElevatedAction::RenewIp { adapter } => vec![args(&["ipconfig", "/renew", adapter])],
+            ElevatedAction::ResetWinsock => vec![args(&["netsh", "winsock", "reset"])],
+            ElevatedAction::ResetTcpIp => vec![args(&["netsh", "int", "ip", "reset"])],
+        })
+    }
+}
+
+/// Exit code and captured output of an elevated command
+#[derive(Debug, Serialize, Clone)]
+pub struct ElevatedOutput {
+    pub exit_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Quote one argument for a `cmd /c` line, rejecting characters cmd would interpret
+fn quote_cmd_arg(arg: &str) -> Result<String, String> {
+    if arg
+        .chars()
+        .any(|c| matches!(c, '"' | '&' | '|' | '<' | '>' | '^' | '%' | '\n' | '\r'))
+    {
+        return Err(format!("Argument contains unsupported characters: {}", arg));
+    }
+    if arg.is_empty() || arg.contains(char::is_whitespace) {
+        Ok(format!("\"{}\"", arg))
+    } else {
+        Ok(arg.to_string())
+    }
+}
+
+/// Build the `cmd /c` line that runs the action's commands (stopping at the first
+/// failure) and redirects their output to the given files
+fn elevated_cmd_line(action: &ElevatedAction, stdout: &str, stderr: &str) -> Result<String, String> {
+    let commands = action
+        .commands()?
+        .iter()
+        .map(|command| {
+            command
+                .iter()
+                .map(|arg| quote_cmd_arg(arg))
+                .collect::<Result<Vec<_>, _>>()
+                .map(|parts| parts.join(" "))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let line = if commands.len() == 1 {
+        commands[0].clone()
+    } else {
+        format!("({})", commands.join(" && "))
+    };
+    Ok(format!("{} > \"{}\" 2> \"{}\"", line, stdout, stderr))
+}
+
+/// Temporary file removed when dropped
+struct TempOutputFile(PathBuf);
+
+impl Drop for TempOutputFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Run one privileged action elevated (after a UAC prompt) and wait for it to exit,
+/// without restarting the app as administrator. Runs off the main thread since it
+/// blocks on the UAC prompt and the elevated process.
+#[tauri::command(async)]
+pub fn run_elevated(action: ElevatedAction) -> Result<ElevatedOutput, AppError> {
+    #[cfg(windows)]
+    {
+        if let Some(adapter) = action.adapter() {
+            crate::network::ensure_adapter_exists(adapter)?;
+        }
+
+        let temp = std::env::temp_dir();
+        let id = format!(
+            "{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        );
+        let stdout_file = TempOutputFile(temp.join(format!("elevated-{}.out", id)));
+        let stderr_file = TempOutputFile(temp.join(format!("elevated-{}.err", id)));
+
+        let line = elevated_cmd_line(
+            &action,
+            &stdout_file.0.display().to_string(),
+            &stderr_file.0.display().to_string(),
+        )?;
+        let script = format!(
+            "$p = Start-Process -FilePath 'cmd.exe' -ArgumentList '/s /c \"{}\"' -Verb RunAs -WindowStyle Hidden -Wait -PassThru; $p.ExitCode",
+            line.replace('\'', "''")
+        );
+
+        let output = crate::ps::run_powershell(&script)
+            .map_err(|e| elevation_error(&e.to_string(), "Elevated command failed to start"))?;
+
+        let exit_code = output
+            .trim()
+            .parse::<i32>()
+            .map_err(|_| format!("Unexpected exit code output: {}", output.trim()))?;
+        let read = |file: &TempOutputFile| std::fs::read_to_string(&file.0).unwrap_or_default();
+
+        Ok(ElevatedOutput {
+            exit_code,
+            stdout: read(&stdout_file),
+            stderr: read(&stderr_file),
+        })
     }
 
     #[cfg(not(windows))]
     {
-        Err("This feature is only available on Windows".to_string())
+        let _ = action;
+        Err("This feature is only available on Windows".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_cmd_arg() {
+        assert_eq!(quote_cmd_arg("advfirewall").unwrap(), "advfirewall");
+        assert_eq!(quote_cmd_arg("Wi-Fi 2").unwrap(), "\"Wi-Fi 2\"");
+        assert_eq!(quote_cmd_arg("").unwrap(), "\"\"");
+        assert!(quote_cmd_arg("a & calc").is_err());
+        assert!(quote_cmd_arg("%PATH%").is_err());
+    }
+
+    #[test]
+    fn test_elevated_cmd_line() {
+        let action = ElevatedAction::SetFirewallProfile {
+            profile: "Private".to_string(),
+            enabled: true,
+        };
+        assert_eq!(
+            elevated_cmd_line(&action, "C:\\t\\o.out", "C:\\t\\o.err").unwrap(),
+            "netsh advfirewall set privateprofile state on > \"C:\\t\\o.out\" 2> \"C:\\t\\o.err\""
+        );
+
+        let action = ElevatedAction::SetDns {
+            adapter: "Wi-Fi 2".to_string(),
+            servers: vec!["1.1.1.1".to_string(), "8.8.8.8".to_string()],
+        };
+        assert_eq!(
+            elevated_cmd_line(&action, "o", "e").unwrap(),
+            "(netsh interface ipv4 set dnsservers \"Wi-Fi 2\" static 1.1.1.1 primary validate=no && \
+             netsh interface ipv4 add dnsservers \"Wi-Fi 2\" 8.8.8.8 index=2 validate=no) > \"o\" 2> \"e\""
+        );
+    }
+
+    #[test]
+    fn test_elevated_action_validation() {
+        let bad_profile = ElevatedAction::SetFirewallProfile {
+            profile: "everything".to_string(),
+            enabled: false,
+        };
+        assert!(bad_profile.commands().is_err());
+
+        let bad_dns = ElevatedAction::SetDns {
+            adapter: "Ethernet".to_string(),
+            servers: vec!["1.1.1.1 & calc".to_string()],
+        };
+        assert!(bad_dns.commands().is_err());
+
+        let bad_adapter = ElevatedAction::RenewIp {
+            adapter: "Ethernet\" & calc".to_string(),
+        };
+        assert!(elevated_cmd_line(&bad_adapter, "o", "e").is_err());
+
+        let dhcp = ElevatedAction::SetDns {
+            adapter: "Ethernet".to_string(),
+            servers: vec![],
+        };
+        assert_eq!(
+            dhcp.commands().unwrap(),
+            vec![vec!["netsh", "interface", "ipv4", "set", "dnsservers", "Ethernet", "dhcp"]]
+        );
+
+        let action: ElevatedAction = serde_json::from_str(r#"{"type":"renew_ip","adapter":"Ethernet"}"#).unwrap();
+        assert_eq!(action, ElevatedAction::RenewIp { adapter: "Ethernet".to_string() });
+        assert!(serde_json::from_str::<ElevatedAction>(r#"{"type":"run","command":"sc"}"#).is_err());
+    }
+
+    #[test]
+    fn test_classify_elevation() {
+        use ElevationCapability::*;
+        assert_eq!(classify_elevation(true, false, true, true), AlreadyElevated);
+        assert_eq!(classify_elevation(false, true, false, true), Available);
+        assert_eq!(classify_elevation(false, false, true, true), Available);
+        assert_eq!(classify_elevation(false, true, false, false), UacDisabled);
+        assert_eq!(
+            classify_elevation(false, false, false, true),
+            NotAdministrator
+        );
+    }
+
+    #[test]
+    fn test_elevation_error() {
+        assert_eq!(
+            elevation_error(
+                "PowerShell error: The operation was canceled by the user.",
+                "Failed"
+            ),
+            "Elevation was cancelled at the UAC prompt"
+        );
+        assert_eq!(elevation_error("boom", "Failed"), "Failed: boom");
     }
 }
@@ -1,57 +1,960 @@
-use std::process::Command;
+use lazy_static::lazy_static;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, StreamOwned};
+use std::io::{ErrorKind, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::collections::HashMap;
+use std::process::{Child, Command, Stdio};
 use std::os::windows::process::CommandExt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
 
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+use crate::error::AppError;
+use crate::network::{parse_cidr, prefix_mask};
+use crate::network_unified::map_concurrent;
+use crate::ps::{run_cmd, run_powershell, CREATE_NO_WINDOW};
+use crate::tls::https_client_config;
+
+/// Event emitted by ping streams for every probe
+const PING_REPLY_EVENT: &str = "ping-reply";
+
+/// Per-probe reply timeout for ping streams
+const PING_STREAM_TIMEOUT_MS: u64 = 2000;
+
+/// Running ping stream: stop flag, the in-flight ping child and the worker thread
+struct PingStream {
+    stop: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+    handle: JoinHandle<()>,
+}
+
+lazy_static! {
+    /// Active ping streams keyed by host
+    static ref PING_STREAMS: Mutex<HashMap<String, PingStream>> = Mutex::new(HashMap::new());
+}
 
 /// Run ping command
 #[tauri::command]
-pub fn run_ping(host: String, count: u32) -> Result<String, String> {
+pub fn run_ping(host: String, count: u32) -> Result<String, AppError> {
+    let count_str = count.to_string();
+    let output = run_cmd("ping", &["-n", &count_str, &host])?;
+
+    if !output.stdout.is_empty() {
+        Ok(output.stdout)
+    } else {
+        Err(output.stderr.into())
+    }
+}
+
+/// A single ping reply (or timeout)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PingReply {
+    pub bytes: Option<u32>,
+    pub time_ms: Option<u32>,
+    pub ttl: Option<u32>,
+    pub timed_out: bool,
+}
+
+/// Parsed ping run
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PingResult {
+    pub replies: Vec<PingReply>,
+    pub packets_sent: u32,
+    pub packets_received: u32,
+    pub packets_lost: u32,
+    pub min_ms: Option<u32>,
+    pub max_ms: Option<u32>,
+    pub avg_ms: Option<u32>,
+}
+
+/// Value of a `key=value` / `key<value` token, matched case-insensitively
+/// (field names like "bytes" and "TTL" are the same in most ping locales)
+fn ping_field(line: &str, key: &str) -> Option<u32> {
+    line.split_whitespace().find_map(|token| {
+        let lower = token.to_lowercase();
+        let rest = lower.strip_prefix(key)?;
+        let value = rest.strip_prefix('=').or_else(|| rest.strip_prefix('<'))?;
+        value.trim_end_matches("ms").parse().ok()
+    })
+}
+
+/// Round-trip time from a reply line: the `=`/`<` token ending in "ms"
+/// ("time=14ms", "Zeit<1ms", ...); "<1ms" counts as 0 like ping's own summary
+fn ping_time(line: &str) -> Option<u32> {
+    line.split_whitespace().find_map(|token| {
+        let value = token.strip_suffix("ms")?;
+        if let Some((_, v)) = value.split_once('=') {
+            v.parse().ok()
+        } else if value.contains('<') {
+            Some(0)
+        } else {
+            None
+        }
+    })
+}
+
+/// Parse a single line from the reply block
+/// Lines with a TTL are replies; anything else (timeouts, unreachable) counts as lost
+pub(crate) fn parse_ping_line(line: &str) -> PingReply {
+    match ping_field(line, "ttl") {
+        Some(ttl) => PingReply {
+            bytes: ping_field(line, "bytes"),
+            time_ms: ping_time(line),
+            ttl: Some(ttl),
+            timed_out: false,
+        },
+        None => PingReply {
+            bytes: None,
+            time_ms: None,
+            ttl: None,
+            timed_out: true,
+        },
+    }
+}
+
+/// Parse `ping -n` output
+///
+/// The reply block is the run of lines after the "Pinging ..." header up to
+/// the first blank line; statistics are computed from the replies rather than
+/// read from the localized summary.
+fn parse_ping_output(stdout: &str) -> Option<PingResult> {
+    let mut lines = stdout.lines().map(str::trim).skip_while(|l| l.is_empty());
+    let header = lines.next()?;
+    if !header.ends_with(':') {
+        return None;
+    }
+
+    let replies: Vec<PingReply> = lines
+        .take_while(|l| !l.is_empty())
+        .map(parse_ping_line)
+        .collect();
+
+    let times: Vec<u32> = replies.iter().filter_map(|r| r.time_ms).collect();
+    let packets_sent = replies.len() as u32;
+    let packets_received = replies.iter().filter(|r| !r.timed_out).count() as u32;
+
+    Some(PingResult {
+        packets_sent,
+        packets_received,
+        packets_lost: packets_sent - packets_received,
+        min_ms: times.iter().min().copied(),
+        max_ms: times.iter().max().copied(),
+        avg_ms: (!times.is_empty())
+            .then(|| (times.iter().sum::<u32>() as f64 / times.len() as f64).round() as u32),
+        replies,
+    })
+}
+
+/// Run ping and return parsed replies and statistics
+#[tauri::command]
+pub fn run_ping_structured(host: String, count: u32) -> Result<PingResult, AppError> {
+    if (host.as_str(), 0).to_socket_addrs().is_err() {
+        return Err(format!("Could not resolve host {}", host).into());
+    }
+
     let count_str = count.to_string();
-    let output = Command::new("ping")
+    let output = run_cmd("ping", &["-n", &count_str, &host])?;
+
+    parse_ping_output(&output.stdout).ok_or_else(|| {
+        let message = output.stdout.trim();
+        if message.is_empty() {
+            AppError::CommandFailed {
+                stderr: output.stderr.trim().to_string(),
+            }
+        } else {
+            AppError::ParseError(message.to_string())
+        }
+    })
+}
+
+/// Payload of the `ping-reply` event
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PingStreamReply {
+    pub host: String,
+    pub seq: u64,
+    pub time_ms: Option<u32>,
+    pub ttl: Option<u32>,
+    pub timed_out: bool,
+}
+
+/// Send one ping, keeping the child reachable so `stop_ping_stream` can kill it
+fn ping_once(host: &str, slot: &Mutex<Option<Child>>) -> Option<PingReply> {
+    let mut child = Command::new("ping")
         .creation_flags(CREATE_NO_WINDOW)
-        .args(["-n", &count_str, &host])
-        .output()
-        .map_err(|e| format!("Failed to run ping: {}", e))?;
+        .args(["-n", "1", "-w", &PING_STREAM_TIMEOUT_MS.to_string(), host])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let mut pipe = child.stdout.take()?;
+    *slot.lock().unwrap_or_else(|e| e.into_inner()) = Some(child);
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Returns once ping exits, or as soon as a concurrent stop kills it
+    let mut output = String::new();
+    let _ = pipe.read_to_string(&mut output);
+    if let Some(mut child) = slot.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        let _ = child.wait();
+    }
 
-    if !stdout.is_empty() {
-        Ok(stdout.to_string())
-    } else {
-        Err(stderr.to_string())
+    parse_ping_output(&output).and_then(|result| result.replies.into_iter().next())
+}
+
+/// Ping `host` continuously, emitting a `ping-reply` event per probe
+/// Restarts the stream if one is already running for this host
+#[tauri::command]
+pub fn start_ping_stream(
+    app: AppHandle,
+    host: String,
+    interval_ms: Option<u64>,
+) -> Result<String, AppError> {
+    if (host.as_str(), 0).to_socket_addrs().is_err() {
+        return Err(format!("Could not resolve host {}", host).into());
+    }
+    stop_ping_stream(host.clone());
+
+    let interval = Duration::from_millis(interval_ms.unwrap_or(1000).max(100));
+    let stop = Arc::new(AtomicBool::new(false));
+    let child = Arc::new(Mutex::new(None));
+
+    let handle = std::thread::spawn({
+        let (host, stop, child) = (host.clone(), stop.clone(), child.clone());
+        move || {
+            let mut seq = 0u64;
+            while !stop.load(Ordering::Relaxed) {
+                let start_time = Instant::now();
+                let reply = ping_once(&host, &child);
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                seq += 1;
+                let reply = reply.unwrap_or(PingReply {
+                    bytes: None,
+                    time_ms: None,
+                    ttl: None,
+                    timed_out: true,
+                });
+                let payload = PingStreamReply {
+                    host: host.clone(),
+                    seq,
+                    time_ms: reply.time_ms,
+                    ttl: reply.ttl,
+                    timed_out: reply.timed_out,
+                };
+                if let Err(e) = app.emit(PING_REPLY_EVENT, &payload) {
+                    warn!("Failed to emit {}: {}", PING_REPLY_EVENT, e);
+                }
+
+                if let Some(remaining) = interval.checked_sub(start_time.elapsed()) {
+                    std::thread::sleep(remaining);
+                }
+            }
+        }
+    });
+
+    PING_STREAMS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(host.clone(), PingStream { stop, child, handle });
+    Ok(format!("Streaming ping to {}", host))
+}
+
+/// Stop the ping stream for `host`, killing any in-flight ping
+#[tauri::command]
+pub fn stop_ping_stream(host: String) {
+    let stream = PING_STREAMS.lock().unwrap_or_else(|e| e.into_inner()).remove(&host);
+    if let Some(stream) = stream {
+        stream.stop.store(true, Ordering::Relaxed);
+        if let Some(mut child) = stream.child.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        let _ = stream.handle.join();
+    }
+}
+
+/// Stop every running ping stream (used on app exit)
+pub fn stop_all_ping_streams() {
+    let hosts: Vec<String> = PING_STREAMS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys()
+        .cloned()
+        .collect();
+    for host in hosts {
+        stop_ping_stream(host);
     }
 }
 
 /// Run tracert command
 #[tauri::command]
-pub fn run_tracert(host: String) -> Result<String, String> {
-    let output = Command::new("tracert")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-d", &host])
-        .output()
-        .map_err(|e| format!("Failed to run tracert: {}", e))?;
+pub fn run_tracert(host: String) -> Result<String, AppError> {
+    Ok(run_cmd("tracert", &["-d", &host])?.stdout)
+}
+
+/// One hop of a traceroute
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TraceHop {
+    pub hop_number: u32,
+    /// RTT per probe in ms, None on timeout
+    pub probes: [Option<u32>; 3],
+    /// None when every probe timed out
+    pub address: Option<String>,
+}
+
+/// Parse one hop line such as `  3    10 ms     9 ms    <1 ms  10.0.0.1`
+fn parse_trace_hop(line: &str) -> Option<TraceHop> {
+    let mut tokens = line.split_whitespace().peekable();
+    let hop_number: u32 = tokens.next()?.parse().ok()?;
+
+    let mut probes = [None; 3];
+    for probe in probes.iter_mut() {
+        let token = tokens.next()?;
+        if token == "*" {
+            continue;
+        }
+        // "<1 ms" counts as 0, matching ping
+        *probe = if token.starts_with('<') {
+            Some(0)
+        } else {
+            Some(token.trim_end_matches("ms").parse().ok()?)
+        };
+        // "10 ms" is two tokens, "10ms" is one
+        if tokens.peek() == Some(&"ms") {
+            tokens.next();
+        }
+    }
+
+    // "host [ip]" without -d, plain "ip" with -d, or a localized "Request timed out."
+    let rest: Vec<&str> = tokens.collect();
+    let address = rest
+        .iter()
+        .map(|t| t.trim_matches(|c| c == '[' || c == ']'))
+        .find(|t| t.parse::<std::net::IpAddr>().is_ok())
+        .map(|t| t.to_string());
+
+    Some(TraceHop {
+        hop_number,
+        probes,
+        address,
+    })
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+/// Parse `tracert` output into hops, ignoring the header and footer
+fn parse_tracert_output(stdout: &str) -> Vec<TraceHop> {
+    stdout.lines().filter_map(parse_trace_hop).collect()
 }
 
-/// Run nslookup command
+/// Run tracert and return per-hop probe times
 #[tauri::command]
-pub fn run_nslookup(host: String) -> Result<String, String> {
-    let output = Command::new("nslookup")
-        .creation_flags(CREATE_NO_WINDOW)
-        .arg(&host)
-        .output()
-        .map_err(|e| format!("Failed to run nslookup: {}", e))?;
+pub fn run_tracert_structured(
+    host: String,
+    max_hops: Option<u32>,
+) -> Result<Vec<TraceHop>, AppError> {
+    if (host.as_str(), 0).to_socket_addrs().is_err() {
+        return Err(format!("Could not resolve host {}", host).into());
+    }
+
+    let max_hops = max_hops.unwrap_or(30).clamp(1, 255).to_string();
+    let stdout = run_cmd("tracert", &["-d", "-h", &max_hops, &host])?.stdout;
+    let hops = parse_tracert_output(&stdout);
+    if hops.is_empty() {
+        return Err(stdout.trim().into());
+    }
+    Ok(hops)
+}
+
+/// Record types supported by `run_nslookup`
+const NSLOOKUP_TYPES: [&str; 6] = ["A", "AAAA", "MX", "TXT", "CNAME", "NS"];
+
+/// A DNS answer record
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DnsRecord {
+    pub record_type: String,
+    pub value: String,
+    pub ttl: Option<u32>,
+}
+
+/// Parsed nslookup answer
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NslookupResult {
+    /// Record type actually queried
+    pub record_type: String,
+    pub records: Vec<DnsRecord>,
+    /// Set when the requested record type was unrecognized and A was used instead
+    pub note: Option<String>,
+}
+
+/// Map an answer line from `nslookup -debug` to its record type and value
+fn nslookup_answer_value(line: &str) -> Option<(&'static str, String)> {
+    let (key, value) = line.split_once(" = ")?;
+    let value = value.trim().to_string();
+    match key.trim() {
+        "internet address" => Some(("A", value)),
+        "AAAA IPv6 address" => Some(("AAAA", value)),
+        "canonical name" => Some(("CNAME", value)),
+        "nameserver" => Some(("NS", value)),
+        // "MX preference = 10, mail exchanger = smtp.example.com"
+        "MX preference" => {
+            let (preference, exchanger) = value.split_once(", mail exchanger = ")?;
+            Some(("MX", format!("{} {}", preference.trim(), exchanger.trim())))
+        }
+        _ => None,
+    }
+}
+
+/// Parse the ANSWERS sections of `nslookup -debug` output
+///
+/// Each answer starts with `->  name`, followed by indented `key = value`
+/// lines and a `ttl = N (...)` line. TXT data follows `text =` on the next
+/// lines as quoted strings.
+fn parse_nslookup_debug(stdout: &str) -> Vec<DnsRecord> {
+    let mut records: Vec<DnsRecord> = Vec::new();
+    let mut in_answers = false;
+    let mut in_txt = false;
+
+    for raw in stdout.lines() {
+        let line = raw.trim();
+        if line == "ANSWERS:" {
+            in_answers = true;
+            continue;
+        }
+        if line.starts_with("---") || (line.ends_with(':') && line.chars().all(|c| !c.is_lowercase())) {
+            in_answers = false;
+            in_txt = false;
+            continue;
+        }
+        if !in_answers || line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with("->") {
+            in_txt = false;
+        } else if line == "text =" {
+            in_txt = true;
+            records.push(DnsRecord {
+                record_type: "TXT".to_string(),
+                value: String::new(),
+                ttl: None,
+            });
+        } else if let Some(ttl) = line.strip_prefix("ttl = ") {
+            in_txt = false;
+            if let Some(record) = records.last_mut() {
+                record.ttl = ttl.split_whitespace().next().and_then(|t| t.parse().ok());
+            }
+        } else if in_txt && line.starts_with('"') {
+            if let Some(record) = records.last_mut() {
+                record.value.push_str(line.trim_matches('"'));
+            }
+        } else if let Some((record_type, value)) = nslookup_answer_value(line) {
+            records.push(DnsRecord {
+                record_type: record_type.to_string(),
+                value,
+                ttl: None,
+            });
+        }
+    }
+
+    records
+}
+
+/// Run nslookup for a record type (A, AAAA, MX, TXT, CNAME, NS) against an optional server
+#[tauri::command]
+pub fn run_nslookup(
+    host: String,
+    record_type: Option<String>,
+    server: Option<String>,
+) -> Result<NslookupResult, AppError> {
+    let requested = record_type.unwrap_or_else(|| "A".to_string()).trim().to_uppercase();
+    let (query_type, note) = if NSLOOKUP_TYPES.contains(&requested.as_str()) {
+        (requested, None)
+    } else {
+        (
+            "A".to_string(),
+            Some(format!("Unsupported record type '{}', queried A instead", requested)),
+        )
+    };
+
+    let type_arg = format!("-type={}", query_type);
+    let mut args = vec!["-debug", type_arg.as_str(), host.as_str()];
+    if let Some(server) = server.as_deref().filter(|s| !s.trim().is_empty()) {
+        args.push(server);
+    }
+
+    let output = run_cmd("nslookup", &args)?;
+
+    let records = parse_nslookup_debug(&output.stdout);
+    if records.is_empty() {
+        if let Some(error) = output.stderr.lines().find(|l| l.contains("***")) {
+            return Err(error.trim_start_matches('*').trim().into());
+        }
+    }
+
+    Ok(NslookupResult {
+        record_type: query_type,
+        records,
+        note,
+    })
+}
+
+/// Concurrent connection attempts during a port scan
+const PORT_SCAN_WORKERS: usize = 32;
+
+/// Result of probing one TCP port
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PortStatus {
+    pub port: u16,
+    pub open: bool,
+    pub latency_ms: Option<u64>,
+}
+
+/// Check which TCP ports accept connections, using a bounded pool of worker threads
+#[tauri::command]
+pub fn scan_ports(
+    host: String,
+    ports: Vec<u16>,
+    timeout_ms: u64,
+) -> Result<Vec<PortStatus>, AppError> {
+    let ip = (host.as_str(), 0)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("No address found for {}", host))?
+        .ip();
+    let timeout = Duration::from_millis(timeout_ms.max(1));
+
+    let mut ports = ports;
+    ports.sort_unstable();
+    ports.dedup();
+
+    Ok(map_concurrent(&ports, PORT_SCAN_WORKERS, |&port| {
+        let start_time = Instant::now();
+        let open = TcpStream::connect_timeout(&SocketAddr::new(ip, port), timeout).is_ok();
+        PortStatus {
+            port,
+            open,
+            latency_ms: open.then(|| start_time.elapsed().as_millis() as u64),
+        }
+    }))
+}
+
+/// Concurrent pings during a network sweep
+const PING_SWEEP_WORKERS: usize = 64;
+
+/// Smallest prefix a sweep accepts (/22 = 1022 hosts)
+const PING_SWEEP_MIN_PREFIX: u8 = 22;
+
+/// Per-host reply timeout for a network sweep
+const PING_SWEEP_TIMEOUT_MS: u32 = 500;
+
+/// Result of pinging one host in a sweep
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HostStatus {
+    pub ip: String,
+    pub alive: bool,
+    pub latency_ms: Option<u32>,
+    /// PTR name, filled in when the sweep is asked to resolve names
+    pub hostname: Option<String>,
+}
+
+/// Host addresses in a CIDR block, skipping the network and broadcast
+/// addresses except for /31 and /32 where every address is a host
+fn sweep_hosts(cidr: &str) -> Result<Vec<Ipv4Addr>, String> {
+    let (addr, prefix) = parse_cidr(cidr)?;
+    if prefix < PING_SWEEP_MIN_PREFIX {
+        return Err(format!(
+            "Range /{} is too large to sweep (maximum is /{})",
+            prefix, PING_SWEEP_MIN_PREFIX
+        ));
+    }
+
+    let mask = prefix_mask(prefix);
+    let first = u32::from(addr) & mask;
+    let last = first | !mask;
+    let hosts = if prefix >= 31 {
+        first..=last
+    } else {
+        first + 1..=last - 1
+    };
+    Ok(hosts.map(Ipv4Addr::from).collect())
+}
+
+/// Send a single echo request, returning the reply if the host answered
+pub(crate) fn ping_reply(host: &str, timeout_ms: u32) -> Option<PingReply> {
+    let timeout = timeout_ms.to_string();
+    run_cmd("ping", &["-n", "1", "-w", &timeout, host])
+        .ok()
+        .and_then(|output| parse_ping_output(&output.stdout))
+        .and_then(|result| result.replies.into_iter().find(|r| !r.timed_out))
+}
+
+/// Send a single echo request and report whether the host replied
+fn ping_host(ip: Ipv4Addr) -> HostStatus {
+    let ip = ip.to_string();
+    let reply = ping_reply(&ip, PING_SWEEP_TIMEOUT_MS);
+
+    HostStatus {
+        ip,
+        alive: reply.is_some(),
+        latency_ms: reply.and_then(|r| r.time_ms),
+        hostname: None,
+    }
+}
+
+/// Ping every host in a CIDR block (e.g. 192.168.1.0/24), using a bounded pool of worker threads
+/// With `resolve_names`, alive hosts are annotated with their reverse DNS name
+#[tauri::command]
+pub fn ping_sweep(cidr: String, resolve_names: Option<bool>) -> Result<Vec<HostStatus>, AppError> {
+    let targets = sweep_hosts(&cidr)?;
+    let mut hosts = map_concurrent(&targets, PING_SWEEP_WORKERS, |&ip| ping_host(ip));
+
+    if resolve_names.unwrap_or(false) {
+        let alive: Vec<&mut HostStatus> = hosts.iter_mut().filter(|h| h.alive).collect();
+        let names = map_concurrent(&alive, REVERSE_DNS_WORKERS, |host| {
+            host.ip.parse().ok().and_then(lookup_ptr)
+        });
+        for (host, name) in alive.into_iter().zip(names) {
+            host.hostname = name;
+        }
+    }
+
+    Ok(hosts)
+}
+
+/// Concurrent PTR lookups in a batch
+const REVERSE_DNS_WORKERS: usize = 16;
+
+/// Reverse lookup result for one address
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReverseDnsEntry {
+    pub ip: String,
+    pub hostname: Option<String>,
+}
+
+/// Name queried for a PTR lookup: "4.3.2.1.in-addr.arpa" or the nibble form under "ip6.arpa"
+fn ptr_query_name(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, c, d] = v4.octets();
+            format!("{}.{}.{}.{}.in-addr.arpa", d, c, b, a)
+        }
+        IpAddr::V6(v6) => {
+            let nibbles: Vec<String> = v6
+                .octets()
+                .iter()
+                .rev()
+                .flat_map(|byte| [byte & 0x0F, byte >> 4])
+                .map(|nibble| format!("{:x}", nibble))
+                .collect();
+            format!("{}.ip6.arpa", nibbles.join("."))
+        }
+    }
+}
+
+/// PTR name for `query` from `nslookup -debug` output
+///
+/// nslookup first reverse-resolves its own server, so only answers whose
+/// `->  owner` line matches the queried name are considered.
+fn parse_ptr_answer(stdout: &str, query: &str) -> Option<String> {
+    let mut owner_matches = false;
+
+    for raw in stdout.lines() {
+        let line = raw.trim();
+        if let Some(owner) = line.strip_prefix("->") {
+            owner_matches = owner.trim().trim_end_matches('.').eq_ignore_ascii_case(query);
+        } else if let Some(name) = line.strip_prefix("name = ") {
+            if owner_matches {
+                return Some(name.trim().trim_end_matches('.').to_string());
+            }
+        } else if line.starts_with("---") {
+            owner_matches = false;
+        }
+    }
+
+    None
+}
+
+/// Look up the PTR name of an address; any failure counts as "no name"
+fn lookup_ptr(ip: IpAddr) -> Option<String> {
+    let ip_str = ip.to_string();
+    let output = run_cmd("nslookup", &["-debug", "-type=PTR", &ip_str]).ok()?;
+    parse_ptr_answer(&output.stdout, &ptr_query_name(ip))
+}
+
+/// Reverse DNS (PTR) lookup; returns None when the address has no name
+#[tauri::command]
+pub fn reverse_dns(ip: String) -> Result<Option<String>, AppError> {
+    let addr: IpAddr = ip
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid IP address: {}", ip))?;
+    Ok(lookup_ptr(addr))
+}
+
+/// Reverse DNS for many addresses concurrently, keeping input order
+/// Invalid or non-resolving addresses get no hostname instead of failing the batch
+#[tauri::command]
+pub fn reverse_dns_many(ips: Vec<String>) -> Result<Vec<ReverseDnsEntry>, AppError> {
+    Ok(map_concurrent(&ips, REVERSE_DNS_WORKERS, |ip| ReverseDnsEntry {
+        ip: ip.clone(),
+        hostname: ip.trim().parse().ok().and_then(lookup_ptr),
+    }))
+}
+
+/// Largest ICMP payload that fits a standard 1500-byte Ethernet MTU
+const PMTU_MAX_PAYLOAD: u32 = 1472;
+
+/// IPv4 (20) + ICMP (8) header bytes added to the payload
+const PMTU_HEADER_BYTES: u32 = 28;
+
+/// Enough probes to binary-search 0..=1472 exactly
+const PMTU_MAX_PROBES: u32 = 12;
+
+/// Outcome of one don't-fragment ping
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DfProbe {
+    Passed,
+    NeedsFragmentation,
+    NoReply,
+}
+
+/// Path MTU discovery result
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PathMtuResult {
+    pub host: String,
+    /// Largest payload that got through plus 28 header bytes
+    pub mtu: u32,
+    pub probes: u32,
+    /// False if the host stopped replying or the probe limit was hit before
+    /// the search converged, so `mtu` is a lower bound
+    pub complete: bool,
+}
+
+fn classify_df_ping(stdout: &str) -> DfProbe {
+    let passed = parse_ping_output(stdout)
+        .map(|result| result.packets_received > 0)
+        .unwrap_or(false);
+    if passed {
+        DfProbe::Passed
+    } else if stdout.to_lowercase().contains("fragmented") {
+        DfProbe::NeedsFragmentation
+    } else {
+        DfProbe::NoReply
+    }
+}
+
+/// Binary-search the largest payload that passes, starting at 1472.
+/// Returns (best payload, probes used, converged)
+fn search_path_mtu(mut probe: impl FnMut(u32) -> DfProbe) -> (Option<u32>, u32, bool) {
+    let mut best = None;
+    let (mut low, mut high) = (0, PMTU_MAX_PAYLOAD);
+    let mut probes = 0;
+
+    while probes < PMTU_MAX_PROBES {
+        let size = if probes == 0 { high } else { (low + high).div_ceil(2) };
+        probes += 1;
+        match probe(size) {
+            DfProbe::Passed => {
+                best = Some(size);
+                low = size;
+            }
+            DfProbe::NeedsFragmentation => high = size - 1,
+            DfProbe::NoReply => return (best, probes, false),
+        }
+        if low >= high {
+            return (best, probes, true);
+        }
+    }
+    (best, probes, false)
+}
+
+/// Find the path MTU to `host` by binary-searching don't-fragment ping sizes
+#[tauri::command]
+pub fn discover_path_mtu(host: String) -> Result<PathMtuResult, AppError> {
+    if (host.as_str(), 0).to_socket_addrs().is_err() {
+        return Err(format!("Could not resolve host {}", host).into());
+    }
+
+    let (best, probes, complete) = search_path_mtu(|size| {
+        let size = size.to_string();
+        run_cmd("ping", &["-n", "1", "-w", "2000", "-f", "-l", &size, &host])
+            .map(|output| classify_df_ping(&output.stdout))
+            .unwrap_or(DfProbe::NoReply)
+    });
+
+    let payload = best.ok_or_else(|| format!("{} did not reply to don't-fragment pings", host))?;
+    Ok(PathMtuResult {
+        host,
+        mtu: payload + PMTU_HEADER_BYTES,
+        probes,
+        complete,
+    })
+}
+
+/// Root whois server that refers each TLD / IP block to its authoritative server
+const WHOIS_ROOT_SERVER: &str = "whois.iana.org";
+
+/// Connect and read timeout for each whois query
+const WHOIS_TIMEOUT_MS: u64 = 5000;
+
+/// Whois response with the commonly needed fields pulled out
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WhoisResult {
+    pub query: String,
+    /// Server that gave the final answer
+    pub server: String,
+    pub raw: String,
+    /// Registrar for domains, owning organisation for IP blocks
+    pub registrar: Option<String>,
+    pub creation_date: Option<String>,
+    pub expiry_date: Option<String>,
+    pub name_servers: Vec<String>,
+}
+
+/// Send one whois query (RFC 3912) and read the reply until the server closes the connection
+fn whois_query(server: &str, query: &str) -> Result<String, String> {
+    let timeout = Duration::from_millis(WHOIS_TIMEOUT_MS);
+    let addr = (server, 43)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", server, e))?
+        .next()
+        .ok_or_else(|| format!("No address found for {}", server))?;
+
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)
+        .map_err(|e| format!("Failed to connect to {}: {}", server, e))?;
+    stream.set_read_timeout(Some(timeout)).ok();
+    stream.set_write_timeout(Some(timeout)).ok();
+    stream
+        .write_all(format!("{}\r\n", query).as_bytes())
+        .map_err(|e| format!("Failed to send whois query to {}: {}", server, e))?;
+
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .map_err(|e| format!("Timed out reading whois reply from {}: {}", server, e))?;
+    Ok(String::from_utf8_lossy(&response).to_string())
+}
+
+/// Value of the first `key: value` line whose key matches one of `keys` (case-insensitive)
+fn whois_field(text: &str, keys: &[&str]) -> Option<String> {
+    text.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let value = value.trim();
+        (keys.iter().any(|k| key.trim().eq_ignore_ascii_case(k)) && !value.is_empty())
+            .then(|| value.to_string())
+    })
+}
+
+/// Authoritative server named in an IANA reply
+fn whois_referral(text: &str) -> Option<String> {
+    whois_field(text, &["refer", "whois"])
+}
+
+fn parse_whois(query: &str, server: &str, raw: String) -> WhoisResult {
+    let mut name_servers: Vec<String> = Vec::new();
+    for line in raw.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim();
+            let value = value.trim().to_lowercase();
+            if (key.eq_ignore_ascii_case("Name Server") || key.eq_ignore_ascii_case("nserver"))
+                && !value.is_empty()
+                && !name_servers.contains(&value)
+            {
+                name_servers.push(value);
+            }
+        }
+    }
+
+    WhoisResult {
+        query: query.to_string(),
+        server: server.to_string(),
+        registrar: whois_field(&raw, &["Registrar", "OrgName", "org-name", "Organization"]),
+        creation_date: whois_field(&raw, &["Creation Date", "created", "RegDate", "Registered on"]),
+        expiry_date: whois_field(
+            &raw,
+            &["Registry Expiry Date", "Registrar Registration Expiration Date", "Expiry Date", "expires", "paid-till"],
+        ),
+        name_servers,
+        raw,
+    }
+}
+
+/// Look up a domain or IP address: ask IANA for the authoritative server, then query it
+#[tauri::command]
+pub fn run_whois(query: String) -> Result<WhoisResult, AppError> {
+    let query = query.trim().trim_end_matches('.').to_string();
+    if query.is_empty() {
+        return Err("Whois query cannot be empty".into());
+    }
+
+    let root = whois_query(WHOIS_ROOT_SERVER, &query)?;
+    let result = match whois_referral(&root) {
+        Some(server) => parse_whois(&query, &server, whois_query(&server, &query)?),
+        None => parse_whois(&query, WHOIS_ROOT_SERVER, root),
+    };
+    Ok(result)
+}
+
+/// An ARP cache entry
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ArpEntry {
+    pub ip_address: String,
+    pub mac_address: String,
+    /// "dynamic" or "static"
+    pub entry_type: String,
+    /// Local address of the interface the entry belongs to
+    pub interface: String,
+}
+
+/// Parse `arp -a` output, tagging each entry with its interface block
+///
+/// Interface separators are recognised by their `addr --- 0xN` shape and
+/// entries by a leading IP address, so localized headers are skipped.
+fn parse_arp_output(stdout: &str) -> Vec<ArpEntry> {
+    let mut entries = Vec::new();
+    let mut interface = String::new();
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    for line in stdout.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if let Some(pos) = tokens.iter().position(|t| *t == "---") {
+            interface = pos
+                .checked_sub(1)
+                .and_then(|i| tokens.get(i))
+                .map(|t| t.to_string())
+                .unwrap_or_default();
+            continue;
+        }
+
+        if let [ip, mac, entry_type, ..] = tokens.as_slice() {
+            if ip.parse::<std::net::IpAddr>().is_ok() && mac.split('-').count() == 6 {
+                entries.push(ArpEntry {
+                    ip_address: ip.to_string(),
+                    mac_address: mac.to_uppercase(),
+                    entry_type: entry_type.to_string(),
+                    interface: interface.clone(),
+                });
+            }
+        }
+    }
+
+    entries
+}
+
+/// Read the local ARP cache, grouped by interface
+#[tauri::command]
+pub fn get_arp_table() -> Result<Vec<ArpEntry>, AppError> {
+    let output = run_cmd("arp", &["-a"])?;
+    Ok(parse_arp_output(&output.stdout))
 }
 
 /// Run netstat command
 #[tauri::command]
-pub fn run_netstat(option: String) -> Result<String, String> {
+pub fn run_netstat(option: String) -> Result<String, AppError> {
     let args: Vec<&str> = match option.as_str() {
         "all" => vec!["-a", "-n"],
         "listening" => vec!["-a", "-n", "-p", "TCP"],
@@ -60,59 +963,1728 @@ pub fn run_netstat(option: String) -> Result<String, String> {
         _ => vec!["-a"],
     };
 
-    let output = Command::new("netstat")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(&args)
-        .output()
-        .map_err(|e| format!("Failed to run netstat: {}", e))?;
+    Ok(run_cmd("netstat", &args)?.stdout)
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+/// A TCP or UDP endpoint with its owning process
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Connection {
+    /// "TCP" or "UDP"
+    pub protocol: String,
+    pub local_addr: String,
+    pub remote_addr: String,
+    /// TCP state as netstat prints it (localized); None for UDP
+    pub state: Option<String>,
+    pub pid: u32,
+    pub process_name: Option<String>,
 }
 
-/// Get hostname
+/// Parse one `netstat -a -n -o` line; headers and blank lines return None
+/// TCP lines have a state column before the PID, UDP lines don't
+fn parse_netstat_line(line: &str) -> Option<Connection> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let (protocol, state) = match (fields.first()?.to_uppercase().as_str(), fields.len()) {
+        ("TCP", 5) => ("TCP", Some(fields[3].to_string())),
+        ("UDP", 4) => ("UDP", None),
+        _ => return None,
+    };
+
+    Some(Connection {
+        protocol: protocol.to_string(),
+        local_addr: fields[1].to_string(),
+        remote_addr: fields[2].to_string(),
+        state,
+        pid: fields.last()?.parse().ok()?,
+        process_name: None,
+    })
+}
+
+/// Map PIDs to image names from `tasklist /fo csv /nh` ("name","pid",...)
+fn parse_tasklist_csv(stdout: &str) -> HashMap<u32, String> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().trim_matches('"').split("\",\"");
+            let name = fields.next()?;
+            let pid = fields.next()?.parse().ok()?;
+            Some((pid, name.to_string()))
+        })
+        .collect()
+}
+
+/// List TCP and UDP connections/listeners with the process that owns each one
 #[tauri::command]
-pub fn get_hostname() -> Result<String, String> {
-    let output = Command::new("hostname")
-        .creation_flags(CREATE_NO_WINDOW)
-        .output()
-        .map_err(|e| format!("Failed to get hostname: {}", e))?;
+pub fn get_connections_with_pid() -> Result<Vec<Connection>, AppError> {
+    let netstat = run_cmd("netstat", &["-a", "-n", "-o"])?.into_result()?;
+    let mut connections: Vec<Connection> = netstat.lines().filter_map(parse_netstat_line).collect();
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.trim().to_string())
+    // Names are best effort; a failed tasklist still leaves the PIDs
+    let processes = run_cmd("tasklist", &["/fo", "csv", "/nh"])
+        .map(|output| parse_tasklist_csv(&output.stdout))
+        .unwrap_or_default();
+    for connection in &mut connections {
+        connection.process_name = processes.get(&connection.pid).cloned();
+    }
+
+    Ok(connections)
+}
+
+/// PIDs of the System Idle Process and the kernel's System process
+const PROTECTED_PIDS: [u32; 2] = [0, 4];
+
+/// Forcefully terminate a process by PID (e.g. one holding a port)
+/// System processes may require administrator privileges
+#[tauri::command]
+pub fn kill_process(pid: u32) -> Result<String, AppError> {
+    use windows::Win32::Foundation::{CloseHandle, ERROR_ACCESS_DENIED, ERROR_INVALID_PARAMETER};
+    use windows::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+
+    if PROTECTED_PIDS.contains(&pid) {
+        return Err(format!("Refusing to terminate the System process (PID {})", pid).into());
+    }
+    if pid == std::process::id() {
+        return Err("Refusing to terminate this application".into());
+    }
+
+    // SAFETY: the handle is only used after OpenProcess succeeds and is closed before returning
+    let result = unsafe {
+        OpenProcess(PROCESS_TERMINATE, false, pid).and_then(|handle| {
+            let terminated = TerminateProcess(handle, 1);
+            let _ = CloseHandle(handle);
+            terminated
+        })
+    };
+
+    match result {
+        Ok(()) => Ok(format!("Terminated process {}", pid)),
+        Err(e) if e.code() == ERROR_INVALID_PARAMETER.to_hresult() => {
+            Err(AppError::NotFound(format!("No process with PID {}", pid)))
+        }
+        Err(e) if e.code() == ERROR_ACCESS_DENIED.to_hresult() => {
+            if crate::admin::is_admin() {
+                Err(format!("Windows refused to terminate protected process {}", pid).into())
+            } else {
+                Err(AppError::not_elevated(&format!("terminate process {}", pid)))
+            }
+        }
+        Err(e) => Err(format!("Failed to terminate process {}: {}", pid, e.message()).into()),
+    }
+}
+
+/// Get hostname
+#[tauri::command]
+pub fn get_hostname() -> Result<String, AppError> {
+    let output = run_cmd("hostname", &[])?;
+    Ok(output.stdout.trim().to_string())
 }
 
 /// Get system network info
 #[tauri::command]
-pub fn get_network_info() -> Result<String, String> {
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args([
-            "-NoProfile",
-            "-Command",
-            r#"
+pub fn get_network_info() -> Result<String, AppError> {
+    let script = r#"
             $info = @{}
             $info['Hostname'] = $env:COMPUTERNAME
             $info['Username'] = $env:USERNAME
-            $info['Domain'] = (Get-WmiObject Win32_ComputerSystem).Domain
+            $info['Domain'] = (Get-CimInstance Win32_ComputerSystem).Domain
             $info | ConvertTo-Json
-            "#,
-        ])
-        .output()
-        .map_err(|e| format!("Failed to get network info: {}", e))?;
+            "#;
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    Ok(run_powershell(script)?)
 }
 
-/// Check internet connectivity
-#[tauri::command]
-pub fn check_internet() -> Result<bool, String> {
-    let output = Command::new("ping")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-n", "1", "-w", "3000", "8.8.8.8"])
-        .output()
-        .map_err(|e| format!("Failed to check internet: {}", e))?;
+/// Default timeout for HTTP reachability checks
+const HTTP_CHECK_TIMEOUT_MS: u64 = 5000;
+
+/// Redirects followed before giving up
+const HTTP_CHECK_MAX_REDIRECTS: u32 = 5;
+
+/// Windows' own connectivity probe; plain HTTP so captive portals can intercept it
+const INTERNET_CHECK_URL: &str = "http://www.msftconnecttest.com/connecttest.txt";
+
+/// Why an HTTP check got no response
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpFailure {
+    /// The host name didn't resolve
+    Dns,
+    /// The TLS handshake failed, e.g. a portal or proxy intercepting HTTPS
+    Tls,
+    /// No response within the timeout
+    Timeout,
+    /// The connection was refused or dropped
+    Connection,
+    TooManyRedirects,
+    Other,
+}
+
+/// Result of an HTTP reachability check
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HttpCheck {
+    pub url: String,
+    /// True when the server answered with any HTTP status
+    pub reachable: bool,
+    pub status_code: Option<u16>,
+    pub elapsed_ms: u64,
+    /// URL after following redirects
+    pub final_url: Option<String>,
+    pub failure: Option<HttpFailure>,
+    pub error: Option<String>,
+}
+
+/// Prefix bare host names with https://
+fn normalize_http_url(url: &str) -> String {
+    let url = url.trim();
+    if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("https://{}", url)
+    }
+}
+
+/// Blocking HTTP client that trusts the Windows certificate store
+fn http_agent(timeout: Duration, redirects: u32) -> Result<ureq::Agent, String> {
+    Ok(ureq::AgentBuilder::new()
+        .tls_config(https_client_config()?)
+        .timeout(timeout)
+        .redirects(redirects)
+        .build())
+}
+
+/// Classify a transport error by walking its source chain for TLS and timeout causes
+fn classify_transport(error: &ureq::Transport) -> HttpFailure {
+    match error.kind() {
+        ureq::ErrorKind::Dns => return HttpFailure::Dns,
+        ureq::ErrorKind::TooManyRedirects => return HttpFailure::TooManyRedirects,
+        _ => {}
+    }
+
+    let mut source: Option<&(dyn std::error::Error + 'static)> = std::error::Error::source(error);
+    while let Some(e) = source {
+        if e.is::<rustls::Error>() {
+            return HttpFailure::Tls;
+        }
+        if let Some(io) = e.downcast_ref::<std::io::Error>() {
+            if matches!(io.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock) {
+                return HttpFailure::Timeout;
+            }
+            // io::Error::source skips the wrapped error, so check it directly
+            if io.get_ref().is_some_and(|inner| inner.is::<rustls::Error>()) {
+                return HttpFailure::Tls;
+            }
+        }
+        source = e.source();
+    }
+
+    match error.kind() {
+        ureq::ErrorKind::ConnectionFailed | ureq::ErrorKind::Io => HttpFailure::Connection,
+        _ => HttpFailure::Other,
+    }
+}
+
+/// User-facing message for a failed check
+fn http_failure_message(failure: HttpFailure, host: &str, timeout: Duration, detail: &str) -> String {
+    match failure {
+        HttpFailure::Dns => format!("Could not resolve {}: {}", host, detail),
+        HttpFailure::Tls => format!(
+            "TLS handshake with {} failed (a captive portal or proxy may be intercepting HTTPS): {}",
+            host, detail
+        ),
+        HttpFailure::Timeout => format!("No response from {} within {}ms", host, timeout.as_millis()),
+        HttpFailure::Connection => format!("Could not connect to {}: {}", host, detail),
+        HttpFailure::TooManyRedirects => format!("{} redirected more than {} times", host, HTTP_CHECK_MAX_REDIRECTS),
+        HttpFailure::Other => detail.to_string(),
+    }
+}
+
+/// Send a HEAD request (falling back to GET when HEAD isn't allowed) and follow redirects
+fn http_check(url: &str, timeout: Duration) -> Result<HttpCheck, String> {
+    let agent = http_agent(timeout, HTTP_CHECK_MAX_REDIRECTS)?;
+    let start_time = Instant::now();
+
+    let mut result = agent.head(url).call();
+    if let Err(ureq::Error::Status(405 | 501, _)) = result {
+        result = agent.get(url).call();
+    }
+    let elapsed_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(ureq::Error::Transport(error)) => {
+            if matches!(
+                error.kind(),
+                ureq::ErrorKind::InvalidUrl | ureq::ErrorKind::UnknownScheme
+            ) {
+                return Err(format!("Invalid URL {}: {}", url, error));
+            }
+            let failure = classify_transport(&error);
+            let host = error.url().and_then(|u| u.host_str()).unwrap_or(url);
+            let detail = error
+                .message()
+                .map(str::to_string)
+                .or_else(|| std::error::Error::source(&error).map(|e| e.to_string()))
+                .unwrap_or_else(|| error.kind().to_string());
+            return Ok(HttpCheck {
+                url: url.to_string(),
+                reachable: false,
+                status_code: None,
+                elapsed_ms,
+                final_url: None,
+                failure: Some(failure),
+                error: Some(http_failure_message(failure, host, timeout, &detail)),
+            });
+        }
+    };
 
-    Ok(output.status.success())
+    Ok(HttpCheck {
+        url: url.to_string(),
+        reachable: true,
+        status_code: Some(response.status()),
+        elapsed_ms,
+        final_url: Some(response.get_url().to_string()),
+        failure: None,
+        error: None,
+    })
+}
+
+/// Check whether a URL answers over HTTP(S), reporting status, timing and the final URL
+/// DNS, TLS and timeout failures are reported in the result rather than as errors
+#[tauri::command]
+pub fn check_http(url: String, timeout_ms: Option<u64>) -> Result<HttpCheck, AppError> {
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(HTTP_CHECK_TIMEOUT_MS).max(1));
+    Ok(http_check(&normalize_http_url(&url), timeout)?)
+}
+
+/// Body served by the connectivity probe when nothing intercepts it
+const INTERNET_CHECK_BODY: &str = "Microsoft Connect Test";
+
+/// Timeout for the captive portal probe
+const CAPTIVE_PORTAL_TIMEOUT_MS: u64 = 3000;
+
+/// Largest probe body read; the expected one is a few bytes
+const CAPTIVE_PORTAL_MAX_BODY: u64 = 64 * 1024;
+
+/// Whether traffic is being intercepted by a captive portal
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CaptivePortalStatus {
+    pub behind_portal: bool,
+    /// Login page the portal redirected to, when it used a redirect
+    pub redirect_url: Option<String>,
+}
+
+/// Compare the probe response against what the real endpoint serves
+/// Portals either redirect the request or answer it with their own page
+fn classify_portal_response(status: u16, location: Option<&str>, body: &str) -> CaptivePortalStatus {
+    if (300..400).contains(&status) {
+        return CaptivePortalStatus {
+            behind_portal: true,
+            redirect_url: location.map(|l| l.trim().to_string()).filter(|l| !l.is_empty()),
+        };
+    }
+    CaptivePortalStatus {
+        behind_portal: status != 200 || body.trim() != INTERNET_CHECK_BODY,
+        redirect_url: None,
+    }
+}
+
+/// Detect a captive portal (hotel/cafe login page) by fetching Windows' connectivity
+/// probe without following redirects
+#[tauri::command]
+pub fn detect_captive_portal() -> Result<CaptivePortalStatus, AppError> {
+    let timeout = Duration::from_millis(CAPTIVE_PORTAL_TIMEOUT_MS);
+    let agent = http_agent(timeout, 0)?;
+
+    let response = match agent.get(INTERNET_CHECK_URL).call() {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(ureq::Error::Transport(error)) => {
+            let failure = classify_transport(&error);
+            let message = http_failure_message(
+                failure,
+                "www.msftconnecttest.com",
+                timeout,
+                &error.to_string(),
+            );
+            return Err(match failure {
+                HttpFailure::Timeout => AppError::Timeout(message),
+                _ => AppError::Other(message),
+            });
+        }
+    };
+
+    let status = response.status();
+    let location = response.header("Location").map(str::to_string);
+    let mut body = String::new();
+    if (200..300).contains(&status) {
+        let _ = response
+            .into_reader()
+            .take(CAPTIVE_PORTAL_MAX_BODY)
+            .read_to_string(&mut body);
+    }
+
+    Ok(classify_portal_response(status, location.as_deref(), &body))
+}
+
+/// Check internet connectivity
+/// Falls back to an HTTP probe so networks that block ICMP aren't reported offline
+#[tauri::command]
+pub fn check_internet() -> Result<bool, AppError> {
+    let output = run_cmd("ping", &["-n", "1", "-w", "3000", "8.8.8.8"])?;
+    if output.success {
+        return Ok(true);
+    }
+
+    let timeout = Duration::from_millis(3000);
+    Ok(http_check(INTERNET_CHECK_URL, timeout).is_ok_and(|check| check.reachable))
+}
+
+/// Event emitted by the connectivity monitor after every round of probes
+const CONNECTIVITY_EVENT: &str = "connectivity-status";
+
+/// Targets probed when the caller doesn't pass any
+const DEFAULT_CONNECTIVITY_TARGETS: [&str; 2] = ["8.8.8.8", "1.1.1.1"];
+
+/// Shortest allowed interval between connectivity rounds
+const MIN_CONNECTIVITY_INTERVAL_MS: u64 = 1000;
+
+/// Per-target reply timeout for connectivity probes
+const CONNECTIVITY_PROBE_TIMEOUT_MS: u32 = 2000;
+
+/// Running connectivity monitor: stop flag and worker thread
+struct ConnectivityMonitor {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+lazy_static! {
+    static ref CONNECTIVITY_MONITOR: Mutex<Option<ConnectivityMonitor>> = Mutex::new(None);
+}
+
+/// Probe result for one monitored target
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TargetStatus {
+    pub target: String,
+    pub alive: bool,
+    pub latency_ms: Option<u32>,
+}
+
+/// Payload of the `connectivity-status` event
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConnectivityStatus {
+    /// True when at least one target replied
+    pub online: bool,
+    /// Fastest reply among the targets that answered
+    pub latency_ms: Option<u32>,
+    pub targets: Vec<TargetStatus>,
+    /// Rounds in a row where no target replied
+    pub consecutive_failures: u32,
+    /// Time since the first failed round of the current outage
+    pub offline_for_ms: Option<u64>,
+}
+
+/// Outage bookkeeping across monitor rounds
+#[derive(Default)]
+struct OutageTracker {
+    consecutive_failures: u32,
+    offline_since: Option<Instant>,
+}
+
+impl OutageTracker {
+    /// Record one round and return (consecutive failures, time offline)
+    fn record(&mut self, online: bool, now: Instant) -> (u32, Option<Duration>) {
+        if online {
+            *self = OutageTracker::default();
+            return (0, None);
+        }
+        self.consecutive_failures += 1;
+        let since = *self.offline_since.get_or_insert(now);
+        (self.consecutive_failures, Some(now.duration_since(since)))
+    }
+}
+
+/// Ping every target once; the network counts as up if any of them replies
+fn check_connectivity(targets: &[String], tracker: &mut OutageTracker) -> ConnectivityStatus {
+    let targets = map_concurrent(targets, targets.len(), |target| {
+        let reply = ping_reply(target, CONNECTIVITY_PROBE_TIMEOUT_MS);
+        TargetStatus {
+            target: target.clone(),
+            alive: reply.is_some(),
+            latency_ms: reply.and_then(|r| r.time_ms),
+        }
+    });
+
+    let online = targets.iter().any(|t| t.alive);
+    let (consecutive_failures, offline_for) = tracker.record(online, Instant::now());
+    ConnectivityStatus {
+        online,
+        latency_ms: targets.iter().filter_map(|t| t.latency_ms).min(),
+        targets,
+        consecutive_failures,
+        offline_for_ms: offline_for.map(|d| d.as_millis() as u64),
+    }
+}
+
+/// Ping `targets` (default 8.8.8.8 and 1.1.1.1) every `interval_ms`, emitting
+/// `connectivity-status` after each round. Restarts the monitor if already running,
+/// which waits for the old worker's current round, so this runs off the main thread.
+#[tauri::command(async)]
+pub fn start_connectivity_monitor(
+    app: AppHandle,
+    interval_ms: Option<u64>,
+    targets: Option<Vec<String>>,
+) -> Result<String, AppError> {
+    let mut targets: Vec<String> = targets
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    if targets.is_empty() {
+        targets = DEFAULT_CONNECTIVITY_TARGETS.iter().map(|t| t.to_string()).collect();
+    }
+    halt_connectivity_monitor();
+
+    let interval = Duration::from_millis(
+        interval_ms.unwrap_or(5000).max(MIN_CONNECTIVITY_INTERVAL_MS),
+    );
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    let message = format!(
+        "Monitoring {} every {}ms",
+        targets.join(", "),
+        interval.as_millis()
+    );
+
+    let handle = std::thread::spawn(move || {
+        let mut tracker = OutageTracker::default();
+
+        while !thread_stop.load(Ordering::Relaxed) {
+            let deadline = Instant::now() + interval;
+            let status = check_connectivity(&targets, &mut tracker);
+            if thread_stop.load(Ordering::Relaxed) {
+                break;
+            }
+            if let Err(e) = app.emit(CONNECTIVITY_EVENT, &status) {
+                warn!("Failed to emit {}: {}", CONNECTIVITY_EVENT, e);
+            }
+
+            // Sleep in short steps so stop requests are honoured promptly
+            while Instant::now() < deadline && !thread_stop.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        }
+    });
+
+    *CONNECTIVITY_MONITOR.lock().unwrap_or_else(|e| e.into_inner()) =
+        Some(ConnectivityMonitor { stop, handle });
+    Ok(message)
+}
+
+/// Signal the monitor thread to stop and wait for it to exit (also used on app exit).
+/// Returns false if no monitor was running.
+pub fn halt_connectivity_monitor() -> bool {
+    let monitor = CONNECTIVITY_MONITOR.lock().unwrap_or_else(|e| e.into_inner()).take();
+    match monitor {
+        Some(monitor) => {
+            monitor.stop.store(true, Ordering::Relaxed);
+            let _ = monitor.handle.join();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Stop the connectivity monitor. Waiting for an in-flight round can take up to
+/// the probe timeout, so this runs off the main thread.
+#[tauri::command(async)]
+pub fn stop_connectivity_monitor() -> Result<String, AppError> {
+    if halt_connectivity_monitor() {
+        Ok("Connectivity monitor stopped".to_string())
+    } else {
+        Ok("Connectivity monitor was not running".to_string())
+    }
+}
+
+/// DNS resolution result for one adapter/server pair
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdapterDnsResult {
+    pub adapter: String,
+    pub dns_server: String,
+    #[serde(default)]
+    pub resolved_addresses: Vec<String>,
+    pub latency_ms: u64,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Resolve the same name through every adapter's configured DNS servers.
+/// Differences between adapters reveal split-DNS issues on VPN connections.
+#[tauri::command]
+pub fn compare_dns_across_adapters(test_domain: String) -> Result<Vec<AdapterDnsResult>, AppError> {
+    if test_domain.trim().is_empty() {
+        return Err("Test domain is required".into());
+    }
+
+    let script = format!(
+        r#"
+        $domain = '{}'
+        $results = @()
+        Get-DnsClientServerAddress -AddressFamily IPv4 -ErrorAction SilentlyContinue |
+            Where-Object {{ $_.ServerAddresses.Count -gt 0 }} | ForEach-Object {{
+            $adapter = $_.InterfaceAlias
+            foreach ($server in $_.ServerAddresses) {{
+                $entry = @{{ adapter = $adapter; dns_server = $server; resolved_addresses = @(); latency_ms = 0; error = $null }}
+                $sw = [System.Diagnostics.Stopwatch]::StartNew()
+                try {{
+                    $answers = Resolve-DnsName -Name $domain -Server $server -DnsOnly -QuickTimeout -ErrorAction Stop
+                    $entry.resolved_addresses = @($answers | Where-Object {{ $_.IPAddress }} | ForEach-Object {{ $_.IPAddress }})
+                }} catch {{
+                    $entry.error = $_.Exception.Message
+                }}
+                $sw.Stop()
+                $entry.latency_ms = [int]$sw.ElapsedMilliseconds
+                $results += $entry
+            }}
+        }}
+        ConvertTo-Json -InputObject @($results) -Depth 3 -Compress
+        "#,
+        test_domain.trim().replace("'", "''")
+    );
+
+    let stdout = run_powershell(&script)?;
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Err("No DNS comparison data returned".into());
+    }
+
+    Ok(serde_json::from_str(trimmed)?)
+}
+
+/// Time allowed for a DNS query before it counts as a timeout
+const DNS_QUERY_TIMEOUT_MS: u64 = 3000;
+
+/// getaddrinfo errors (WSA codes) that map to a DNS outcome
+const WSAHOST_NOT_FOUND: i32 = 11001;
+const WSATRY_AGAIN: i32 = 11002;
+const WSANO_DATA: i32 = 11004;
+
+/// How a timed DNS query ended
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DnsOutcome {
+    Resolved,
+    /// The name doesn't exist (NXDOMAIN)
+    NxDomain,
+    /// The name exists but has no A record
+    NoAnswer,
+    /// No reply within the timeout
+    Timeout,
+    /// The server answered with another error (SERVFAIL, REFUSED, ...)
+    ServerError,
+}
+
+/// Result of timing one DNS query
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DnsTiming {
+    pub hostname: String,
+    pub resolved_ip: Option<String>,
+    pub elapsed_ms: f64,
+    /// Server address, or "system" for the OS resolver
+    pub server_used: String,
+    pub outcome: DnsOutcome,
+}
+
+/// Encode a recursive A/IN query for `hostname`
+fn build_dns_query(id: u16, hostname: &str) -> Result<Vec<u8>, String> {
+    let mut packet = Vec::with_capacity(18 + hostname.len());
+    packet.extend_from_slice(&id.to_be_bytes());
+    // Standard query with recursion desired, one question
+    packet.extend_from_slice(&[0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    for label in hostname.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(format!("Invalid hostname: {}", hostname));
+        }
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+    packet.extend_from_slice(&[0x00, 0x01, 0x00, 0x01]);
+    Ok(packet)
+}
+
+/// Offset just past the (possibly compressed) name starting at `pos`
+fn skip_dns_name(packet: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = *packet.get(pos)?;
+        if len == 0 {
+            return Some(pos + 1);
+        }
+        if len & 0xC0 == 0xC0 {
+            // A compression pointer always ends the name
+            return Some(pos + 2);
+        }
+        pos += 1 + len as usize;
+    }
+}
+
+/// Response code and first A record of a reply to query `id`
+/// Returns None for packets that aren't a well-formed reply to that query
+fn parse_dns_response(packet: &[u8], id: u16) -> Option<(u8, Option<Ipv4Addr>)> {
+    if packet.len() < 12 || packet[0..2] != id.to_be_bytes() || packet[2] & 0x80 == 0 {
+        return None;
+    }
+    let rcode = packet[3] & 0x0F;
+    let question_count = u16::from_be_bytes([packet[4], packet[5]]);
+    let answer_count = u16::from_be_bytes([packet[6], packet[7]]);
+
+    let mut pos = 12;
+    for _ in 0..question_count {
+        pos = skip_dns_name(packet, pos)? + 4;
+    }
+    for _ in 0..answer_count {
+        pos = skip_dns_name(packet, pos)?;
+        let header = packet.get(pos..pos + 10)?;
+        let record_type = u16::from_be_bytes([header[0], header[1]]);
+        let data_len = u16::from_be_bytes([header[8], header[9]]) as usize;
+        let data = packet.get(pos + 10..pos + 10 + data_len)?;
+        if record_type == 1 && data_len == 4 {
+            return Some((rcode, Some(Ipv4Addr::new(data[0], data[1], data[2], data[3]))));
+        }
+        pos += 10 + data_len;
+    }
+
+    Some((rcode, None))
+}
+
+/// Outcome of a reply: NOERROR with an address, NOERROR without one, NXDOMAIN or anything else
+fn dns_outcome(rcode: u8, ip: Option<Ipv4Addr>) -> DnsOutcome {
+    match (rcode, ip) {
+        (0, Some(_)) => DnsOutcome::Resolved,
+        (0, None) => DnsOutcome::NoAnswer,
+        (3, _) => DnsOutcome::NxDomain,
+        _ => DnsOutcome::ServerError,
+    }
+}
+
+/// Send an A query straight to `server` over UDP and wait for the matching reply
+fn query_dns_server(
+    hostname: &str,
+    server: SocketAddr,
+) -> Result<(DnsOutcome, Option<Ipv4Addr>, Duration), String> {
+    // Not security-sensitive: only used to match the reply to the query
+    let id = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u16)
+        .unwrap_or(0x4e54);
+    let query = build_dns_query(id, hostname)?;
+
+    let bind_addr = if server.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    let socket = UdpSocket::bind(bind_addr).map_err(|e| format!("Failed to bind UDP socket: {}", e))?;
+    socket
+        .connect(server)
+        .map_err(|e| format!("Failed to reach DNS server {}: {}", server, e))?;
+
+    let start_time = Instant::now();
+    let deadline = start_time + Duration::from_millis(DNS_QUERY_TIMEOUT_MS);
+    socket
+        .send(&query)
+        .map_err(|e| format!("Failed to send DNS query: {}", e))?;
+
+    let mut buf = [0u8; 1500];
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()).filter(|d| !d.is_zero()) {
+        let _ = socket.set_read_timeout(Some(remaining));
+        let n = match socket.recv(&mut buf) {
+            Ok(n) => n,
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => break,
+            Err(e) => return Err(format!("DNS query to {} failed: {}", server, e)),
+        };
+        // Ignore stray packets that don't answer this query
+        if let Some((rcode, ip)) = parse_dns_response(&buf[..n], id) {
+            return Ok((dns_outcome(rcode, ip), ip, start_time.elapsed()));
+        }
+    }
+
+    Ok((DnsOutcome::Timeout, None, start_time.elapsed()))
+}
+
+/// Resolve through the OS resolver, mapping getaddrinfo failures to an outcome
+fn query_system_resolver(hostname: &str) -> (DnsOutcome, Option<IpAddr>, Duration) {
+    let start_time = Instant::now();
+    let result = (hostname, 0).to_socket_addrs();
+    let elapsed = start_time.elapsed();
+
+    match result {
+        Ok(addrs) => {
+            let addrs: Vec<IpAddr> = addrs.map(|a| a.ip()).collect();
+            let ip = addrs.iter().find(|ip| ip.is_ipv4()).or(addrs.first()).copied();
+            let outcome = if ip.is_some() { DnsOutcome::Resolved } else { DnsOutcome::NoAnswer };
+            (outcome, ip, elapsed)
+        }
+        Err(e) => {
+            let outcome = match e.raw_os_error() {
+                Some(WSAHOST_NOT_FOUND) => DnsOutcome::NxDomain,
+                Some(WSANO_DATA) => DnsOutcome::NoAnswer,
+                Some(WSATRY_AGAIN) => DnsOutcome::Timeout,
+                _ if e.kind() == ErrorKind::TimedOut => DnsOutcome::Timeout,
+                _ => DnsOutcome::ServerError,
+            };
+            (outcome, None, elapsed)
+        }
+    }
+}
+
+/// Time an A lookup of `hostname`, directly against `server` (IP or IP:port) when given,
+/// otherwise through the system resolver
+#[tauri::command]
+pub fn measure_dns_resolution(hostname: String, server: Option<String>) -> Result<DnsTiming, AppError> {
+    let hostname = hostname.trim().to_string();
+    if hostname.is_empty() {
+        return Err("Hostname is required".into());
+    }
+
+    let (outcome, resolved_ip, elapsed, server_used) =
+        match server.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            Some(server) => {
+                let addr = server
+                    .parse::<SocketAddr>()
+                    .or_else(|_| server.parse::<IpAddr>().map(|ip| SocketAddr::new(ip, 53)))
+                    .map_err(|_| format!("Invalid DNS server address: {}", server))?;
+                let (outcome, ip, elapsed) = query_dns_server(&hostname, addr)?;
+                (outcome, ip.map(IpAddr::V4), elapsed, addr.to_string())
+            }
+            None => {
+                let (outcome, ip, elapsed) = query_system_resolver(&hostname);
+                (outcome, ip, elapsed, "system".to_string())
+            }
+        };
+
+    Ok(DnsTiming {
+        hostname,
+        resolved_ip: resolved_ip.map(|ip| ip.to_string()),
+        elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+        server_used,
+        outcome,
+    })
+}
+
+
+/// Windows Time service synchronization status
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TimeSyncStatus {
+    pub source: String,
+    pub stratum: Option<u32>,
+    pub last_sync: String,
+    pub offset_ms: Option<f64>,
+}
+
+/// HRESULT returned by w32tm when the Windows Time service isn't running
+const W32TM_SERVICE_NOT_STARTED: &str = "0x80070426";
+
+/// Parse `w32tm /query /status /verbose` output
+fn parse_w32tm_status(text: &str) -> TimeSyncStatus {
+    let mut status = TimeSyncStatus::default();
+
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match key.trim() {
+            "Source" => status.source = value.to_string(),
+            "Stratum" => {
+                status.stratum = value
+                    .split_whitespace()
+                    .next()
+                    .and_then(|n| n.parse().ok());
+            }
+            "Last Successful Sync Time" => status.last_sync = value.to_string(),
+            // Offset is reported in seconds, e.g. "-0.0012345s"
+            "Phase Offset" => {
+                status.offset_ms = value
+                    .trim_end_matches('s')
+                    .parse::<f64>()
+                    .ok()
+                    .map(|secs| secs * 1000.0);
+            }
+            _ => {}
+        }
+    }
+
+    status
+}
+
+/// Get time synchronization (NTP) status
+/// Time skew breaks Kerberos and SMB authentication
+#[tauri::command]
+pub fn get_time_sync_status() -> Result<TimeSyncStatus, AppError> {
+    let output = run_cmd("w32tm", &["/query", "/status", "/verbose"])?;
+
+    let stdout = output.stdout;
+    if stdout.contains(W32TM_SERVICE_NOT_STARTED) {
+        return Err("Windows Time service (w32time) is not running".into());
+    }
+    if !output.success {
+        return Err(format!("w32tm query failed: {}", stdout.trim()).into());
+    }
+
+    Ok(parse_w32tm_status(&stdout))
+}
+
+/// Force an immediate time resync (requires admin)
+#[tauri::command]
+pub fn resync_time() -> Result<String, AppError> {
+    let output = run_cmd("w32tm", &["/resync"])?;
+
+    let stdout = output.stdout.trim();
+    if stdout.contains(W32TM_SERVICE_NOT_STARTED) {
+        return Err("Windows Time service (w32time) is not running".into());
+    }
+    if output.success {
+        Ok(stdout.to_string())
+    } else {
+        Err(format!("Time resync failed: {}", stdout).into())
+    }
+}
+
+/// Known router/network vendor OUIs (first 3 bytes of the MAC, uppercase hex)
+const ROUTER_OUIS: &[(&str, &str)] = &[
+    ("00000C", "Cisco"),
+    ("0014BF", "Linksys"),
+    ("001A70", "Linksys"),
+    ("00259C", "Linksys"),
+    ("00055D", "D-Link"),
+    ("1C7EE5", "D-Link"),
+    ("28107B", "D-Link"),
+    ("00E0FC", "Huawei"),
+    ("50C7BF", "TP-Link"),
+    ("14CC20", "TP-Link"),
+    ("F4F26D", "TP-Link"),
+    ("C025E9", "TP-Link"),
+    ("A040A0", "Netgear"),
+    ("204E7F", "Netgear"),
+    ("C40415", "Netgear"),
+    ("04D4C4", "ASUS"),
+    ("2C56DC", "ASUS"),
+    ("AC220B", "ASUS"),
+    ("24A43C", "Ubiquiti"),
+    ("788A20", "Ubiquiti"),
+    ("FCECDA", "Ubiquiti"),
+    ("802AA8", "Ubiquiti"),
+    ("0418D6", "Ubiquiti"),
+    ("4C5E0C", "MikroTik"),
+    ("D4CA6D", "MikroTik"),
+    ("6C3B6B", "MikroTik"),
+    ("E48D8C", "MikroTik"),
+    ("488F5A", "MikroTik"),
+    ("3CA62F", "AVM (FRITZ!Box)"),
+    ("7CFF4D", "AVM (FRITZ!Box)"),
+    ("C80E14", "AVM (FRITZ!Box)"),
+];
+
+/// Look up the vendor for a MAC address in any common notation
+pub(crate) fn oui_vendor(mac: &str) -> Option<String> {
+    let hex: String = mac
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if hex.len() != 12 {
+        return None;
+    }
+
+    // Second-lowest bit of the first octet marks a locally administered (randomized) MAC
+    let first_octet = u8::from_str_radix(&hex[..2], 16).ok()?;
+    if first_octet & 0x02 != 0 {
+        return Some("Locally administered (randomized)".to_string());
+    }
+
+    ROUTER_OUIS
+        .iter()
+        .find(|(oui, _)| hex.starts_with(oui))
+        .map(|(_, vendor)| vendor.to_string())
+}
+
+/// Extract the Server header or HTML title from a raw HTTP response
+fn parse_router_banner(response: &str) -> Option<String> {
+    let title = response.find("<title>").and_then(|start| {
+        let rest = &response[start + "<title>".len()..];
+        rest.find("</title>").map(|end| rest[..end].trim().to_string())
+    });
+    if let Some(title) = title.filter(|t| !t.is_empty()) {
+        return Some(title);
+    }
+
+    response
+        .lines()
+        .take_while(|line| !line.trim().is_empty())
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case("server")
+                .then(|| value.trim().to_string())
+        })
+        .filter(|server| !server.is_empty())
+}
+
+/// Fetch the router's admin page on common HTTP ports and read its banner
+fn probe_router_http(gateway_ip: &str) -> Option<String> {
+    let timeout = Duration::from_millis(1500);
+    for port in [80u16, 8080] {
+        let addr: SocketAddr = match format!("{}:{}", gateway_ip, port).parse() {
+            Ok(addr) => addr,
+            Err(_) => return None,
+        };
+        let mut stream = match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+        stream.set_read_timeout(Some(timeout)).ok();
+        let request = format!(
+            "GET / HTTP/1.0\r\nHost: {}\r\nConnection: close\r\n\r\n",
+            gateway_ip
+        );
+        if stream.write_all(request.as_bytes()).is_err() {
+            continue;
+        }
+
+        // The banner is near the top; don't download whole admin pages
+        let mut buf = Vec::new();
+        let _ = stream.take(16 * 1024).read_to_end(&mut buf);
+        if let Some(banner) = parse_router_banner(&String::from_utf8_lossy(&buf)) {
+            return Some(banner);
+        }
+    }
+    None
+}
+
+/// Information about the default gateway
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RouterInfo {
+    pub gateway_ip: String,
+    pub mac: Option<String>,
+    pub vendor: Option<String>,
+    pub likely_model: Option<String>,
+}
+
+/// Identify the default gateway: IP, MAC, vendor (from OUI) and, optionally,
+/// a model hint from its web admin page
+#[tauri::command]
+pub fn identify_router(probe_http: Option<bool>) -> Result<RouterInfo, AppError> {
+    let stdout = run_powershell(
+        r#"
+        $route = Get-NetRoute -DestinationPrefix '0.0.0.0/0' -ErrorAction SilentlyContinue |
+            Sort-Object RouteMetric | Select-Object -First 1
+        if (-not $route) { return }
+        $neighbor = Get-NetNeighbor -IPAddress $route.NextHop -ErrorAction SilentlyContinue | Select-Object -First 1
+        @{ gateway_ip = $route.NextHop; mac = $neighbor.LinkLayerAddress } | ConvertTo-Json -Compress
+        "#,
+    )?;
+
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Err(AppError::NotFound("No default gateway found".to_string()));
+    }
+    let data: serde_json::Value =
+        serde_json::from_str(trimmed).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+    let gateway_ip = data["gateway_ip"].as_str().unwrap_or("").to_string();
+    let mac = data["mac"]
+        .as_str()
+        .filter(|mac| !mac.is_empty())
+        .map(|mac| mac.to_string());
+    let vendor = mac.as_deref().and_then(oui_vendor);
+    let likely_model = if probe_http.unwrap_or(false) {
+        probe_router_http(&gateway_ip)
+    } else {
+        None
+    };
+
+    Ok(RouterInfo {
+        gateway_ip,
+        mac,
+        vendor,
+        likely_model,
+    })
+}
+
+// ============== Bufferbloat ==============
+
+/// Server used to saturate the downlink during the loaded phase
+const LOAD_HOST: &str = "speed.cloudflare.com";
+const LOAD_PATH: &str = "/__down?bytes=1000000000";
+
+/// Parallel download streams; one stream rarely fills a fast link
+const LOAD_STREAMS: usize = 4;
+
+/// Upper bound on the whole test
+const MAX_BUFFERBLOAT_SECS: u64 = 60;
+
+const LATENCY_SAMPLE_INTERVAL_MS: u64 = 250;
+const LATENCY_PROBE_TIMEOUT_MS: u64 = 2000;
+
+/// Latency with an idle vs. a saturated link
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BufferbloatResult {
+    pub host: String,
+    pub idle_ms: f64,
+    pub loaded_ms: f64,
+    pub increase_ms: f64,
+    /// A+ (no bloat) through F (severe)
+    pub rating: String,
+    /// Average download rate achieved by the load streams
+    pub load_mbps: f64,
+}
+
+/// Median of latency samples, None when no probe succeeded
+fn median(samples: &mut [f64]) -> Option<f64> {
+    if samples.is_empty() {
+        return None;
+    }
+    samples.sort_by(|a, b| a.total_cmp(b));
+    let mid = samples.len() / 2;
+    Some(if samples.len().is_multiple_of(2) {
+        (samples[mid - 1] + samples[mid]) / 2.0
+    } else {
+        samples[mid]
+    })
+}
+
+/// Grade the latency increase under load
+fn bufferbloat_rating(increase_ms: f64) -> &'static str {
+    match increase_ms {
+        x if x < 5.0 => "A+",
+        x if x < 30.0 => "A",
+        x if x < 60.0 => "B",
+        x if x < 200.0 => "C",
+        x if x < 400.0 => "D",
+        _ => "F",
+    }
+}
+
+/// How latency to the target is measured
+enum LatencyProbe {
+    /// ICMP echo, the same probe the ping tool uses
+    Ping(String),
+    /// TCP handshake time, for hosts that drop ICMP
+    Tcp(SocketAddr),
+}
+
+impl LatencyProbe {
+    /// Prefer ping; fall back to a TCP handshake on port 443
+    fn select(host: &str) -> Result<Self, String> {
+        let ping = LatencyProbe::Ping(host.to_string());
+        if ping.sample().is_some() {
+            return Ok(ping);
+        }
+
+        let addr = (host, 443)
+            .to_socket_addrs()
+            .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+            .next()
+            .ok_or_else(|| format!("No address found for {}", host))?;
+        let tcp = LatencyProbe::Tcp(addr);
+        match tcp.sample() {
+            Some(_) => Ok(tcp),
+            None => Err(format!("{} did not answer ping or TCP port 443", host)),
+        }
+    }
+
+    /// One latency sample in milliseconds, None if the probe got no answer
+    fn sample(&self) -> Option<f64> {
+        match self {
+            LatencyProbe::Ping(host) => ping_reply(host, LATENCY_PROBE_TIMEOUT_MS as u32)
+                .and_then(|reply| reply.time_ms)
+                .map(f64::from),
+            LatencyProbe::Tcp(addr) => {
+                let start_time = Instant::now();
+                TcpStream::connect_timeout(addr, Duration::from_millis(LATENCY_PROBE_TIMEOUT_MS)).ok()?;
+                Some(start_time.elapsed().as_secs_f64() * 1000.0)
+            }
+        }
+    }
+}
+
+/// Collect latency samples for `duration`
+fn sample_latency(probe: &LatencyProbe, duration: Duration) -> Vec<f64> {
+    let deadline = Instant::now() + duration;
+    let mut samples = Vec::new();
+    while Instant::now() < deadline {
+        if let Some(rtt) = probe.sample() {
+            samples.push(rtt);
+        }
+        std::thread::sleep(Duration::from_millis(LATENCY_SAMPLE_INTERVAL_MS));
+    }
+    samples
+}
+
+/// Open an HTTPS download from the load server
+fn open_load_stream(config: Arc<ClientConfig>) -> Option<StreamOwned<ClientConnection, TcpStream>> {
+    let addr = (LOAD_HOST, 443).to_socket_addrs().ok()?.next()?;
+    let tcp = TcpStream::connect_timeout(&addr, Duration::from_secs(5)).ok()?;
+    // Short read timeout so the stop flag is checked promptly
+    tcp.set_read_timeout(Some(Duration::from_millis(500))).ok()?;
+
+    let name = ServerName::try_from(LOAD_HOST).ok()?;
+    let conn = ClientConnection::new(config, name).ok()?;
+    let mut tls = StreamOwned::new(conn, tcp);
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        LOAD_PATH, LOAD_HOST
+    );
+    tls.write_all(request.as_bytes()).ok()?;
+    Some(tls)
+}
+
+/// Download from the load server until told to stop, reconnecting if a transfer ends
+fn run_load_stream(stop: &AtomicBool, bytes: &AtomicU64) {
+    let Ok(config) = https_client_config() else {
+        return;
+    };
+    let mut buf = vec![0u8; 64 * 1024];
+
+    while !stop.load(Ordering::Relaxed) {
+        let Some(mut tls) = open_load_stream(config.clone()) else {
+            std::thread::sleep(Duration::from_millis(500));
+            continue;
+        };
+
+        while !stop.load(Ordering::Relaxed) {
+            match tls.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    bytes.fetch_add(n as u64, Ordering::Relaxed);
+                }
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+/// Stops and joins the load streams when dropped, so they never outlive the test
+struct LoadGenerator {
+    stop: Arc<AtomicBool>,
+    bytes: Arc<AtomicU64>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl LoadGenerator {
+    fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let bytes = Arc::new(AtomicU64::new(0));
+        let handles = (0..LOAD_STREAMS)
+            .map(|_| {
+                let stop = stop.clone();
+                let bytes = bytes.clone();
+                std::thread::spawn(move || run_load_stream(&stop, &bytes))
+            })
+            .collect();
+        LoadGenerator { stop, bytes, handles }
+    }
+}
+
+impl Drop for LoadGenerator {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Measure how much latency to `host` rises while the downlink is saturated
+///
+/// The first third of `duration_secs` samples idle latency; the rest runs
+/// parallel downloads while sampling again. Latency is measured with ping, or
+/// with the TCP handshake time on port 443 when `host` doesn't answer ping.
+///
+/// The load comes from `LOAD_STREAMS` HTTPS downloads of a 1 GB test file from
+/// Cloudflare's speed test server (`LOAD_HOST`), so the test uses real data
+/// (hundreds of MB on a fast link). Runs off the main thread since it takes
+/// `duration_secs` (6-60 s).
+#[tauri::command(async)]
+pub fn test_bufferbloat(
+    host: String,
+    duration_secs: Option<u64>,
+) -> Result<BufferbloatResult, AppError> {
+    let total = duration_secs.unwrap_or(15).clamp(6, MAX_BUFFERBLOAT_SECS);
+    let idle_duration = Duration::from_secs(total / 3);
+    let loaded_duration = Duration::from_secs(total - total / 3);
+
+    let probe = LatencyProbe::select(&host)?;
+    let mut idle = sample_latency(&probe, idle_duration);
+    let idle_ms = median(&mut idle).ok_or_else(|| format!("{} stopped responding", host))?;
+
+    let load = LoadGenerator::start();
+    // Let the streams ramp up before sampling
+    std::thread::sleep(Duration::from_secs(1));
+    let start_bytes = load.bytes.load(Ordering::Relaxed);
+    let load_start = Instant::now();
+    let mut loaded = sample_latency(&probe, loaded_duration - Duration::from_secs(1));
+    let load_secs = load_start.elapsed().as_secs_f64();
+    let load_bytes = load.bytes.load(Ordering::Relaxed) - start_bytes;
+    drop(load);
+
+    let loaded_ms = median(&mut loaded)
+        .ok_or_else(|| format!("{} stopped responding under load", host))?;
+    if load_bytes == 0 {
+        return Err(format!("Could not generate load: {} is unreachable", LOAD_HOST).into());
+    }
+
+    let increase_ms = (loaded_ms - idle_ms).max(0.0);
+    Ok(BufferbloatResult {
+        host,
+        idle_ms,
+        loaded_ms,
+        increase_ms,
+        rating: bufferbloat_rating(increase_ms).to_string(),
+        load_mbps: load_bytes as f64 * 8.0 / 1_000_000.0 / load_secs.max(0.001),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ping_output() {
+        let sample = "\r\nPinging 8.8.8.8 with 32 bytes of data:\r\n\
+Reply from 8.8.8.8: bytes=32 time=14ms TTL=117\r\n\
+Request timed out.\r\n\
+Reply from 8.8.8.8: bytes=32 time<1ms TTL=117\r\n\
+Reply from 8.8.8.8: bytes=32 time=20ms TTL=117\r\n\
+\r\n\
+Ping statistics for 8.8.8.8:\r\n\
+    Packets: Sent = 4, Received = 3, Lost = 1 (25% loss),\r\n";
+
+        let result = parse_ping_output(sample).unwrap();
+        assert_eq!(result.packets_sent, 4);
+        assert_eq!(result.packets_received, 3);
+        assert_eq!(result.packets_lost, 1);
+        assert_eq!(
+            result.replies[0],
+            PingReply {
+                bytes: Some(32),
+                time_ms: Some(14),
+                ttl: Some(117),
+                timed_out: false
+            }
+        );
+        assert!(result.replies[1].timed_out);
+        assert_eq!(result.replies[2].time_ms, Some(0));
+        assert_eq!((result.min_ms, result.max_ms, result.avg_ms), (Some(0), Some(20), Some(11)));
+    }
+
+    #[test]
+    fn test_parse_ping_output_localized() {
+        let sample = "Ping wird ausgef\u{fc}hrt f\u{fc}r 1.1.1.1 mit 32 Bytes Daten:\n\
+Antwort von 1.1.1.1: Bytes=32 Zeit=9ms TTL=58\n\n";
+        let result = parse_ping_output(sample).unwrap();
+        assert_eq!(result.replies[0].time_ms, Some(9));
+        assert_eq!(result.replies[0].bytes, Some(32));
+    }
+
+    #[test]
+    fn test_parse_ping_output_unresolved() {
+        let sample = "Ping request could not find host nosuchhost. Please check the name and try again.\r\n";
+        assert!(parse_ping_output(sample).is_none());
+    }
+
+    #[test]
+    fn test_parse_tracert_output() {
+        let sample = "\r\nTracing route to 8.8.8.8 over a maximum of 30 hops\r\n\r\n\
+  1    <1 ms    <1 ms    <1 ms  192.168.1.1\r\n\
+  2     *        *        *     Request timed out.\r\n\
+  3    10 ms     9 ms    11 ms  10.0.0.1\r\n\
+  4     *       12 ms     *     172.16.0.1\r\n\
+\r\n\
+Trace complete.\r\n";
+
+        let hops = parse_tracert_output(sample);
+        assert_eq!(hops.len(), 4);
+        assert_eq!(hops[0].probes, [Some(0), Some(0), Some(0)]);
+        assert_eq!(
+            hops[1],
+            TraceHop {
+                hop_number: 2,
+                probes: [None, None, None],
+                address: None
+            }
+        );
+        assert_eq!(hops[2].address.as_deref(), Some("10.0.0.1"));
+        assert_eq!(hops[3].probes, [None, Some(12), None]);
+    }
+
+    #[test]
+    fn test_parse_trace_hop_with_hostname() {
+        let hop = parse_trace_hop("  5    14 ms    13 ms    15 ms  dns.google [8.8.8.8]").unwrap();
+        assert_eq!(hop.address.as_deref(), Some("8.8.8.8"));
+        assert!(parse_trace_hop("Trace complete.").is_none());
+    }
+
+    #[test]
+    fn test_parse_nslookup_debug() {
+        let sample = "------------\n\
+Got answer:\n\
+    HEADER:\n\
+        opcode = QUERY, id = 1, rcode = NOERROR\n\
+    QUESTIONS:\n\
+        8.8.8.8.in-addr.arpa, type = PTR, class = IN\n\
+    ANSWERS:\n\
+    ->  8.8.8.8.in-addr.arpa\n\
+        name = dns.google\n\
+        ttl = 20000 (5 hours 33 mins 20 secs)\n\
+------------\n\
+Got answer:\n\
+    HEADER:\n\
+        opcode = QUERY, id = 2, rcode = NOERROR\n\
+    QUESTIONS:\n\
+        example.com, type = MX, class = IN\n\
+    ANSWERS:\n\
+    ->  example.com\n\
+        MX preference = 10, mail exchanger = mx1.example.com\n\
+        ttl = 300 (5 mins)\n\
+    ->  example.com\n\
+        MX preference = 20, mail exchanger = mx2.example.com\n\
+        ttl = 300 (5 mins)\n\
+    ADDITIONAL RECORDS:\n\
+    ->  mx1.example.com\n\
+        internet address = 192.0.2.1\n\
+        ttl = 300 (5 mins)\n\
+------------\n";
+
+        let records = parse_nslookup_debug(sample);
+        assert_eq!(
+            records,
+            vec![
+                DnsRecord {
+                    record_type: "MX".to_string(),
+                    value: "10 mx1.example.com".to_string(),
+                    ttl: Some(300)
+                },
+                DnsRecord {
+                    record_type: "MX".to_string(),
+                    value: "20 mx2.example.com".to_string(),
+                    ttl: Some(300)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_nslookup_debug_txt_and_cname() {
+        let sample = "    ANSWERS:\n\
+    ->  www.example.com\n\
+        canonical name = example.com\n\
+        ttl = 60 (1 min)\n\
+    ->  example.com\n\
+        text =\n\
+\n\
+        \"v=spf1 \"\n\
+        \"-all\"\n\
+        ttl = 120 (2 mins)\n";
+
+        let records = parse_nslookup_debug(sample);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].record_type, "CNAME");
+        assert_eq!(records[1].value, "v=spf1 -all");
+        assert_eq!(records[1].ttl, Some(120));
+    }
+
+    #[test]
+    fn test_parse_arp_output() {
+        let sample = "\r\nInterface: 192.168.1.10 --- 0xb\r\n\
+  Internet Address      Physical Address      Type\r\n\
+  192.168.1.1           aa-bb-cc-dd-ee-ff     dynamic\r\n\
+  192.168.1.255         ff-ff-ff-ff-ff-ff     static\r\n\
+\r\n\
+Interface: 10.0.0.5 --- 0x4\r\n\
+  Internet Address      Physical Address      Type\r\n\
+  10.0.0.1              11-22-33-44-55-66     dynamic\r\n";
+
+        let entries = parse_arp_output(sample);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0],
+            ArpEntry {
+                ip_address: "192.168.1.1".to_string(),
+                mac_address: "AA-BB-CC-DD-EE-FF".to_string(),
+                entry_type: "dynamic".to_string(),
+                interface: "192.168.1.10".to_string(),
+            }
+        );
+        assert_eq!(entries[1].entry_type, "static");
+        assert_eq!(entries[2].interface, "10.0.0.5");
+    }
+
+    #[test]
+    fn test_search_path_mtu() {
+        // Path allows 1400-byte payloads (MTU 1428)
+        let (best, probes, complete) = search_path_mtu(|size| {
+            if size <= 1400 { DfProbe::Passed } else { DfProbe::NeedsFragmentation }
+        });
+        assert_eq!(best, Some(1400));
+        assert!(complete);
+        assert!(probes <= PMTU_MAX_PROBES);
+
+        // Standard Ethernet: first probe passes
+        assert_eq!(search_path_mtu(|_| DfProbe::Passed), (Some(1472), 1, true));
+
+        // Host goes quiet after the first fragmentation
+        let mut calls = 0;
+        let (best, _, complete) = search_path_mtu(|_| {
+            calls += 1;
+            if calls == 1 { DfProbe::NeedsFragmentation } else if calls == 2 { DfProbe::Passed } else { DfProbe::NoReply }
+        });
+        assert_eq!(best, Some(736));
+        assert!(!complete);
+    }
+
+    #[test]
+    fn test_classify_df_ping() {
+        let passed = "\nPinging 8.8.8.8 with 1472 bytes of data:\nReply from 8.8.8.8: bytes=1472 time=12ms TTL=117\n\nPing statistics for 8.8.8.8:\n";
+        assert_eq!(classify_df_ping(passed), DfProbe::Passed);
+        let fragmented = "\nPinging 8.8.8.8 with 1473 bytes of data:\nPacket needs to be fragmented but DF set.\n\nPing statistics for 8.8.8.8:\n";
+        assert_eq!(classify_df_ping(fragmented), DfProbe::NeedsFragmentation);
+        let timeout = "\nPinging 8.8.8.8 with 1472 bytes of data:\nRequest timed out.\n\nPing statistics for 8.8.8.8:\n";
+        assert_eq!(classify_df_ping(timeout), DfProbe::NoReply);
+    }
+
+    #[test]
+    fn test_whois_referral() {
+        let iana = "% IANA WHOIS server\n\nrefer:        whois.verisign-grs.com\n\ndomain:       COM\n";
+        assert_eq!(whois_referral(iana), Some("whois.verisign-grs.com".to_string()));
+        assert_eq!(whois_referral("% no match\n"), None);
+    }
+
+    #[test]
+    fn test_parse_whois_domain() {
+        let raw = "   Domain Name: EXAMPLE.COM\r\n\
+   Registrar WHOIS Server: whois.iana.org\r\n\
+   Creation Date: 1995-08-14T04:00:00Z\r\n\
+   Registry Expiry Date: 2025-08-13T04:00:00Z\r\n\
+   Registrar: RESERVED-Internet Assigned Numbers Authority\r\n\
+   Name Server: A.IANA-SERVERS.NET\r\n\
+   Name Server: B.IANA-SERVERS.NET\r\n\
+   Name Server: a.iana-servers.net\r\n";
+        let result = parse_whois("example.com", "whois.verisign-grs.com", raw.to_string());
+        assert_eq!(result.registrar.as_deref(), Some("RESERVED-Internet Assigned Numbers Authority"));
+        assert_eq!(result.creation_date.as_deref(), Some("1995-08-14T04:00:00Z"));
+        assert_eq!(result.expiry_date.as_deref(), Some("2025-08-13T04:00:00Z"));
+        assert_eq!(result.name_servers, vec!["a.iana-servers.net", "b.iana-servers.net"]);
+    }
+
+    #[test]
+    fn test_parse_whois_ip() {
+        let raw = "NetRange:       8.8.8.0 - 8.8.8.255\nOrgName:        Google LLC\nRegDate:        2014-03-14\n";
+        let result = parse_whois("8.8.8.8", "whois.arin.net", raw.to_string());
+        assert_eq!(result.registrar.as_deref(), Some("Google LLC"));
+        assert_eq!(result.creation_date.as_deref(), Some("2014-03-14"));
+        assert_eq!(result.expiry_date, None);
+        assert!(result.name_servers.is_empty());
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut []), None);
+        assert_eq!(median(&mut [30.0, 10.0, 20.0]), Some(20.0));
+        assert_eq!(median(&mut [40.0, 10.0, 20.0, 30.0]), Some(25.0));
+    }
+
+    #[test]
+    fn test_bufferbloat_rating() {
+        assert_eq!(bufferbloat_rating(0.0), "A+");
+        assert_eq!(bufferbloat_rating(45.0), "B");
+        assert_eq!(bufferbloat_rating(1000.0), "F");
+    }
+
+    #[test]
+    fn test_parse_w32tm_status() {
+        let sample = "Leap Indicator: 0(no warning)\r\n\
+Stratum: 4 (secondary reference - syncd by (S)NTP)\r\n\
+Phase Offset: -0.0012500s\r\n\
+Last Successful Sync Time: 10/15/2026 10:12:01 AM\r\n\
+Source: time.windows.com,0x9\r\n";
+
+        let status = parse_w32tm_status(sample);
+        assert_eq!(status.source, "time.windows.com,0x9");
+        assert_eq!(status.stratum, Some(4));
+        assert_eq!(status.last_sync, "10/15/2026 10:12:01 AM");
+        assert!((status.offset_ms.unwrap() + 1.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_oui_vendor() {
+        assert_eq!(oui_vendor("50-C7-BF-12-34-56").as_deref(), Some("TP-Link"));
+        assert_eq!(oui_vendor("4c:5e:0c:aa:bb:cc").as_deref(), Some("MikroTik"));
+        assert_eq!(
+            oui_vendor("DA-00-11-22-33-44").as_deref(),
+            Some("Locally administered (randomized)")
+        );
+        assert_eq!(oui_vendor("00-11-22-33-44-55"), None);
+        assert_eq!(oui_vendor("not-a-mac"), None);
+    }
+
+    #[test]
+    fn test_parse_router_banner() {
+        let with_title = "HTTP/1.0 200 OK\r\nServer: lighttpd\r\n\r\n<html><title> Archer C7 </title></html>";
+        assert_eq!(parse_router_banner(with_title).as_deref(), Some("Archer C7"));
+
+        let server_only = "HTTP/1.0 401 Unauthorized\r\nServer: RouterOS\r\n\r\n";
+        assert_eq!(parse_router_banner(server_only).as_deref(), Some("RouterOS"));
+    }
+
+    #[test]
+    fn test_sweep_hosts() {
+        let hosts = sweep_hosts("192.168.1.77/24").unwrap();
+        assert_eq!(hosts.len(), 254);
+        assert_eq!(hosts[0], Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(hosts[253], Ipv4Addr::new(192, 168, 1, 254));
+
+        assert_eq!(sweep_hosts("10.0.0.0/22").unwrap().len(), 1022);
+        assert_eq!(sweep_hosts("10.0.0.4/31").unwrap().len(), 2);
+        assert_eq!(sweep_hosts("10.0.0.4/32").unwrap(), vec![Ipv4Addr::new(10, 0, 0, 4)]);
+        assert!(sweep_hosts("10.0.0.0/21").is_err());
+        assert!(sweep_hosts("10.0.0.0").is_err());
+    }
+
+    #[test]
+    fn test_ptr_query_name() {
+        assert_eq!(
+            ptr_query_name("192.168.1.20".parse().unwrap()),
+            "20.1.168.192.in-addr.arpa"
+        );
+        assert_eq!(
+            ptr_query_name("2001:db8::1".parse().unwrap()),
+            "1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa"
+        );
+    }
+
+    #[test]
+    fn test_parse_ptr_answer() {
+        // The server's own reverse lookup comes first and must be ignored
+        let sample = "------------\n\
+Got answer:\n\
+    HEADER:\n\
+        opcode = QUERY, id = 1, rcode = NOERROR\n\
+    QUESTIONS:\n\
+        1.1.168.192.in-addr.arpa, type = PTR, class = IN\n\
+    ANSWERS:\n\
+    ->  1.1.168.192.in-addr.arpa\n\
+        name = router.lan\n\
+        ttl = 0 (0 secs)\n\
+------------\n\
+Server:  router.lan\n\
+Address:  192.168.1.1\n\
+\n\
+------------\n\
+Got answer:\n\
+    HEADER:\n\
+        opcode = QUERY, id = 2, rcode = NOERROR\n\
+    QUESTIONS:\n\
+        8.8.8.8.in-addr.arpa, type = PTR, class = IN\n\
+    ANSWERS:\n\
+    ->  8.8.8.8.in-addr.arpa\n\
+        name = dns.google\n\
+        ttl = 20925 (5 hours 48 mins 45 secs)\n\
+------------\n";
+        assert_eq!(
+            parse_ptr_answer(sample, "8.8.8.8.in-addr.arpa").as_deref(),
+            Some("dns.google")
+        );
+        assert_eq!(parse_ptr_answer(sample, "9.9.9.9.in-addr.arpa"), None);
+    }
+
+    #[test]
+    fn test_outage_tracker() {
+        let start = Instant::now();
+        let mut tracker = OutageTracker::default();
+
+        assert_eq!(tracker.record(true, start), (0, None));
+        assert_eq!(tracker.record(false, start), (1, Some(Duration::ZERO)));
+        assert_eq!(
+            tracker.record(false, start + Duration::from_secs(30)),
+            (2, Some(Duration::from_secs(30)))
+        );
+        assert_eq!(tracker.record(true, start + Duration::from_secs(35)), (0, None));
+        assert_eq!(
+            tracker.record(false, start + Duration::from_secs(40)),
+            (1, Some(Duration::ZERO))
+        );
+    }
+
+    #[test]
+    fn test_build_dns_query() {
+        let query = build_dns_query(0xABCD, "example.com.").unwrap();
+        assert_eq!(&query[..4], &[0xAB, 0xCD, 0x01, 0x00]);
+        assert_eq!(&query[12..], b"\x07example\x03com\x00\x00\x01\x00\x01");
+        assert!(build_dns_query(1, "bad..name").is_err());
+    }
+
+    #[test]
+    fn test_parse_dns_response() {
+        let mut reply = vec![0x12, 0x34, 0x81, 0x80, 0, 1, 0, 2, 0, 0, 0, 0];
+        reply.extend_from_slice(b"\x07example\x03com\x00\x00\x01\x00\x01");
+        // CNAME answer, then the A record, both using compressed owner names
+        reply.extend_from_slice(&[0xC0, 0x0C, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xC0, 0x0C]);
+        reply.extend_from_slice(&[0xC0, 0x0C, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 93, 184, 216, 34]);
+        assert_eq!(
+            parse_dns_response(&reply, 0x1234),
+            Some((0, Some(Ipv4Addr::new(93, 184, 216, 34))))
+        );
+        assert_eq!(parse_dns_response(&reply, 0x9999), None);
+
+        let mut nxdomain = vec![0x12, 0x34, 0x81, 0x83, 0, 1, 0, 0, 0, 0, 0, 0];
+        nxdomain.extend_from_slice(b"\x07missing\x03com\x00\x00\x01\x00\x01");
+        let (rcode, ip) = parse_dns_response(&nxdomain, 0x1234).unwrap();
+        assert_eq!(dns_outcome(rcode, ip), DnsOutcome::NxDomain);
+        assert_eq!(dns_outcome(0, None), DnsOutcome::NoAnswer);
+        assert_eq!(dns_outcome(2, None), DnsOutcome::ServerError);
+    }
+
+    #[test]
+    fn test_normalize_http_url() {
+        assert_eq!(normalize_http_url(" example.com "), "https://example.com");
+        assert_eq!(normalize_http_url("http://example.com/a"), "http://example.com/a");
+    }
+
+    #[test]
+    fn test_classify_portal_response() {
+        let clear = classify_portal_response(200, None, "Microsoft Connect Test");
+        assert!(!clear.behind_portal);
+
+        let redirected = classify_portal_response(302, Some("http://portal.hotel.example/login"), "");
+        assert!(redirected.behind_portal);
+        assert_eq!(redirected.redirect_url.as_deref(), Some("http://portal.hotel.example/login"));
+
+        let rewritten = classify_portal_response(200, None, "<html>Please log in</html>");
+        assert!(rewritten.behind_portal);
+        assert_eq!(rewritten.redirect_url, None);
+    }
+
+    #[test]
+    fn test_parse_netstat_line() {
+        let tcp = parse_netstat_line("  TCP    0.0.0.0:8080           0.0.0.0:0              LISTENING       4321").unwrap();
+        assert_eq!(tcp.protocol, "TCP");
+        assert_eq!(tcp.local_addr, "0.0.0.0:8080");
+        assert_eq!(tcp.state.as_deref(), Some("LISTENING"));
+        assert_eq!(tcp.pid, 4321);
+
+        let udp = parse_netstat_line("  UDP    [::]:5353              *:*                                    1880").unwrap();
+        assert_eq!(udp.protocol, "UDP");
+        assert_eq!(udp.local_addr, "[::]:5353");
+        assert_eq!(udp.remote_addr, "*:*");
+        assert_eq!(udp.state, None);
+        assert_eq!(udp.pid, 1880);
+
+        assert!(parse_netstat_line("  Proto  Local Address          Foreign Address        State           PID").is_none());
+        assert!(parse_netstat_line("Active Connections").is_none());
+    }
+
+    #[test]
+    fn test_parse_tasklist_csv() {
+        let stdout = "\"System Idle Process\",\"0\",\"Services\",\"0\",\"8 K\"\r\n\"node.exe\",\"4321\",\"Console\",\"1\",\"52,144 K\"\r\n";
+        let processes = parse_tasklist_csv(stdout);
+        assert_eq!(processes.get(&4321).map(String::as_str), Some("node.exe"));
+        assert_eq!(processes.get(&0).map(String::as_str), Some("System Idle Process"));
+    }
 }
@@ -0,0 +1,159 @@
+//! Error type returned by Tauri commands
+//! Serialized as `{ "kind": ..., "message": ... }` so the frontend can branch
+//! on the category (e.g. offer elevation for `not_elevated`)
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+use crate::ps::PsError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    /// The operation needs administrator rights
+    NotElevated(String),
+    /// An external program or script ran but failed
+    CommandFailed { stderr: String },
+    /// PowerShell is missing or blocked by policy
+    PowerShellUnavailable(String),
+    /// Output couldn't be parsed
+    ParseError(String),
+    /// The requested item (adapter, share, drive, ...) doesn't exist
+    NotFound(String),
+    /// The operation didn't finish in time
+    Timeout(String),
+    /// Anything without a more specific category
+    Other(String),
+}
+
+impl AppError {
+    /// Elevation error for a user-facing operation, e.g. "change the MTU of Ethernet"
+    pub fn not_elevated(operation: &str) -> Self {
+        AppError::NotElevated(format!("Administrator privileges are required to {}", operation))
+    }
+
+    /// Stable identifier the frontend matches on
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::NotElevated(_) => "not_elevated",
+            AppError::CommandFailed { .. } => "command_failed",
+            AppError::PowerShellUnavailable(_) => "powershell_unavailable",
+            AppError::ParseError(_) => "parse_error",
+            AppError::NotFound(_) => "not_found",
+            AppError::Timeout(_) => "timeout",
+            AppError::Other(_) => "other",
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::PowerShellUnavailable(reason) => {
+                write!(f, "PowerShell is not available on this system ({})", reason)
+            }
+            AppError::CommandFailed { stderr: message }
+            | AppError::NotElevated(message)
+            | AppError::ParseError(message)
+            | AppError::NotFound(message)
+            | AppError::Timeout(message)
+            | AppError::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Other(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::Other(message.to_string())
+    }
+}
+
+impl From<PsError> for AppError {
+    fn from(e: PsError) -> Self {
+        match e {
+            PsError::PowerShellUnavailable(reason) => AppError::PowerShellUnavailable(reason),
+            PsError::Failed(stderr) => AppError::CommandFailed { stderr },
+            PsError::AccessDenied(stderr) => {
+                AppError::NotElevated(format!("Administrator privileges are required: {}", stderr))
+            }
+        }
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        AppError::ParseError(format!("Failed to parse JSON: {}", e))
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::NotFound(e.to_string()),
+            std::io::ErrorKind::TimedOut => AppError::Timeout(e.to_string()),
+            _ => AppError::Other(e.to_string()),
+        }
+    }
+}
+
+/// Lets helpers that still return `Result<T, String>` call commands with `?`
+impl From<AppError> for String {
+    fn from(e: AppError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_kind_and_message() {
+        let json = serde_json::to_value(AppError::NotElevated(
+            "Administrator privileges are required".to_string(),
+        ))
+        .unwrap();
+        assert_eq!(json["kind"], "not_elevated");
+        assert_eq!(json["message"], "Administrator privileges are required");
+
+        let json = serde_json::to_value(AppError::CommandFailed {
+            stderr: "Access denied".to_string(),
+        })
+        .unwrap();
+        assert_eq!(json["kind"], "command_failed");
+        assert_eq!(json["message"], "Access denied");
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(
+            AppError::from("boom".to_string()),
+            AppError::Other("boom".to_string())
+        );
+        assert_eq!(
+            AppError::from(PsError::Failed("bad".to_string())),
+            AppError::CommandFailed {
+                stderr: "bad".to_string()
+            }
+        );
+        assert_eq!(
+            String::from(AppError::Timeout("timed out".to_string())),
+            "timed out"
+        );
+    }
+}
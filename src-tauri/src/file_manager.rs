@@ -1,16 +1,20 @@
 use std::path::Path;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
 #[cfg(not(target_os = "windows"))]
 use std::process::Command;
 
+use crate::error::AppError;
+
 /// Opens files or folders in the native file explorer
 /// 
 /// For Windows: Uses explorer.exe /select,<path> for files or explorer.exe <path> for folders
 /// For macOS: Uses open -R <path> for files or open <path> for folders  
 /// For Linux: Uses xdg-open <parent_path> or file manager specific commands
 #[tauri::command]
-pub async fn open_in_file_explorer(paths: Vec<String>) -> Result<(), String> {
+pub async fn open_in_file_explorer(paths: Vec<String>) -> Result<(), AppError> {
     if paths.is_empty() {
-        return Err("No paths provided".to_string());
+        return Err("No paths provided".into());
     }
 
     for path_str in paths {
@@ -18,7 +22,10 @@ pub async fn open_in_file_explorer(paths: Vec<String>) -> Result<(), String> {
         
         // Check if path exists
         if !path.exists() {
-            return Err(format!("Path does not exist: {}", path_str));
+            return Err(AppError::NotFound(format!(
+                "Path does not exist: {}",
+                path_str
+            )));
         }
 
         #[cfg(target_os = "windows")]
@@ -120,21 +127,103 @@ fn open_in_explorer_linux(path: &str, is_dir: bool) -> Result<(), String> {
     }
 }
 
-/// Opens an SMB path in Windows Explorer
+/// Opens a file with its default associated program, or with `application` if given
 ///
-/// Constructs a UNC path from server and optional share name, then opens it in Explorer.
-/// Example: open_smb_path("192.168.2.133", Some("shared")) opens \\192.168.2.133\shared
+/// For Windows: ShellExecuteW "open" for the default program, otherwise runs the executable
+/// For macOS: open <path> or open -a <application> <path>
+/// For Linux: xdg-open <path> or <application> <path>
 #[tauri::command]
-pub async fn open_smb_path(server: String, share: Option<String>) -> Result<(), String> {
+pub async fn open_with(path: String, application: Option<String>) -> Result<(), AppError> {
+    if !Path::new(&path).exists() {
+        return Err(AppError::NotFound(format!("Path does not exist: {}", path)));
+    }
+    let application = application.filter(|a| !a.trim().is_empty());
+
+    #[cfg(target_os = "windows")]
+    {
+        match application {
+            Some(app) => std::process::Command::new(&app)
+                .arg(&path)
+                .spawn()
+                .map(|_| ())
+                .map_err(|e| AppError::Other(format!("Failed to launch {}: {}", app, e))),
+            None => Ok(shell_open_windows(&path)?),
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let mut command = Command::new("open");
+        if let Some(app) = &application {
+            command.arg("-a").arg(app);
+        }
+        command
+            .arg(&path)
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| AppError::Other(format!("Failed to open {}: {}", path, e)))
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let program = application.unwrap_or_else(|| "xdg-open".to_string());
+        Command::new(&program)
+            .arg(&path)
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| AppError::Other(format!("Failed to launch {}: {}", program, e)))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn shell_open_windows(path: &str) -> Result<(), String> {
+    use windows::core::{w, HSTRING, PCWSTR};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::ShellExecuteW;
+    use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    // SAFETY: all string arguments are valid null-terminated wide strings for the call
+    let result = unsafe {
+        ShellExecuteW(
+            HWND::default(),
+            w!("open"),
+            &HSTRING::from(path),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+
+    // Values of 32 or below are error codes
+    if result.0 as isize > 32 {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to open {} (ShellExecute error {})",
+            path, result.0 as isize
+        ))
+    }
+}
+
+/// Build a UNC path from a server and optional share: \\server\share or \\server
+fn unc_path(server: &str, share: Option<&str>) -> Result<String, String> {
     if server.is_empty() {
         return Err("Server address is required".to_string());
     }
 
-    // Build UNC path: \\server\share or \\server
-    let unc_path = match share {
+    Ok(match share {
         Some(s) if !s.is_empty() => format!("\\\\{}\\{}", server, s),
         _ => format!("\\\\{}", server),
-    };
+    })
+}
+
+/// Opens an SMB path in Windows Explorer
+///
+/// Constructs a UNC path from server and optional share name, then opens it in Explorer.
+/// Example: open_smb_path("192.168.2.133", Some("shared")) opens \\192.168.2.133\shared
+#[tauri::command]
+pub async fn open_smb_path(server: String, share: Option<String>) -> Result<(), AppError> {
+    let unc_path = unc_path(&server, share.as_deref())?;
 
     #[cfg(target_os = "windows")]
     {
@@ -150,12 +239,103 @@ pub async fn open_smb_path(server: String, share: Option<String>) -> Result<(),
 
         match result {
             Ok(_) => Ok(()),
-            Err(e) => Err(format!("Failed to open file explorer: {}", e)),
+            Err(e) => Err(format!("Failed to open file explorer: {}", e).into()),
         }
     }
 
     #[cfg(not(target_os = "windows"))]
     {
-        Err("SMB path opening is only supported on Windows".to_string())
+        let _ = unc_path;
+        Err("SMB path opening is only supported on Windows".into())
+    }
+}
+
+/// Normalize "z", "Z:" or "Z:\\" to "Z:"
+fn normalize_drive_letter(drive_letter: &str) -> Result<String, String> {
+    let trimmed = drive_letter.trim().trim_end_matches(['\\', '/']);
+    let letter = trimmed.strip_suffix(':').unwrap_or(trimmed);
+    match letter.chars().next() {
+        Some(c) if letter.len() == 1 && c.is_ascii_alphabetic() => {
+            Ok(format!("{}:", c.to_ascii_uppercase()))
+        }
+        _ => Err(format!("Invalid drive letter: {}", drive_letter)),
+    }
+}
+
+/// Opens a mapped network drive in Windows Explorer
+///
+/// Fails if the drive letter isn't currently listed by `list_mapped_drives`.
+#[tauri::command]
+pub async fn open_mapped_drive(drive_letter: String) -> Result<(), AppError> {
+    let drive = normalize_drive_letter(&drive_letter)?;
+
+    let mapped = crate::smb::list_mapped_drives()?
+        .into_iter()
+        .any(|m| m.local_path.eq_ignore_ascii_case(&drive));
+    if !mapped {
+        return Err(AppError::NotFound(format!(
+            "{} is not a mapped network drive",
+            drive
+        )));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        Ok(open_in_explorer_windows(&format!("{}\\", drive), true)?)
     }
-}
\ No newline at end of file
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("Mapped drives are only supported on Windows".into())
+    }
+}
+
+/// Copies a file path (or any path text) to the clipboard and returns the text copied
+#[tauri::command]
+pub fn copy_path_to_clipboard(app: AppHandle, path: String) -> Result<String, AppError> {
+    if path.trim().is_empty() {
+        return Err("No path provided".into());
+    }
+
+    app.clipboard()
+        .write_text(path.clone())
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    Ok(path)
+}
+
+/// Copies the UNC path \\server\share (or \\server) to the clipboard and returns it
+#[tauri::command]
+pub fn copy_smb_path(
+    app: AppHandle,
+    server: String,
+    share: Option<String>,
+) -> Result<String, AppError> {
+    let unc_path = unc_path(&server, share.as_deref())?;
+    copy_path_to_clipboard(app, unc_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unc_path() {
+        assert_eq!(
+            unc_path("192.168.2.133", Some("shared")).unwrap(),
+            "\\\\192.168.2.133\\shared"
+        );
+        assert_eq!(unc_path("nas", Some("")).unwrap(), "\\\\nas");
+        assert_eq!(unc_path("nas", None).unwrap(), "\\\\nas");
+        assert!(unc_path("", Some("shared")).is_err());
+    }
+
+    #[test]
+    fn test_normalize_drive_letter() {
+        assert_eq!(normalize_drive_letter("z").unwrap(), "Z:");
+        assert_eq!(normalize_drive_letter("Z:").unwrap(), "Z:");
+        assert_eq!(normalize_drive_letter(" y:\\ ").unwrap(), "Y:");
+        assert!(normalize_drive_letter("").is_err());
+        assert!(normalize_drive_letter("ZZ:").is_err());
+        assert!(normalize_drive_letter("1:").is_err());
+    }
+}
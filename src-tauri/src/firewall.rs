@@ -1,8 +1,32 @@
 use serde::{Deserialize, Serialize};
-use std::process::Command;
-use std::os::windows::process::CommandExt;
+use lazy_static::lazy_static;
+use log::{debug, info, warn};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
 
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+use crate::cache::FIREWALL_CACHE;
+use crate::error::AppError;
+use crate::ps::{run_cmd_privileged, run_powershell, run_privileged, spawn_cmd};
+
+/// Event emitted by the firewall watcher when a profile's state changes
+const FIREWALL_CHANGED_EVENT: &str = "firewall-changed";
+
+/// Lower bound for the watcher poll interval
+const MIN_WATCH_INTERVAL_MS: u64 = 1000;
+
+/// Running firewall watcher thread and its stop flag
+struct FirewallWatch {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+lazy_static! {
+    static ref FIREWALL_WATCH: Mutex<Option<FirewallWatch>> = Mutex::new(None);
+}
 
 /// Firewall profile status (reserved for future use)
 #[allow(dead_code)]
@@ -13,7 +37,7 @@ pub struct FirewallProfile {
 }
 
 /// All firewall profiles status
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct FirewallStatus {
     pub domain: bool,
     pub private: bool,
@@ -21,19 +45,39 @@ pub struct FirewallStatus {
 }
 
 /// Get firewall status for all profiles
+/// Served from a short-TTL cache so UI polling doesn't spawn PowerShell every time;
+/// falls back to an expired entry if the query fails
 #[tauri::command]
-pub fn get_firewall_status() -> Result<FirewallStatus, String> {
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args([
-            "-NoProfile",
-            "-Command",
-            "Get-NetFirewallProfile | Select-Object Name, Enabled | ConvertTo-Json",
-        ])
-        .output()
-        .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
+pub fn get_firewall_status() -> Result<FirewallStatus, AppError> {
+    if let Some(cached) = FIREWALL_CACHE.get_status() {
+        return Ok(cached);
+    }
+
+    let start_time = Instant::now();
+    let status = match query_firewall_status() {
+        Ok(status) => status,
+        Err(e) => {
+            return FIREWALL_CACHE
+                .get_status_stale()
+                .map(|(stale, _)| stale)
+                .ok_or(AppError::from(e));
+        }
+    };
+    debug!("Queried firewall status in {}ms", start_time.elapsed().as_millis());
+
+    FIREWALL_CACHE.set_status(status.clone());
+    Ok(status)
+}
+
+/// Drop the cached firewall status so the next read queries PowerShell
+#[tauri::command]
+pub fn invalidate_firewall_cache() {
+    FIREWALL_CACHE.invalidate_status();
+}
+
+fn query_firewall_status() -> Result<FirewallStatus, String> {
+    let stdout =
+        run_powershell("Get-NetFirewallProfile | Select-Object Name, Enabled | ConvertTo-Json")?;
     let trimmed = stdout.trim();
 
     if trimmed.is_empty() {
@@ -79,86 +123,500 @@ pub fn get_firewall_status() -> Result<FirewallStatus, String> {
     Ok(status)
 }
 
+/// Default inbound action per profile as (profile, action)
+/// "NotConfigured" means the Windows default, which blocks
+pub(crate) fn default_inbound_actions() -> Result<Vec<(String, String)>, String> {
+    let stdout = run_powershell(
+        r#"ConvertTo-Json -InputObject @(Get-NetFirewallProfile | ForEach-Object { @{ name = $_.Name; action = "$($_.DefaultInboundAction)" } }) -Compress"#,
+    )?;
+    let profiles: Vec<serde_json::Value> =
+        serde_json::from_str(stdout.trim()).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+    Ok(profiles
+        .iter()
+        .map(|p| {
+            (
+                p["name"].as_str().unwrap_or("").to_string(),
+                p["action"].as_str().unwrap_or("").to_string(),
+            )
+        })
+        .collect())
+}
+
+/// A Windows Firewall rule with its port filter
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FirewallRule {
+    pub name: String,
+    pub display_name: String,
+    pub direction: String,
+    pub action: String,
+    pub enabled: bool,
+    pub protocol: String,
+    pub local_port: String,
+    pub remote_port: String,
+}
+
+/// Normalize "inbound"/"outbound" to the casing PowerShell uses
+fn parse_direction(direction: &str) -> Result<&'static str, String> {
+    match direction.trim().to_lowercase().as_str() {
+        "inbound" => Ok("Inbound"),
+        "outbound" => Ok("Outbound"),
+        _ => Err(format!("Invalid direction (expected Inbound or Outbound): {}", direction)),
+    }
+}
+
+/// Normalize a profile name ("domain", "Private", ...) to the casing PowerShell uses
+pub(crate) fn parse_profile(profile: &str) -> Result<&'static str, String> {
+    match profile.trim().to_lowercase().as_str() {
+        "domain" => Ok("Domain"),
+        "private" => Ok("Private"),
+        "public" => Ok("Public"),
+        _ => Err(format!("Invalid profile (expected Domain, Private or Public): {}", profile)),
+    }
+}
+
+/// List firewall rules, optionally only "Inbound" or "Outbound" ones
+/// Rules whose port filter can't be resolved are skipped
+#[tauri::command]
+pub fn list_firewall_rules(direction: Option<String>) -> Result<Vec<FirewallRule>, AppError> {
+    let filter = match direction.as_deref() {
+        Some(d) => format!("-Direction {}", parse_direction(d)?),
+        None => String::new(),
+    };
+
+    // Port filters are fetched once and joined by InstanceID; per-rule lookups are very slow
+    let script = format!(
+        r#"
+        $ports = @{{}}
+        Get-NetFirewallPortFilter -All -ErrorAction SilentlyContinue | ForEach-Object {{ $ports[$_.InstanceID] = $_ }}
+        $rules = @(Get-NetFirewallRule {} -ErrorAction SilentlyContinue | ForEach-Object {{
+            $port = $ports[$_.Name]
+            if ($port) {{
+                @{{
+                    name = $_.Name
+                    display_name = "$($_.DisplayName)"
+                    direction = "$($_.Direction)"
+                    action = "$($_.Action)"
+                    enabled = ("$($_.Enabled)" -eq 'True')
+                    protocol = "$($port.Protocol)"
+                    local_port = ($port.LocalPort -join ',')
+                    remote_port = ($port.RemotePort -join ',')
+                }}
+            }}
+        }})
+        ConvertTo-Json -InputObject $rules -Compress
+        "#,
+        filter
+    );
+
+    let stdout = run_powershell(&script)?;
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(serde_json::from_str(trimmed)?)
+}
+
+/// Error prefix for a rule name that's already taken, so the UI can offer to overwrite
+pub const FIREWALL_RULE_EXISTS: &str = "RuleExists";
+
+/// Validated arguments for a new firewall rule
+#[derive(Debug, PartialEq)]
+struct NewRuleArgs {
+    direction: &'static str,
+    protocol: &'static str,
+    action: &'static str,
+    port: u16,
+}
+
+fn validate_new_rule(direction: &str, protocol: &str, port: u32, action: &str) -> Result<NewRuleArgs, String> {
+    let direction = parse_direction(direction)?;
+    let protocol = match protocol.trim().to_uppercase().as_str() {
+        "TCP" => "TCP",
+        "UDP" => "UDP",
+        _ => return Err(format!("Invalid protocol (expected TCP or UDP): {}", protocol)),
+    };
+    let action = match action.trim().to_lowercase().as_str() {
+        "allow" => "Allow",
+        "block" => "Block",
+        _ => return Err(format!("Invalid action (expected Allow or Block): {}", action)),
+    };
+    let port = u16::try_from(port)
+        .ok()
+        .filter(|p| *p >= 1)
+        .ok_or_else(|| format!("Invalid port (expected 1-65535): {}", port))?;
+
+    Ok(NewRuleArgs {
+        direction,
+        protocol,
+        action,
+        port,
+    })
+}
+
+/// Create a port rule; fails with a `RuleExists:` error if the name is taken
+#[tauri::command]
+pub fn add_firewall_rule(
+    name: String,
+    direction: String,
+    protocol: String,
+    port: u32,
+    action: String,
+) -> Result<String, AppError> {
+    let args = validate_new_rule(&direction, &protocol, port, &action)?;
+    if name.trim().is_empty() {
+        return Err("Rule name cannot be empty".into());
+    }
+    let name_escaped = name.replace("'", "''");
+
+    let exists = run_powershell(&format!(
+        "if (Get-NetFirewallRule -Name '{}' -ErrorAction SilentlyContinue) {{ 'true' }} else {{ 'false' }}",
+        name_escaped
+    ))?;
+    if exists.trim() == "true" {
+        return Err(format!(
+            "{}: a firewall rule named '{}' already exists",
+            FIREWALL_RULE_EXISTS, name
+        )
+        .into());
+    }
+
+    let script = format!(
+        "(New-NetFirewallRule -Name '{0}' -DisplayName '{0}' -Direction {1} -Protocol {2} -LocalPort {3} -Action {4} -ErrorAction Stop).DisplayName",
+        name_escaped, args.direction, args.protocol, args.port, args.action
+    );
+    let display_name = run_privileged(&format!("add firewall rule '{}'", name), &script)?;
+    Ok(display_name.trim().to_string())
+}
+
+/// Remove a firewall rule by name
+#[tauri::command]
+pub fn remove_firewall_rule(name: String) -> Result<String, AppError> {
+    let script = format!(
+        "Remove-NetFirewallRule -Name '{}' -ErrorAction Stop",
+        name.replace("'", "''")
+    );
+    run_privileged(&format!("remove firewall rule '{}'", name), &script)?;
+    Ok(format!("Removed firewall rule {}", name))
+}
+
 /// Enable or disable a specific firewall profile
 #[tauri::command]
-pub fn set_firewall_profile(profile: String, enabled: bool) -> Result<String, String> {
+pub fn set_firewall_profile(profile: String, enabled: bool) -> Result<String, AppError> {
+    let profile = parse_profile(&profile)?;
     let state = if enabled { "True" } else { "False" };
     let cmd = format!(
         "Set-NetFirewallProfile -Profile {} -Enabled {}",
         profile, state
     );
 
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output()
-        .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
+    let result = run_privileged(&format!("change the {} firewall profile", profile), &cmd);
+    invalidate_firewall_cache();
+    result?;
 
-    if output.status.success() {
-        let action = if enabled { "enabled" } else { "disabled" };
-        Ok(format!("{} profile {}", profile, action))
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Failed to set {} profile: {}", profile, stderr))
-    }
+    let action = if enabled { "enabled" } else { "disabled" };
+    Ok(format!("{} profile {}", profile, action))
 }
 
 /// Enable all firewall profiles
 #[tauri::command]
-pub fn enable_all_firewall() -> Result<String, String> {
+pub fn enable_all_firewall() -> Result<String, AppError> {
     let cmd = "Set-NetFirewallProfile -Profile Domain,Public,Private -Enabled True";
 
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", cmd])
-        .output()
-        .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
+    let result = run_privileged("enable all firewall profiles", cmd);
+    invalidate_firewall_cache();
+    result?;
 
-    if output.status.success() {
-        Ok("All firewall profiles enabled".to_string())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Failed to enable all profiles: {}", stderr))
-    }
+    Ok("All firewall profiles enabled".to_string())
 }
 
 /// Disable all firewall profiles
 #[tauri::command]
-pub fn disable_all_firewall() -> Result<String, String> {
+pub fn disable_all_firewall() -> Result<String, AppError> {
     let cmd = "Set-NetFirewallProfile -Profile Domain,Public,Private -Enabled False";
 
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", cmd])
-        .output()
-        .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
+    let result = run_privileged("disable all firewall profiles", cmd);
+    invalidate_firewall_cache();
+    result?;
 
-    if output.status.success() {
-        Ok("All firewall profiles disabled".to_string())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Failed to disable all profiles: {}", stderr))
-    }
+    Ok("All firewall profiles disabled".to_string())
 }
 
 /// Open Windows Firewall settings
 #[tauri::command]
-pub fn open_firewall_settings() -> Result<String, String> {
-    Command::new("cmd")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["/c", "control", "firewall.cpl"])
-        .spawn()
+pub fn open_firewall_settings() -> Result<String, AppError> {
+    spawn_cmd("cmd", &["/c", "control", "firewall.cpl"])
         .map_err(|e| format!("Failed to open: {}", e))?;
     Ok("Opened Firewall Settings".to_string())
 }
 
 /// Open Advanced Firewall settings
 #[tauri::command]
-pub fn open_advanced_firewall() -> Result<String, String> {
-    Command::new("cmd")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["/c", "wf.msc"])
-        .spawn()
-        .map_err(|e| format!("Failed to open: {}", e))?;
+pub fn open_advanced_firewall() -> Result<String, AppError> {
+    spawn_cmd("cmd", &["/c", "wf.msc"]).map_err(|e| format!("Failed to open: {}", e))?;
     Ok("Opened Advanced Firewall".to_string())
 }
+
+/// Create an inbound and an outbound rule for a program with the given action
+/// The rules are named "<name> (Inbound)" and "<name> (Outbound)"
+fn add_program_rules(name: &str, exe_path: &str, action: &str) -> Result<Vec<String>, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Rule name cannot be empty".into());
+    }
+    let exe = Path::new(exe_path.trim());
+    if !exe.is_file() {
+        return Err(AppError::NotFound(format!("Program not found: {}", exe_path)));
+    }
+    if !exe.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("exe")) {
+        return Err(format!("Not an executable (.exe): {}", exe_path).into());
+    }
+
+    let rule_names: Vec<String> = ["Inbound", "Outbound"]
+        .iter()
+        .map(|direction| format!("{} ({})", name, direction))
+        .collect();
+    let escaped: Vec<String> = rule_names.iter().map(|n| n.replace("'", "''")).collect();
+
+    let existing = run_powershell(&format!(
+        "@(Get-NetFirewallRule -Name '{}','{}' -ErrorAction SilentlyContinue).Count",
+        escaped[0], escaped[1]
+    ))?;
+    if existing.trim() != "0" {
+        return Err(format!(
+            "{}: firewall rules for '{}' already exist",
+            FIREWALL_RULE_EXISTS, name
+        )
+        .into());
+    }
+
+    let program = exe_path.trim().replace("'", "''");
+    let script = format!(
+        "New-NetFirewallRule -Name '{0}' -DisplayName '{0}' -Direction Inbound -Program '{2}' -Action {3} -ErrorAction Stop | Out-Null; \
+         New-NetFirewallRule -Name '{1}' -DisplayName '{1}' -Direction Outbound -Program '{2}' -Action {3} -ErrorAction Stop | Out-Null",
+        escaped[0], escaped[1], program, action
+    );
+    let operation = format!("add firewall rules for '{}'", name);
+    run_privileged(&operation, &script)?;
+
+    Ok(rule_names)
+}
+
+/// Let a program through the firewall in both directions; returns the created rule names
+#[tauri::command]
+pub fn allow_app_through_firewall(name: String, exe_path: String) -> Result<Vec<String>, AppError> {
+    add_program_rules(&name, &exe_path, "Allow")
+}
+
+/// Block a program in both directions; returns the created rule names
+#[tauri::command]
+pub fn block_app(name: String, exe_path: String) -> Result<Vec<String>, AppError> {
+    add_program_rules(&name, &exe_path, "Block")
+}
+
+/// Windows' default firewall log, used when a profile has no log file configured
+const DEFAULT_FIREWALL_LOG: &str = r"%systemroot%\system32\LogFiles\Firewall\pfirewall.log";
+
+/// Largest log size Windows Firewall accepts
+const MAX_FIREWALL_LOG_KB: u32 = 32767;
+
+/// Logging settings of one firewall profile
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FirewallLogging {
+    pub profile: String,
+    /// Log packets the firewall drops
+    pub log_dropped: bool,
+    /// Log connections the firewall allows
+    pub log_allowed: bool,
+    pub max_size_kb: u32,
+    /// Log file with environment variables expanded, ready to open
+    pub log_file_path: String,
+}
+
+/// Get the logging settings and log file of a firewall profile
+#[tauri::command]
+pub fn get_firewall_logging(profile: String) -> Result<FirewallLogging, AppError> {
+    let profile = parse_profile(&profile)?;
+    let script = format!(
+        r#"
+        $p = Get-NetFirewallProfile -Profile {0} -ErrorAction Stop
+        $file = if ("$($p.LogFileName)" -in @('', 'NotConfigured')) {{ '{1}' }} else {{ $p.LogFileName }}
+        @{{
+            profile = "$($p.Name)"
+            log_dropped = ("$($p.LogBlocked)" -eq 'True')
+            log_allowed = ("$($p.LogAllowed)" -eq 'True')
+            max_size_kb = [int]$p.LogMaxSizeKilobytes
+            log_file_path = [Environment]::ExpandEnvironmentVariables($file)
+        }} | ConvertTo-Json -Compress
+        "#,
+        profile, DEFAULT_FIREWALL_LOG
+    );
+
+    let stdout = run_powershell(&script)?;
+    Ok(serde_json::from_str(stdout.trim())?)
+}
+
+/// Turn logging of dropped/allowed connections on or off for a profile and set the log size
+/// Keeps the profile's log file (or Windows' default); requires administrator privileges
+#[tauri::command]
+pub fn set_firewall_logging(
+    profile: String,
+    log_dropped: bool,
+    log_allowed: bool,
+    max_size_kb: u32,
+) -> Result<FirewallLogging, AppError> {
+    let profile = parse_profile(&profile)?;
+    if !(1..=MAX_FIREWALL_LOG_KB).contains(&max_size_kb) {
+        return Err(format!(
+            "Invalid log size (expected 1-{} KB): {}",
+            MAX_FIREWALL_LOG_KB, max_size_kb
+        )
+        .into());
+    }
+
+    let flag = |on: bool| if on { "True" } else { "False" };
+    let script = format!(
+        r#"
+        $p = Get-NetFirewallProfile -Profile {0} -ErrorAction Stop
+        $file = if ("$($p.LogFileName)" -in @('', 'NotConfigured')) {{ '{1}' }} else {{ $p.LogFileName }}
+        Set-NetFirewallProfile -Profile {0} -LogBlocked {2} -LogAllowed {3} -LogMaxSizeKilobytes {4} -LogFileName $file -ErrorAction Stop
+        "#,
+        profile,
+        DEFAULT_FIREWALL_LOG,
+        flag(log_dropped),
+        flag(log_allowed),
+        max_size_kb
+    );
+    run_privileged(&format!("change {} firewall logging", profile), &script)?;
+
+    get_firewall_logging(profile.to_string())
+}
+
+/// Export the whole firewall policy (rules and profile settings) to a .wfw file
+/// Requires administrator privileges
+#[tauri::command]
+pub fn export_firewall_rules(path: String) -> Result<String, AppError> {
+    let path = path.trim().to_string();
+    if path.is_empty() {
+        return Err("Export path is required".into());
+    }
+    let parent = Path::new(&path).parent().filter(|p| !p.as_os_str().is_empty());
+    if parent.is_some_and(|dir| !dir.is_dir()) {
+        return Err(AppError::NotFound(format!("Folder not found for {}", path)));
+    }
+
+    run_cmd_privileged(
+        "export firewall rules",
+        "netsh",
+        &["advfirewall", "export", &path],
+    )?;
+    Ok(format!("Firewall rules exported to {}", path))
+}
+
+/// Replace the firewall policy with one previously exported to a .wfw file
+/// Requires administrator privileges
+#[tauri::command]
+pub fn import_firewall_rules(path: String) -> Result<String, AppError> {
+    let path = path.trim().to_string();
+    if !Path::new(&path).is_file() {
+        return Err(AppError::NotFound(format!("Firewall policy file not found: {}", path)));
+    }
+
+    let result = run_cmd_privileged(
+        "import firewall rules",
+        "netsh",
+        &["advfirewall", "import", &path],
+    );
+    invalidate_firewall_cache();
+    result?;
+
+    Ok(format!("Firewall rules imported from {}", path))
+}
+
+/// Poll firewall state and emit `firewall-changed` with the new status when
+/// any profile's enabled state changes. Restarts the watcher if already running.
+#[tauri::command]
+pub fn start_firewall_watch(app: AppHandle, interval_ms: Option<u64>) -> Result<String, AppError> {
+    stop_firewall_watch();
+
+    let interval = Duration::from_millis(interval_ms.unwrap_or(5000).max(MIN_WATCH_INTERVAL_MS));
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+
+    let handle = std::thread::spawn(move || {
+        // Baseline snapshot; a change is only reported relative to this
+        let mut last = get_firewall_status().ok();
+
+        while !thread_stop.load(Ordering::Relaxed) {
+            // Sleep in short steps so stop requests are honoured promptly
+            let deadline = Instant::now() + interval;
+            while Instant::now() < deadline && !thread_stop.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            if thread_stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            // Query directly: the status cache would hide changes for up to its TTL
+            match query_firewall_status() {
+                Ok(status) => {
+                    FIREWALL_CACHE.set_status(status.clone());
+                    if last.as_ref() != Some(&status) {
+                        if last.is_some() {
+                            info!("Firewall state changed: {:?}", status);
+                            if let Err(e) = app.emit(FIREWALL_CHANGED_EVENT, &status) {
+                                warn!("Failed to emit {}: {}", FIREWALL_CHANGED_EVENT, e);
+                            }
+                        }
+                        last = Some(status);
+                    }
+                }
+                Err(e) => debug!("Firewall watch poll failed: {}", e),
+            }
+        }
+    });
+
+    *FIREWALL_WATCH.lock().unwrap_or_else(|e| e.into_inner()) = Some(FirewallWatch { stop, handle });
+    Ok(format!("Watching firewall every {}ms", interval.as_millis()))
+}
+
+/// Stop the firewall watcher, waiting for its thread to exit
+#[tauri::command]
+pub fn stop_firewall_watch() {
+    let watch = FIREWALL_WATCH.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(watch) = watch {
+        watch.stop.store(true, Ordering::Relaxed);
+        let _ = watch.handle.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_new_rule() {
+        assert_eq!(
+            validate_new_rule("inbound", "tcp", 8080, "ALLOW"),
+            Ok(NewRuleArgs {
+                direction: "Inbound",
+                protocol: "TCP",
+                action: "Allow",
+                port: 8080,
+            })
+        );
+        assert!(validate_new_rule("sideways", "TCP", 80, "Allow").is_err());
+        assert!(validate_new_rule("Inbound", "ICMP", 80, "Allow").is_err());
+        assert!(validate_new_rule("Inbound", "TCP", 80, "Deny").is_err());
+        assert!(validate_new_rule("Inbound", "TCP", 0, "Allow").is_err());
+        assert!(validate_new_rule("Inbound", "TCP", 65536, "Allow").is_err());
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(parse_profile(" PUBLIC "), Ok("Public"));
+        assert_eq!(parse_profile("domain"), Ok("Domain"));
+        assert!(parse_profile("Public; Remove-Item").is_err());
+    }
+}
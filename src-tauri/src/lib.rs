@@ -2,11 +2,20 @@
 mod admin;
 mod cache;
 mod diagnostics;
+mod error;
 mod file_manager;
 mod firewall;
 mod network;
 mod network_unified;
+mod printers;
+mod ps;
+mod report;
+mod security;
 mod smb;
+mod snapshot;
+mod tls;
+mod upnp;
+mod wifi;
 
 // Re-export command functions
 use admin::*;
@@ -15,64 +24,214 @@ use file_manager::*;
 use firewall::*;
 use network::*;
 use network_unified::*;
+use printers::*;
+use ps::*;
+use report::*;
+use security::*;
 use smb::*;
+use snapshot::*;
+use tls::*;
+use upnp::*;
+use wifi::*;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .setup(|_app| {
+            // Probe PowerShell once in the background so the first command doesn't pay for it
+            std::thread::spawn(check_powershell_available);
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // SMB Commands
             get_smb_settings,
             set_smb_guest_auth,
             set_smb_client_signature,
             set_smb_server_signature,
+            is_smb1_enabled,
+            set_smb1_enabled,
             reset_smb_settings,
             restart_smb_service,
+            get_smb_durability_settings,
+            set_smb_durability_settings,
             test_smb_connection,
+            probe_smb_security,
+            get_smb_connection_info,
             list_smb_shares,
+            save_smb_credential,
+            delete_smb_credential,
+            create_smb_share,
+            remove_smb_share,
+            get_share_permissions,
             map_network_drive,
             unmap_network_drive,
+            list_mapped_drives,
+            cleanup_dead_mappings,
             open_advanced_sharing,
+            save_smb_favorite,
+            list_smb_favorites,
+            remove_smb_favorite,
+            // PowerShell Commands
+            check_powershell_available,
+            get_powershell_edition,
+            set_powershell_preference,
             // Admin Commands
             is_admin,
+            can_elevate,
             request_elevation,
+            run_elevated,
             // Network Commands
             get_network_adapters,
+            get_nic_teams,
             get_ip_configuration,
             apply_dhcp,
+            apply_dhcp_verified,
+            get_dhcp_option_details,
+            get_dhcp_lease,
             apply_static_ip,
+            apply_static_ipv6,
+            get_all_ip_addresses,
+            add_secondary_ip,
+            remove_ip_address,
+            set_dns_servers,
+            apply_dns_preset,
+            enable_adapter,
+            disable_adapter,
+            rename_adapter,
+            get_adapter_link_info,
+            get_mtu,
+            set_mtu,
+            set_mac_address,
+            reset_mac_address,
+            get_wol_enabled,
+            set_wol_enabled,
+            get_adapter_statistics,
+            get_adapter_throughput,
+            backup_adapter_config,
+            list_adapter_backups,
+            restore_adapter_config,
             run_ipconfig,
+            get_ipconfig_structured,
             release_ip,
             renew_ip,
+            release_ip_adapter,
+            renew_ip_adapter,
             flush_dns,
+            flush_all_caches,
+            reset_dns_registration,
             display_dns,
+            read_hosts_file,
+            write_hosts_entry,
+            remove_hosts_entry,
             open_network_connections,
             open_network_settings,
+            detect_wpad,
+            get_proxy_settings,
+            set_proxy_settings,
+            get_ipv6_transition_status,
+            set_ipv6_transition_enabled,
+            cidr_to_range,
+            range_to_cidrs,
+            get_route_table,
+            add_route,
+            delete_route,
             // Network Unified Commands (optimized with caching)
             get_ip_configuration_unified,
+            get_all_ip_configurations,
             invalidate_adapter_cache,
             invalidate_all_network_cache,
             get_network_cache_stats,
+            reset_cache_stats,
+            set_cache_ttl,
+            get_cache_ttl,
+            stress_cache,
+            // WiFi Commands
+            get_wifi_info,
+            scan_wifi_networks,
+            connect_wifi,
             // Firewall Commands
             get_firewall_status,
+            invalidate_firewall_cache,
+            list_firewall_rules,
+            add_firewall_rule,
+            remove_firewall_rule,
+            allow_app_through_firewall,
+            block_app,
+            get_firewall_logging,
+            set_firewall_logging,
+            start_firewall_watch,
+            stop_firewall_watch,
             set_firewall_profile,
             enable_all_firewall,
             disable_all_firewall,
             open_firewall_settings,
             open_advanced_firewall,
+            export_firewall_rules,
+            import_firewall_rules,
             // Diagnostic Commands
             run_ping,
+            run_ping_structured,
+            start_ping_stream,
+            stop_ping_stream,
             run_tracert,
+            run_tracert_structured,
             run_nslookup,
+            scan_ports,
+            ping_sweep,
+            reverse_dns,
+            reverse_dns_many,
+            discover_path_mtu,
+            run_whois,
+            get_arp_table,
             run_netstat,
+            get_connections_with_pid,
+            kill_process,
             get_hostname,
             get_network_info,
             check_internet,
+            check_http,
+            detect_captive_portal,
+            start_connectivity_monitor,
+            stop_connectivity_monitor,
+            compare_dns_across_adapters,
+            measure_dns_resolution,
+            get_time_sync_status,
+            resync_time,
+            identify_router,
+            inspect_tls,
+            test_bufferbloat,
+            // Printer Commands
+            list_network_printers,
+            test_printer_connection,
+            // Security Commands
+            get_security_posture,
+            // Snapshot Commands
+            capture_network_snapshot,
+            diff_network_snapshots,
+            // Report Commands
+            export_network_report,
+            // UPnP Commands
+            discover_upnp_gateway,
+            add_upnp_port_mapping,
+            remove_upnp_port_mapping,
             // File Manager Commands
             open_in_file_explorer,
+            open_with,
+            open_mapped_drive,
+            copy_path_to_clipboard,
+            copy_smb_path,
             open_smb_path,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Background watchers hold an AppHandle; stop them before teardown
+                stop_firewall_watch();
+                stop_all_ping_streams();
+                halt_connectivity_monitor();
+            }
+        });
 }
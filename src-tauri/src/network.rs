@@ -1,8 +1,17 @@
+use lazy_static::lazy_static;
+use log::warn;
 use serde::{Deserialize, Serialize};
-use std::process::Command;
-use std::os::windows::process::CommandExt;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, TcpStream, ToSocketAddrs};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use winreg::enums::*;
+use winreg::RegKey;
 
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+use crate::error::AppError;
+use crate::ps::{run_cmd, run_cmd_privileged, run_powershell, run_privileged, spawn_cmd};
 
 /// Network adapter information
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -22,57 +31,49 @@ pub struct IPConfiguration {
     pub primary_dns: String,
     pub secondary_dns: String,
     pub dhcp_enabled: bool,
+    /// Global IPv6 address; empty on IPv4-only networks
+    #[serde(default)]
+    pub ipv6_address: String,
+    /// 0 when there is no IPv6 address
+    #[serde(default)]
+    pub ipv6_prefix_length: u8,
+    #[serde(default)]
+    pub ipv6_gateway: String,
 }
 
 /// Get list of network adapters
 #[tauri::command]
-pub fn get_network_adapters() -> Result<Vec<NetworkAdapter>, String> {
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args([
-            "-NoProfile",
-            "-Command",
-            r#"Get-NetAdapter | Where-Object {
-                $_.InterfaceDescription -like '*PCI*' -or
-                $_.PnPDeviceID -like '*PCI*' -or
-                $_.InterfaceDescription -like '*Ethernet*' -or
-                $_.InterfaceDescription -like '*Realtek*' -or
-                $_.InterfaceDescription -like '*Intel*' -or
-                $_.InterfaceDescription -like '*Killer*' -or
-                $_.InterfaceDescription -like '*Qualcomm*' -or
-                $_.InterfaceDescription -like '*Broadcom*' -or
-                $_.InterfaceDescription -like '*Wi-Fi*' -or
-                $_.InterfaceDescription -like '*Wireless*'
-            } | Select-Object Name, InterfaceDescription, Status, MacAddress | ConvertTo-Json"#,
-        ])
-        .output()
-        .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
-
-    if !output.status.success() {
+pub fn get_network_adapters() -> Result<Vec<NetworkAdapter>, AppError> {
+    let stdout = match run_powershell(
+        r#"Get-NetAdapter | Where-Object {
+            $_.InterfaceDescription -like '*PCI*' -or
+            $_.PnPDeviceID -like '*PCI*' -or
+            $_.InterfaceDescription -like '*Ethernet*' -or
+            $_.InterfaceDescription -like '*Realtek*' -or
+            $_.InterfaceDescription -like '*Intel*' -or
+            $_.InterfaceDescription -like '*Killer*' -or
+            $_.InterfaceDescription -like '*Qualcomm*' -or
+            $_.InterfaceDescription -like '*Broadcom*' -or
+            $_.InterfaceDescription -like '*Wi-Fi*' -or
+            $_.InterfaceDescription -like '*Wireless*'
+        } | Select-Object Name, InterfaceDescription, Status, MacAddress | ConvertTo-Json"#,
+    ) {
+        Ok(stdout) => stdout,
         // Fallback: get all adapters
-        return get_all_adapters();
-    }
+        Err(_) => return Ok(get_all_adapters()?),
+    };
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
     if stdout.trim().is_empty() {
-        return get_all_adapters();
+        return Ok(get_all_adapters()?);
     }
 
-    parse_adapters_json(&stdout)
+    Ok(parse_adapters_json(&stdout)?)
 }
 
 fn get_all_adapters() -> Result<Vec<NetworkAdapter>, String> {
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args([
-            "-NoProfile",
-            "-Command",
-            "Get-NetAdapter | Select-Object Name, InterfaceDescription, Status, MacAddress | ConvertTo-Json",
-        ])
-        .output()
-        .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = run_powershell(
+        "Get-NetAdapter | Select-Object Name, InterfaceDescription, Status, MacAddress | ConvertTo-Json",
+    )?;
     parse_adapters_json(&stdout)
 }
 
@@ -104,9 +105,46 @@ fn parse_adapters_json(json_str: &str) -> Result<Vec<NetworkAdapter>, String> {
     Ok(result)
 }
 
+/// NIC team (LBFO) information
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NicTeam {
+    pub team_name: String,
+    #[serde(default)]
+    pub members: Vec<String>,
+    pub mode: String,
+    pub status: String,
+}
+
+/// Get NIC teams configured via LBFO
+/// Returns an empty list when no teams exist or the Windows edition has no LBFO support
+#[tauri::command]
+pub fn get_nic_teams() -> Result<Vec<NicTeam>, AppError> {
+    let stdout = run_powershell(
+        r#"
+        if (-not (Get-Command Get-NetLbfoTeam -ErrorAction SilentlyContinue)) { '[]'; return }
+        $teams = @(Get-NetLbfoTeam -ErrorAction SilentlyContinue | ForEach-Object {
+            @{
+                team_name = $_.Name
+                members = @($_.Members | ForEach-Object { "$_" })
+                mode = "$($_.TeamingMode)"
+                status = "$($_.Status)"
+            }
+        })
+        ConvertTo-Json -InputObject $teams -Depth 3 -Compress
+        "#,
+    )?;
+
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(serde_json::from_str(trimmed)?)
+}
+
 /// Get IP configuration for a specific adapter
 #[tauri::command]
-pub fn get_ip_configuration(adapter_name: String) -> Result<IPConfiguration, String> {
+pub fn get_ip_configuration(adapter_name: String) -> Result<IPConfiguration, AppError> {
     let mut config = IPConfiguration {
         ip_address: String::new(),
         subnet_mask: String::new(),
@@ -114,6 +152,9 @@ pub fn get_ip_configuration(adapter_name: String) -> Result<IPConfiguration, Str
         primary_dns: String::new(),
         secondary_dns: String::new(),
         dhcp_enabled: true,
+        ipv6_address: String::new(),
+        ipv6_prefix_length: 0,
+        ipv6_gateway: String::new(),
     };
 
     // Get IP address
@@ -121,12 +162,7 @@ pub fn get_ip_configuration(adapter_name: String) -> Result<IPConfiguration, Str
         r#"Get-NetIPAddress -InterfaceAlias "{}" -AddressFamily IPv4 | Select-Object IPAddress, PrefixLength | ConvertTo-Json"#,
         adapter_name
     );
-    if let Ok(output) = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
+    if let Ok(stdout) = run_powershell(&cmd) {
         if let Ok(data) = serde_json::from_str::<serde_json::Value>(stdout.trim()) {
             let data = if data.is_array() {
                 data.get(0).cloned().unwrap_or(data)
@@ -144,12 +180,7 @@ pub fn get_ip_configuration(adapter_name: String) -> Result<IPConfiguration, Str
         r#"Get-NetRoute -InterfaceAlias "{}" -DestinationPrefix "0.0.0.0/0" -ErrorAction SilentlyContinue | Select-Object NextHop | ConvertTo-Json"#,
         adapter_name
     );
-    if let Ok(output) = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
+    if let Ok(stdout) = run_powershell(&cmd) {
         if let Ok(data) = serde_json::from_str::<serde_json::Value>(stdout.trim()) {
             let data = if data.is_array() {
                 data.get(0).cloned().unwrap_or(data)
@@ -165,12 +196,7 @@ pub fn get_ip_configuration(adapter_name: String) -> Result<IPConfiguration, Str
         r#"Get-DnsClientServerAddress -InterfaceAlias "{}" -AddressFamily IPv4 | Select-Object ServerAddresses | ConvertTo-Json"#,
         adapter_name
     );
-    if let Ok(output) = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
+    if let Ok(stdout) = run_powershell(&cmd) {
         if let Ok(data) = serde_json::from_str::<serde_json::Value>(stdout.trim()) {
             let data = if data.is_array() {
                 data.get(0).cloned().unwrap_or(data)
@@ -193,18 +219,46 @@ pub fn get_ip_configuration(adapter_name: String) -> Result<IPConfiguration, Str
         r#"Get-NetIPInterface -InterfaceAlias "{}" -AddressFamily IPv4 | Select-Object Dhcp | ConvertTo-Json"#,
         adapter_name
     );
-    if let Ok(output) = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
+    if let Ok(stdout) = run_powershell(&cmd) {
         if let Ok(data) = serde_json::from_str::<serde_json::Value>(stdout.trim()) {
             let dhcp_val = data["Dhcp"].as_u64().unwrap_or(1);
             config.dhcp_enabled = dhcp_val == 1;
         }
     }
 
+    // Get IPv6 address (skip link-local and temporary privacy addresses)
+    let cmd = format!(
+        r#"Get-NetIPAddress -InterfaceAlias "{}" -AddressFamily IPv6 -ErrorAction SilentlyContinue | Where-Object {{ $_.IPAddress -notlike 'fe80*' -and $_.SuffixOrigin -ne 'Random' }} | Select-Object IPAddress, PrefixLength | ConvertTo-Json"#,
+        adapter_name
+    );
+    if let Ok(stdout) = run_powershell(&cmd) {
+        if let Ok(data) = serde_json::from_str::<serde_json::Value>(stdout.trim()) {
+            let data = if data.is_array() {
+                data.get(0).cloned().unwrap_or(data)
+            } else {
+                data
+            };
+            config.ipv6_address = data["IPAddress"].as_str().unwrap_or("").to_string();
+            config.ipv6_prefix_length = data["PrefixLength"].as_u64().unwrap_or(0) as u8;
+        }
+    }
+
+    // Get IPv6 gateway
+    let cmd = format!(
+        r#"Get-NetRoute -InterfaceAlias "{}" -DestinationPrefix "::/0" -ErrorAction SilentlyContinue | Select-Object NextHop | ConvertTo-Json"#,
+        adapter_name
+    );
+    if let Ok(stdout) = run_powershell(&cmd) {
+        if let Ok(data) = serde_json::from_str::<serde_json::Value>(stdout.trim()) {
+            let data = if data.is_array() {
+                data.get(0).cloned().unwrap_or(data)
+            } else {
+                data
+            };
+            config.ipv6_gateway = data["NextHop"].as_str().unwrap_or("").to_string();
+        }
+    }
+
     Ok(config)
 }
 
@@ -238,59 +292,345 @@ fn subnet_to_prefix(subnet: &str) -> u8 {
     binary.chars().filter(|&c| c == '1').count() as u8
 }
 
+/// Netmask for a CIDR prefix length as an integer
+pub(crate) fn prefix_mask(prefix: u8) -> u32 {
+    if prefix == 0 {
+        0
+    } else {
+        0xFFFFFFFF << (32 - prefix.min(32))
+    }
+}
+
+/// Parse "a.b.c.d/n" into its address and prefix length
+pub(crate) fn parse_cidr(cidr: &str) -> Result<(Ipv4Addr, u8), String> {
+    let (addr, prefix) = cidr
+        .trim()
+        .split_once('/')
+        .ok_or_else(|| format!("Invalid CIDR (expected a.b.c.d/n): {}", cidr))?;
+
+    let addr: Ipv4Addr = addr
+        .parse()
+        .map_err(|_| format!("Invalid IP address: {}", addr))?;
+    let prefix: u8 = prefix
+        .parse()
+        .ok()
+        .filter(|p| *p <= 32)
+        .ok_or_else(|| format!("Invalid prefix length: {}", prefix))?;
+
+    Ok((addr, prefix))
+}
+
+/// First and last address covered by a CIDR block
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IpRange {
+    pub first_ip: String,
+    pub last_ip: String,
+}
+
+/// Convert a CIDR block to its first and last address
+#[tauri::command]
+pub fn cidr_to_range(cidr: String) -> Result<IpRange, AppError> {
+    let (addr, prefix) = parse_cidr(&cidr)?;
+    let mask = prefix_mask(prefix);
+    let first = u32::from(addr) & mask;
+    let last = first | !mask;
+
+    Ok(IpRange {
+        first_ip: Ipv4Addr::from(first).to_string(),
+        last_ip: Ipv4Addr::from(last).to_string(),
+    })
+}
+
+/// Decompose an arbitrary address range into the minimal set of CIDR blocks
+#[tauri::command]
+pub fn range_to_cidrs(first_ip: String, last_ip: String) -> Result<Vec<String>, AppError> {
+    let first: Ipv4Addr = first_ip
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid IP address: {}", first_ip))?;
+    let last: Ipv4Addr = last_ip
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid IP address: {}", last_ip))?;
+
+    // Work in u64 so the end of the address space doesn't overflow
+    let mut start = u32::from(first) as u64;
+    let end = u32::from(last) as u64;
+    if start > end {
+        return Err(format!("Range start {} is after range end {}", first, last).into());
+    }
+
+    let mut cidrs = Vec::new();
+    while start <= end {
+        // Largest block aligned on `start`...
+        let align_bits = if start == 0 { 32 } else { start.trailing_zeros().min(32) };
+        // ...that still fits in what's left of the range
+        let remaining = end - start + 1;
+        let fit_bits = 63 - remaining.leading_zeros();
+        let host_bits = align_bits.min(fit_bits);
+
+        cidrs.push(format!("{}/{}", Ipv4Addr::from(start as u32), 32 - host_bits));
+        start += 1u64 << host_bits;
+    }
+
+    Ok(cidrs)
+}
+
 /// Apply DHCP configuration to adapter
 #[tauri::command]
-pub fn apply_dhcp(adapter_name: String) -> Result<String, String> {
+pub fn apply_dhcp(adapter_name: String) -> Result<String, AppError> {
     // Remove existing static IP
     let cmd = format!(
         r#"Remove-NetIPAddress -InterfaceAlias "{}" -AddressFamily IPv4 -Confirm:$false -ErrorAction SilentlyContinue"#,
         adapter_name
     );
-    let _ = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output();
+    let _ = run_powershell(&cmd);
 
     // Remove existing gateway
     let cmd = format!(
         r#"Remove-NetRoute -InterfaceAlias "{}" -DestinationPrefix "0.0.0.0/0" -Confirm:$false -ErrorAction SilentlyContinue"#,
         adapter_name
     );
-    let _ = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output();
+    let _ = run_powershell(&cmd);
 
     // Enable DHCP
     let cmd = format!(
         r#"Set-NetIPInterface -InterfaceAlias "{}" -Dhcp Enabled"#,
         adapter_name
     );
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output()
-        .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to enable DHCP: {}", stderr));
-    }
+    run_privileged(&format!("enable DHCP on {}", adapter_name), &cmd)?;
 
     // Reset DNS
     let cmd = format!(
         r#"Set-DnsClientServerAddress -InterfaceAlias "{}" -ResetServerAddresses"#,
         adapter_name
     );
-    let _ = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output();
+    let _ = run_powershell(&cmd);
 
     Ok(format!("DHCP enabled on {}", adapter_name))
 }
 
+/// Whether an address is an APIPA (169.254.0.0/16) self-assigned address,
+/// which Windows falls back to when no DHCP server answers
+pub(crate) fn is_apipa(ip: &str) -> bool {
+    ip.parse::<Ipv4Addr>()
+        .map(|addr| addr.is_link_local())
+        .unwrap_or(false)
+}
+
+/// Outcome of enabling DHCP and waiting for a lease
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DhcpVerifyResult {
+    pub success: bool,
+    pub address: Option<String>,
+    pub got_apipa: bool,
+}
+
+/// Maximum time `apply_dhcp_verified` will wait for a lease
+const DHCP_VERIFY_MAX_WAIT_SECS: u64 = 120;
+
+fn current_ipv4_address(adapter_name: &str) -> Option<String> {
+    let script = format!(
+        r#"Get-NetIPAddress -InterfaceAlias '{}' -AddressFamily IPv4 -ErrorAction SilentlyContinue | Select-Object -First 1 -ExpandProperty IPAddress"#,
+        adapter_name.replace("'", "''")
+    );
+    run_powershell(&script)
+        .ok()
+        .map(|stdout| stdout.trim().to_string())
+        .filter(|ip| !ip.is_empty())
+}
+
+/// Enable DHCP and wait up to `wait_secs` for a real (non-APIPA) lease
+///
+/// Distinguishes "DHCP enabled but no server answered" (APIPA address)
+/// from a successful lease.
+#[tauri::command]
+pub fn apply_dhcp_verified(
+    adapter_name: String,
+    wait_secs: u64,
+) -> Result<DhcpVerifyResult, AppError> {
+    apply_dhcp(adapter_name.clone())?;
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&adapter_name);
+
+    let deadline = Instant::now() + Duration::from_secs(wait_secs.clamp(1, DHCP_VERIFY_MAX_WAIT_SECS));
+    let mut address = None;
+
+    loop {
+        address = current_ipv4_address(&adapter_name).or(address);
+        if let Some(ip) = &address {
+            if !is_apipa(ip) {
+                return Ok(DhcpVerifyResult {
+                    success: true,
+                    address,
+                    got_apipa: false,
+                });
+            }
+        }
+        if Instant::now() >= deadline {
+            break;
+        }
+        std::thread::sleep(Duration::from_secs(1));
+    }
+
+    let got_apipa = address.as_deref().map(is_apipa).unwrap_or(false);
+    Ok(DhcpVerifyResult {
+        success: false,
+        address,
+        got_apipa,
+    })
+}
+
+fn parse_ipv4_field(value: &str, label: &str) -> Result<Ipv4Addr, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid {}: {}", label, value))
+}
+
+/// Validate static IPv4 settings before anything is changed
+///
+/// Gateway and DNS may be empty (they are skipped when applying). Returns a
+/// warning when the gateway lies outside the IP's subnet, which is legal but
+/// almost always a typo.
+fn validate_static_ipv4(
+    ip_address: &str,
+    subnet_mask: &str,
+    gateway: &str,
+    primary_dns: &str,
+    secondary_dns: &str,
+) -> Result<Option<String>, String> {
+    let ip = parse_ipv4_field(ip_address, "IP address")?;
+    let mask = u32::from(parse_ipv4_field(subnet_mask, "subnet mask")?);
+    if mask.leading_ones() + mask.trailing_zeros() != 32 {
+        return Err(format!("Invalid subnet mask: {}", subnet_mask));
+    }
+
+    let gateway = if gateway.trim().is_empty() {
+        None
+    } else {
+        Some(parse_ipv4_field(gateway, "gateway")?)
+    };
+    for dns in [primary_dns, secondary_dns] {
+        if !dns.trim().is_empty() {
+            parse_ipv4_field(dns, "DNS server")?;
+        }
+    }
+
+    Ok(gateway
+        .filter(|gw| u32::from(*gw) & mask != u32::from(ip) & mask)
+        .map(|gw| {
+            format!(
+                "Gateway {} is outside the subnet of {}/{}",
+                gw,
+                ip,
+                mask.leading_ones()
+            )
+        }))
+}
+
+fn parse_ipv6_field(value: &str, label: &str) -> Result<std::net::Ipv6Addr, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid {}: {}", label, value))
+}
+
+/// Validate static IPv6 settings before anything is changed; gateway and DNS may be empty
+fn validate_static_ipv6(
+    ipv6_address: &str,
+    prefix_length: u8,
+    gateway: &str,
+    primary_dns: &str,
+    secondary_dns: &str,
+) -> Result<(), String> {
+    parse_ipv6_field(ipv6_address, "IPv6 address")?;
+    if prefix_length > 128 {
+        return Err(format!("Invalid IPv6 prefix length: {}", prefix_length));
+    }
+    if !gateway.trim().is_empty() {
+        parse_ipv6_field(gateway, "IPv6 gateway")?;
+    }
+    for dns in [primary_dns, secondary_dns] {
+        if !dns.trim().is_empty() {
+            parse_ipv6_field(dns, "IPv6 DNS server")?;
+        }
+    }
+    Ok(())
+}
+
+/// How long to wait for an echo reply when probing for an address conflict
+const IP_CONFLICT_PROBE_TIMEOUT_MS: u32 = 1000;
+
+/// Whether `target` falls inside one of the adapter's current IPv4 subnets,
+/// i.e. a probe reaches it directly instead of through the default gateway
+fn is_on_link(target: Ipv4Addr, addresses: &[(Ipv4Addr, u8)]) -> bool {
+    addresses.iter().any(|(address, prefix)| {
+        let mask = prefix_mask(*prefix);
+        u32::from(*address) & mask == u32::from(target) & mask
+    })
+}
+
+/// MAC of a `Get-NetNeighbor` entry ("<mac> <state>" lines), ignoring entries
+/// that are stale, unreachable or still unresolved
+fn parse_live_neighbor(stdout: &str) -> Option<String> {
+    stdout.lines().find_map(|line| {
+        let (mac, state) = line.trim().split_once(' ')?;
+        let live = matches!(state.trim(), "Reachable" | "Delay" | "Probe" | "Permanent");
+        let resolved = !mac.is_empty() && mac.chars().any(|c| c.is_ascii_hexdigit() && c != '0');
+        (live && resolved).then(|| mac.to_uppercase())
+    })
+}
+
+/// Probe whether another host already answers on `ip_address` on `adapter_name`'s link
+/// Pings the address, then checks the adapter's neighbor cache since the ping also
+/// resolves the MAC of hosts that drop ICMP. Addresses owned by this machine are
+/// skipped, and so are addresses outside the adapter's current subnets, where a
+/// reply would come from a routed host rather than the local network.
+fn find_ip_conflict(adapter_name: &str, ip_address: &str) -> Option<String> {
+    let target: Ipv4Addr = ip_address.parse().ok()?;
+    let alias = adapter_name.replace("'", "''");
+
+    let local = run_powershell(&format!(
+        "@(Get-NetIPAddress -IPAddress '{}' -ErrorAction SilentlyContinue).Count",
+        target
+    ));
+    if local.is_ok_and(|count| count.trim() != "0") {
+        return None;
+    }
+
+    let addresses: Vec<(Ipv4Addr, u8)> = run_powershell(&format!(
+        "Get-NetIPAddress -InterfaceAlias '{}' -AddressFamily IPv4 -ErrorAction SilentlyContinue | ForEach-Object {{ \"$($_.IPAddress)/$($_.PrefixLength)\" }}",
+        alias
+    ))
+    .unwrap_or_default()
+    .lines()
+    .filter_map(|line| parse_cidr(line).ok())
+    .collect();
+    if !is_on_link(target, &addresses) {
+        warn!(
+            "Skipping conflict check: {} is not on the current subnet of {}",
+            target, adapter_name
+        );
+        return None;
+    }
+
+    let replied = crate::diagnostics::ping_reply(ip_address, IP_CONFLICT_PROBE_TIMEOUT_MS).is_some();
+    let mac = run_powershell(&format!(
+        "Get-NetNeighbor -InterfaceAlias '{}' -IPAddress '{}' -ErrorAction SilentlyContinue | ForEach-Object {{ \"$($_.LinkLayerAddress) $($_.State)\" }}",
+        alias, target
+    ))
+    .ok()
+    .and_then(|stdout| parse_live_neighbor(&stdout));
+
+    match mac {
+        Some(mac) => Some(format!("IP address {} is already in use by {}", ip_address, mac)),
+        None if replied => Some(format!("IP address {} is already in use by another host", ip_address)),
+        None => None,
+    }
+}
+
 /// Apply static IP configuration
+/// With `check_conflict`, the address is probed first and nothing is changed if it's taken
 #[tauri::command]
 pub fn apply_static_ip(
     adapter_name: String,
@@ -299,7 +639,25 @@ pub fn apply_static_ip(
     gateway: String,
     primary_dns: String,
     secondary_dns: String,
-) -> Result<String, String> {
+    check_conflict: bool,
+) -> Result<String, AppError> {
+    let warning = validate_static_ipv4(
+        &ip_address,
+        &subnet_mask,
+        &gateway,
+        &primary_dns,
+        &secondary_dns,
+    )?;
+    if let Some(warning) = &warning {
+        warn!("{}", warning);
+    }
+
+    if check_conflict {
+        if let Some(conflict) = find_ip_conflict(&adapter_name, &ip_address) {
+            return Err(conflict.into());
+        }
+    }
+
     let prefix = subnet_to_prefix(&subnet_mask);
 
     // Remove existing IP
@@ -307,30 +665,21 @@ pub fn apply_static_ip(
         r#"Remove-NetIPAddress -InterfaceAlias "{}" -AddressFamily IPv4 -Confirm:$false -ErrorAction SilentlyContinue"#,
         adapter_name
     );
-    let _ = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output();
+    let _ = run_powershell(&cmd);
 
     // Remove existing gateway
     let cmd = format!(
         r#"Remove-NetRoute -InterfaceAlias "{}" -DestinationPrefix "0.0.0.0/0" -Confirm:$false -ErrorAction SilentlyContinue"#,
         adapter_name
     );
-    let _ = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output();
+    let _ = run_powershell(&cmd);
 
     // Disable DHCP
     let cmd = format!(
         r#"Set-NetIPInterface -InterfaceAlias "{}" -Dhcp Disabled"#,
         adapter_name
     );
-    let _ = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output();
+    let _ = run_powershell(&cmd);
 
     // Set new IP
     let cmd = if !gateway.is_empty() {
@@ -345,16 +694,7 @@ pub fn apply_static_ip(
         )
     };
 
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output()
-        .map_err(|e| format!("Failed to set IP: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to set IP: {}", stderr));
-    }
+    run_privileged(&format!("set a static IP on {}", adapter_name), &cmd)?;
 
     // Set DNS
     if !primary_dns.is_empty() {
@@ -368,99 +708,2433 @@ pub fn apply_static_ip(
             r#"Set-DnsClientServerAddress -InterfaceAlias "{}" -ServerAddresses ({})"#,
             adapter_name, dns_servers
         );
-        let _ = Command::new("powershell")
-            .creation_flags(CREATE_NO_WINDOW)
-            .args(["-NoProfile", "-Command", &cmd])
-            .output();
+        let _ = run_powershell(&cmd);
     }
 
-    Ok(format!("Static IP {} applied to {}", ip_address, adapter_name))
+    match warning {
+        Some(warning) => Ok(format!(
+            "Static IP {} applied to {} (warning: {})",
+            ip_address, adapter_name, warning
+        )),
+        None => Ok(format!("Static IP {} applied to {}", ip_address, adapter_name)),
+    }
 }
 
-/// Run ipconfig command
+/// Apply static IPv6 configuration
 #[tauri::command]
-pub fn run_ipconfig(all: bool) -> Result<String, String> {
-    let args = if all { "/all" } else { "" };
-    let output = Command::new("cmd")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["/c", "ipconfig", args])
-        .output()
-        .map_err(|e| format!("Failed to run ipconfig: {}", e))?;
+pub fn apply_static_ipv6(
+    adapter_name: String,
+    ipv6_address: String,
+    prefix_length: u8,
+    gateway: String,
+    primary_dns: String,
+    secondary_dns: String,
+) -> Result<String, AppError> {
+    validate_static_ipv6(&ipv6_address, prefix_length, &gateway, &primary_dns, &secondary_dns)?;
+    let (ipv6_address, gateway) = (ipv6_address.trim(), gateway.trim());
+    let (primary_dns, secondary_dns) = (primary_dns.trim(), secondary_dns.trim());
+
+    // Remove existing manual IPv6 addresses (autoconfigured ones are left alone)
+    let cmd = format!(
+        r#"Get-NetIPAddress -InterfaceAlias "{}" -AddressFamily IPv6 -PrefixOrigin Manual -ErrorAction SilentlyContinue | Remove-NetIPAddress -Confirm:$false -ErrorAction SilentlyContinue"#,
+        adapter_name
+    );
+    let _ = run_powershell(&cmd);
+
+    // Remove existing IPv6 gateway
+    let cmd = format!(
+        r#"Remove-NetRoute -InterfaceAlias "{}" -DestinationPrefix "::/0" -Confirm:$false -ErrorAction SilentlyContinue"#,
+        adapter_name
+    );
+    let _ = run_powershell(&cmd);
+
+    // Set new IPv6 address
+    let cmd = if !gateway.is_empty() {
+        format!(
+            r#"New-NetIPAddress -InterfaceAlias "{}" -AddressFamily IPv6 -IPAddress "{}" -PrefixLength {} -DefaultGateway "{}""#,
+            adapter_name, ipv6_address, prefix_length, gateway
+        )
+    } else {
+        format!(
+            r#"New-NetIPAddress -InterfaceAlias "{}" -AddressFamily IPv6 -IPAddress "{}" -PrefixLength {}"#,
+            adapter_name, ipv6_address, prefix_length
+        )
+    };
+
+    run_privileged(&format!("set an IPv6 address on {}", adapter_name), &cmd)?;
+
+    // Add IPv6 DNS servers alongside any IPv4 ones
+    if !primary_dns.is_empty() {
+        let dns_servers = if !secondary_dns.is_empty() {
+            format!(r#""{}","{}""#, primary_dns, secondary_dns)
+        } else {
+            format!(r#""{}""#, primary_dns)
+        };
+
+        let cmd = format!(
+            r#"$v4 = (Get-DnsClientServerAddress -InterfaceAlias "{0}" -AddressFamily IPv4).ServerAddresses; Set-DnsClientServerAddress -InterfaceAlias "{0}" -ServerAddresses (@($v4) + @({1}))"#,
+            adapter_name, dns_servers
+        );
+        let _ = run_powershell(&cmd);
+    }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&adapter_name);
+
+    Ok(format!("Static IPv6 {}/{} applied to {}", ipv6_address, prefix_length, adapter_name))
 }
 
-/// Release IP address
+/// All IPv4 addresses on an adapter as (address, prefix length)
 #[tauri::command]
-pub fn release_ip() -> Result<String, String> {
-    let output = Command::new("cmd")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["/c", "ipconfig", "/release"])
-        .output()
-        .map_err(|e| format!("Failed to release IP: {}", e))?;
+pub fn get_all_ip_addresses(adapter_name: String) -> Result<Vec<(String, u8)>, AppError> {
+    ensure_adapter_exists(&adapter_name)?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    let script = format!(
+        r#"
+        $addresses = @(Get-NetIPAddress -InterfaceAlias '{}' -AddressFamily IPv4 -ErrorAction SilentlyContinue | ForEach-Object {{
+            ,@($_.IPAddress, [int]$_.PrefixLength)
+        }})
+        ConvertTo-Json -InputObject $addresses -Compress
+        "#,
+        adapter_name.replace("'", "''")
+    );
+    let stdout = run_powershell(&script)?;
+    Ok(serde_json::from_str(stdout.trim())?)
 }
 
-/// Renew IP address
+/// Add another IPv4 address to an adapter, keeping the existing ones
 #[tauri::command]
-pub fn renew_ip() -> Result<String, String> {
-    let output = Command::new("cmd")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["/c", "ipconfig", "/renew"])
-        .output()
-        .map_err(|e| format!("Failed to renew IP: {}", e))?;
+pub fn add_secondary_ip(
+    adapter_name: String,
+    ip_address: String,
+    subnet_mask: String,
+) -> Result<String, AppError> {
+    validate_static_ipv4(&ip_address, &subnet_mask, "", "", "")?;
+    let ip_address = ip_address.trim().to_string();
+
+    if get_all_ip_addresses(adapter_name.clone())?
+        .iter()
+        .any(|(existing, _)| existing == &ip_address)
+    {
+        return Err(format!("{} is already assigned to {}", ip_address, adapter_name).into());
+    }
+
+    let prefix = subnet_to_prefix(subnet_mask.trim());
+    let script = format!(
+        "New-NetIPAddress -InterfaceAlias '{}' -IPAddress '{}' -PrefixLength {} -ErrorAction Stop | Out-Null",
+        adapter_name.replace("'", "''"),
+        ip_address,
+        prefix
+    );
+    run_privileged(&format!("add an address to {}", adapter_name), &script)?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&adapter_name);
+    Ok(format!("Added {}/{} to {}", ip_address, prefix, adapter_name))
 }
 
-/// Flush DNS cache
+/// Remove one IPv4 address from an adapter
 #[tauri::command]
-pub fn flush_dns() -> Result<String, String> {
-    let output = Command::new("cmd")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["/c", "ipconfig", "/flushdns"])
-        .output()
-        .map_err(|e| format!("Failed to flush DNS: {}", e))?;
+pub fn remove_ip_address(adapter_name: String, ip_address: String) -> Result<String, AppError> {
+    let ip_address = ip_address.trim().to_string();
+    parse_ipv4_field(&ip_address, "IP address")?;
+
+    if !get_all_ip_addresses(adapter_name.clone())?
+        .iter()
+        .any(|(existing, _)| existing == &ip_address)
+    {
+        return Err(AppError::NotFound(format!(
+            "{} is not assigned to {}",
+            ip_address, adapter_name
+        )));
+    }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    let script = format!(
+        "Remove-NetIPAddress -InterfaceAlias '{}' -IPAddress '{}' -Confirm:$false -ErrorAction Stop",
+        adapter_name.replace("'", "''"),
+        ip_address
+    );
+    run_privileged(&format!("remove an address from {}", adapter_name), &script)?;
+
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&adapter_name);
+    Ok(format!("Removed {} from {}", ip_address, adapter_name))
 }
 
-/// Display DNS cache
+/// Well-known public resolvers for `apply_dns_preset`
+const DNS_PRESETS: &[(&str, [&str; 2])] = &[
+    ("google", ["8.8.8.8", "8.8.4.4"]),
+    ("cloudflare", ["1.1.1.1", "1.0.0.1"]),
+    ("quad9", ["9.9.9.9", "149.112.112.112"]),
+    ("opendns", ["208.67.222.222", "208.67.220.220"]),
+    ("adguard", ["94.140.14.14", "94.140.15.15"]),
+];
+
+/// Check every entry is an IP address, returning them trimmed and in order
+fn validate_dns_servers(servers: &[String]) -> Result<Vec<String>, String> {
+    if servers.is_empty() {
+        return Err("At least one DNS server is required".to_string());
+    }
+    servers
+        .iter()
+        .map(|server| {
+            let server = server.trim();
+            server
+                .parse::<IpAddr>()
+                .map(|_| server.to_string())
+                .map_err(|_| format!("Invalid DNS server: {}", server))
+        })
+        .collect()
+}
+
+/// Server list for a named preset (case-insensitive)
+fn dns_preset_servers(preset: &str) -> Result<Vec<String>, String> {
+    DNS_PRESETS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(preset.trim()))
+        .map(|(_, servers)| servers.iter().map(|s| s.to_string()).collect())
+        .ok_or_else(|| {
+            let names: Vec<&str> = DNS_PRESETS.iter().map(|(name, _)| *name).collect();
+            format!("Unknown DNS preset '{}' (expected one of: {})", preset, names.join(", "))
+        })
+}
+
+/// Set an ordered list of DNS servers (IPv4 and/or IPv6) on an adapter (requires admin)
 #[tauri::command]
-pub fn display_dns() -> Result<String, String> {
-    let output = Command::new("cmd")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["/c", "ipconfig", "/displaydns"])
-        .output()
-        .map_err(|e| format!("Failed to display DNS: {}", e))?;
+pub fn set_dns_servers(adapter_name: String, servers: Vec<String>) -> Result<String, AppError> {
+    let servers = validate_dns_servers(&servers)?;
+    ensure_adapter_exists(&adapter_name)?;
+
+    let list: Vec<String> = servers.iter().map(|s| format!("'{}'", s)).collect();
+    let script = format!(
+        "Set-DnsClientServerAddress -InterfaceAlias '{}' -ServerAddresses @({}) -ErrorAction Stop",
+        adapter_name.replace("'", "''"),
+        list.join(",")
+    );
+    run_privileged(&format!("change DNS servers on {}", adapter_name), &script)?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&adapter_name);
+    Ok(format!("DNS servers on {} set to {}", adapter_name, servers.join(", ")))
 }
 
-/// Open network connections
+/// Apply a public DNS preset ("google", "cloudflare", "quad9", "opendns", "adguard")
 #[tauri::command]
-pub fn open_network_connections() -> Result<String, String> {
-    Command::new("cmd")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["/c", "ncpa.cpl"])
-        .spawn()
-        .map_err(|e| format!("Failed to open: {}", e))?;
-    Ok("Opened Network Connections".to_string())
+pub fn apply_dns_preset(adapter_name: String, preset: String) -> Result<String, AppError> {
+    set_dns_servers(adapter_name, dns_preset_servers(&preset)?)
 }
 
-/// Open network settings
+/// Make sure an adapter exists before acting on it
+pub(crate) fn ensure_adapter_exists(adapter_name: &str) -> Result<(), AppError> {
+    let script = format!(
+        "if (Get-NetAdapter -Name '{}' -ErrorAction SilentlyContinue) {{ 'true' }} else {{ 'false' }}",
+        adapter_name.replace("'", "''")
+    );
+    if run_powershell(&script)?.trim() == "true" {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!(
+            "Adapter not found: {}",
+            adapter_name
+        )))
+    }
+}
+
+/// Characters Windows doesn't allow in a connection name; * and ? are also
+/// wildcards to Get-NetAdapter
+const INVALID_ADAPTER_NAME_CHARS: &[char] = &['\\', '/', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Check a proposed adapter name
+pub(crate) fn validate_adapter_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Adapter name cannot be empty".to_string());
+    }
+    if name.len() > 256 {
+        return Err("Adapter name is too long (maximum 256 characters)".to_string());
+    }
+    if let Some(c) = name.chars().find(|c| INVALID_ADAPTER_NAME_CHARS.contains(c) || c.is_control()) {
+        return Err(format!("Adapter name cannot contain '{}'", c));
+    }
+    Ok(())
+}
+
+/// Rename an adapter (e.g. to "LAN" or "WAN")
+/// Requires administrator privileges
 #[tauri::command]
-pub fn open_network_settings() -> Result<String, String> {
-    Command::new("cmd")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["/c", "start", "ms-settings:network"])
-        .spawn()
-        .map_err(|e| format!("Failed to open: {}", e))?;
-    Ok("Opened Network Settings".to_string())
+pub fn rename_adapter(current_name: String, new_name: String) -> Result<String, AppError> {
+    let new_name = new_name.trim().to_string();
+    validate_adapter_name(&new_name)?;
+    ensure_adapter_exists(&current_name)?;
+
+    // Names are case-insensitive, so only a different adapter counts as a clash
+    if !new_name.eq_ignore_ascii_case(current_name.trim()) {
+        let taken = run_powershell(&format!(
+            "if (Get-NetAdapter -Name '{}' -ErrorAction SilentlyContinue) {{ 'true' }} else {{ 'false' }}",
+            new_name.replace("'", "''")
+        ))?;
+        if taken.trim() == "true" {
+            return Err(format!("An adapter named '{}' already exists", new_name).into());
+        }
+    }
+
+    let script = format!(
+        "Rename-NetAdapter -Name '{}' -NewName '{}' -ErrorAction Stop",
+        current_name.replace("'", "''"),
+        new_name.replace("'", "''")
+    );
+    run_privileged(&format!("rename {}", current_name), &script)?;
+
+    // Cached configs are keyed by name; drop both so nothing is served under a stale key
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&current_name);
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&new_name);
+    Ok(format!("Renamed {} to {}", current_name, new_name))
+}
+
+/// Enable or disable an adapter
+fn set_adapter_enabled(adapter_name: &str, enabled: bool) -> Result<String, AppError> {
+    ensure_adapter_exists(adapter_name)?;
+
+    let cmdlet = if enabled { "Enable-NetAdapter" } else { "Disable-NetAdapter" };
+    let script = format!(
+        "{} -Name '{}' -Confirm:$false -ErrorAction Stop",
+        cmdlet,
+        adapter_name.replace("'", "''")
+    );
+
+    let action = if enabled { "enable" } else { "disable" };
+    run_privileged(&format!("{} {}", action, adapter_name), &script)?;
+
+    crate::cache::NETWORK_CACHE.invalidate_adapter(adapter_name);
+    Ok(format!(
+        "{} {}",
+        if enabled { "Enabled" } else { "Disabled" },
+        adapter_name
+    ))
+}
+
+/// Enable a network adapter (requires admin)
+#[tauri::command]
+pub fn enable_adapter(adapter_name: String) -> Result<String, AppError> {
+    set_adapter_enabled(&adapter_name, true)
+}
+
+/// Disable a network adapter (requires admin)
+#[tauri::command]
+pub fn disable_adapter(adapter_name: String) -> Result<String, AppError> {
+    set_adapter_enabled(&adapter_name, false)
+}
+
+/// Negotiated link details for a connected adapter
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LinkInfo {
+    pub link_speed_mbps: u64,
+    pub full_duplex: bool,
+    /// Physical media, e.g. "802.3" or "Native 802.11"
+    pub media_type: String,
+    pub mtu: u32,
+}
+
+/// Parse `Get-NetAdapter`'s LinkSpeed ("1 Gbps", "100 Mbps", "2.5 Gbps") into Mbps
+fn parse_link_speed(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let number: f64 = parts.next()?.parse().ok()?;
+    let mbps = match parts.next()?.to_ascii_lowercase().as_str() {
+        "gbps" => number * 1000.0,
+        "mbps" => number,
+        "kbps" => number / 1000.0,
+        "bps" => number / 1_000_000.0,
+        _ => return None,
+    };
+    Some(mbps.round() as u64)
+}
+
+/// Negotiated speed, duplex, media type and MTU of an adapter
+#[tauri::command]
+pub fn get_adapter_link_info(adapter_name: String) -> Result<LinkInfo, AppError> {
+    #[derive(Deserialize)]
+    struct RawLinkInfo {
+        status: String,
+        link_speed: String,
+        full_duplex: bool,
+        media_type: String,
+        mtu: u32,
+    }
+
+    let name = adapter_name.replace("'", "''");
+    let script = format!(
+        r#"
+        $adapter = Get-NetAdapter -Name '{name}' -ErrorAction SilentlyContinue
+        if (-not $adapter) {{ return }}
+        $mtu = [uint32]$adapter.MtuSize
+        if (-not $mtu) {{
+            $jumbo = Get-NetAdapterAdvancedProperty -Name '{name}' -RegistryKeyword '*JumboPacket' -ErrorAction SilentlyContinue
+            if ($jumbo) {{ $mtu = [uint32]$jumbo.RegistryValue[0] - 14 }}
+        }}
+        @{{ status = "$($adapter.Status)"; link_speed = "$($adapter.LinkSpeed)"; full_duplex = [bool]$adapter.FullDuplex; media_type = "$($adapter.PhysicalMediaType)"; mtu = $mtu }} | ConvertTo-Json -Compress
+        "#
+    );
+
+    let stdout = run_powershell(&script)?;
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "Adapter not found: {}",
+            adapter_name
+        )));
+    }
+
+    let raw: RawLinkInfo = serde_json::from_str(trimmed).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    if !raw.status.eq_ignore_ascii_case("Up") {
+        return Err(AppError::Other(format!(
+            "{} is {}; link speed is only defined while connected",
+            adapter_name, raw.status
+        )));
+    }
+
+    Ok(LinkInfo {
+        link_speed_mbps: parse_link_speed(&raw.link_speed)
+            .ok_or_else(|| format!("Unrecognized link speed: {}", raw.link_speed))?,
+        full_duplex: raw.full_duplex,
+        media_type: raw.media_type,
+        mtu: raw.mtu,
+    })
+}
+
+/// Smallest MTU IPv4 hosts must accept (RFC 791)
+const MIN_MTU: u32 = 576;
+
+/// Largest common jumbo frame MTU
+const MAX_MTU: u32 = 9000;
+
+fn validate_mtu(mtu: u32) -> Result<(), String> {
+    if (MIN_MTU..=MAX_MTU).contains(&mtu) {
+        Ok(())
+    } else {
+        Err(format!("MTU must be between {} and {} (got {})", MIN_MTU, MAX_MTU, mtu))
+    }
+}
+
+/// Current IPv4 MTU of an adapter
+#[tauri::command]
+pub fn get_mtu(adapter_name: String) -> Result<u32, AppError> {
+    let script = format!(
+        "(Get-NetIPInterface -InterfaceAlias '{}' -AddressFamily IPv4 -ErrorAction SilentlyContinue).NlMtu",
+        adapter_name.replace("'", "''")
+    );
+    let stdout = run_powershell(&script)?;
+    stdout
+        .trim()
+        .parse()
+        .map_err(|_| AppError::NotFound(format!("Adapter not found: {}", adapter_name)))
+}
+
+/// Set the IPv4 MTU of an adapter (576-9000, requires admin)
+#[tauri::command]
+pub fn set_mtu(adapter_name: String, mtu: u32) -> Result<String, AppError> {
+    validate_mtu(mtu)?;
+    let previous = get_mtu(adapter_name.clone())?;
+
+    let script = format!(
+        "Set-NetIPInterface -InterfaceAlias '{}' -AddressFamily IPv4 -NlMtuBytes {} -ErrorAction Stop",
+        adapter_name.replace("'", "''"),
+        mtu
+    );
+    run_privileged(&format!("change the MTU of {}", adapter_name), &script)?;
+
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&adapter_name);
+    Ok(format!("Changed MTU of {} from {} to {}", adapter_name, previous, mtu))
+}
+
+/// Outcome of changing or resetting an adapter's MAC address
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MacAddressChange {
+    /// MAC the adapter reports after restarting
+    pub mac_address: String,
+    /// False when the driver ignored the requested address
+    pub applied: bool,
+    pub note: Option<String>,
+}
+
+/// Normalize "AA-BB-CC-DD-EE-FF", "aa:bb:..." or "AABBCCDDEEFF" to dashed uppercase,
+/// rejecting multicast and all-zero addresses
+fn normalize_mac(mac: &str) -> Result<String, String> {
+    let hex: String = mac
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, '-' | ':' | '.'))
+        .collect::<String>()
+        .to_uppercase();
+    if hex.len() != 12 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid MAC address (expected 12 hex digits): {}", mac));
+    }
+
+    let first_octet = u8::from_str_radix(&hex[..2], 16).map_err(|e| e.to_string())?;
+    if first_octet & 0x01 != 0 {
+        return Err(format!(
+            "{} is a multicast address; the first octet must be even (e.g. 02)",
+            mac
+        ));
+    }
+    if hex.chars().all(|c| c == '0') {
+        return Err("MAC address cannot be all zeros".to_string());
+    }
+
+    let octets: Vec<&str> = (0..12).step_by(2).map(|i| &hex[i..i + 2]).collect();
+    Ok(octets.join("-"))
+}
+
+/// Locally administered addresses have the second-lowest bit of the first octet set
+/// (second hex digit 2, 6, A or E); many Wi-Fi drivers only accept these
+fn is_locally_administered(mac: &str) -> bool {
+    u8::from_str_radix(&mac[..2], 16).is_ok_and(|octet| octet & 0x02 != 0)
+}
+
+/// Write (or reset) the NetworkAddress property, restart the adapter and read back its MAC
+fn apply_network_address(adapter_name: &str, value: Option<&str>) -> Result<String, AppError> {
+    ensure_adapter_exists(adapter_name)?;
+    let alias = adapter_name.replace("'", "''");
+
+    let supported = run_powershell(&format!(
+        "if (Get-NetAdapterAdvancedProperty -Name '{}' -RegistryKeyword NetworkAddress -ErrorAction SilentlyContinue) {{ 'true' }} else {{ 'false' }}",
+        alias
+    ))?;
+    if supported.trim() != "true" {
+        return Err(format!(
+            "The driver of {} doesn't support changing the MAC address",
+            adapter_name
+        )
+        .into());
+    }
+
+    let change = match value {
+        Some(mac) => format!(
+            "Set-NetAdapterAdvancedProperty -Name '{}' -RegistryKeyword NetworkAddress -RegistryValue '{}' -ErrorAction Stop",
+            alias, mac
+        ),
+        None => format!(
+            "Reset-NetAdapterAdvancedProperty -Name '{}' -RegistryKeyword NetworkAddress -ErrorAction Stop",
+            alias
+        ),
+    };
+    let script = format!(
+        "{0}; Restart-NetAdapter -Name '{1}' -ErrorAction Stop; (Get-NetAdapter -Name '{1}' -ErrorAction Stop).MacAddress",
+        change, alias
+    );
+    let stdout = run_privileged(&format!("change the MAC address of {}", adapter_name), &script)?;
+
+    crate::cache::NETWORK_CACHE.invalidate_adapter(adapter_name);
+    Ok(stdout.trim().to_uppercase())
+}
+
+/// Override an adapter's MAC address and restart it to apply (requires admin)
+#[tauri::command]
+pub fn set_mac_address(adapter_name: String, mac: String) -> Result<MacAddressChange, AppError> {
+    let requested = normalize_mac(&mac)?;
+    let effective = apply_network_address(&adapter_name, Some(&requested.replace('-', "")))?;
+
+    let applied = effective == requested;
+    let note = if applied {
+        None
+    } else if !is_locally_administered(&requested) {
+        Some(format!(
+            "The driver kept {}; try a locally administered address (second hex digit 2, 6, A or E, e.g. 02-...)",
+            effective
+        ))
+    } else {
+        Some(format!("The driver kept {}; it may not support MAC overrides", effective))
+    };
+
+    Ok(MacAddressChange {
+        mac_address: effective,
+        applied,
+        note,
+    })
+}
+
+/// Remove the MAC override so the adapter uses its burned-in address (requires admin)
+#[tauri::command]
+pub fn reset_mac_address(adapter_name: String) -> Result<MacAddressChange, AppError> {
+    let effective = apply_network_address(&adapter_name, None)?;
+    Ok(MacAddressChange {
+        mac_address: effective,
+        applied: true,
+        note: None,
+    })
+}
+
+/// Map a `WakeOnMagicPacket` value to on/off; `None` for Unsupported/Inactive
+fn parse_wol_state(value: &str) -> Option<bool> {
+    match value.trim() {
+        v if v.eq_ignore_ascii_case("Enabled") => Some(true),
+        v if v.eq_ignore_ascii_case("Disabled") => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether the adapter wakes the machine on a magic packet
+#[tauri::command]
+pub fn get_wol_enabled(adapter_name: String) -> Result<bool, AppError> {
+    ensure_adapter_exists(&adapter_name)?;
+    let script = format!(
+        "\"$((Get-NetAdapterPowerManagement -Name '{}' -ErrorAction Stop).WakeOnMagicPacket)\"",
+        adapter_name.replace("'", "''")
+    );
+    let stdout = run_powershell(&script)?;
+    parse_wol_state(&stdout).ok_or_else(|| {
+        format!("{} doesn't support Wake-on-LAN ({})", adapter_name, stdout.trim()).into()
+    })
+}
+
+/// Turn "Wake on Magic Packet" on or off for an adapter (requires admin)
+#[tauri::command]
+pub fn set_wol_enabled(adapter_name: String, enabled: bool) -> Result<bool, AppError> {
+    get_wol_enabled(adapter_name.clone())?;
+
+    let script = format!(
+        "Set-NetAdapterPowerManagement -Name '{}' -WakeOnMagicPacket {} -ErrorAction Stop",
+        adapter_name.replace("'", "''"),
+        if enabled { "Enabled" } else { "Disabled" }
+    );
+    run_privileged(&format!("change Wake-on-LAN on {}", adapter_name), &script)?;
+
+    get_wol_enabled(adapter_name)
+}
+
+/// Cumulative traffic counters of an adapter
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct AdapterStats {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    pub errors_in: u64,
+    pub errors_out: u64,
+}
+
+/// Counters plus per-second rates since the previous call for the same adapter
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AdapterThroughput {
+    pub stats: AdapterStats,
+    /// `None` on the first call for an adapter
+    pub bytes_sent_per_sec: Option<f64>,
+    pub bytes_received_per_sec: Option<f64>,
+    pub interval_ms: Option<u64>,
+}
+
+lazy_static! {
+    /// Last counters read by `get_adapter_throughput`, keyed by adapter name
+    static ref THROUGHPUT_SAMPLES: Mutex<HashMap<String, (AdapterStats, Instant)>> = Mutex::new(HashMap::new());
+}
+
+/// Per-second send/receive rates between two samples
+/// Counters that went backwards (adapter reset) count as zero traffic
+fn throughput_rates(previous: &AdapterStats, current: &AdapterStats, elapsed: Duration) -> (f64, f64) {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return (0.0, 0.0);
+    }
+    (
+        current.bytes_sent.saturating_sub(previous.bytes_sent) as f64 / secs,
+        current.bytes_received.saturating_sub(previous.bytes_received) as f64 / secs,
+    )
+}
+
+/// Cumulative bytes, packets and errors from `Get-NetAdapterStatistics`
+#[tauri::command]
+pub fn get_adapter_statistics(adapter_name: String) -> Result<AdapterStats, AppError> {
+    let script = format!(
+        r#"
+        $s = Get-NetAdapterStatistics -Name '{}' -ErrorAction SilentlyContinue
+        if (-not $s) {{ return }}
+        @{{
+            bytes_sent = [uint64]$s.SentBytes
+            bytes_received = [uint64]$s.ReceivedBytes
+            packets_sent = [uint64]($s.SentUnicastPackets + $s.SentMulticastPackets + $s.SentBroadcastPackets)
+            packets_received = [uint64]($s.ReceivedUnicastPackets + $s.ReceivedMulticastPackets + $s.ReceivedBroadcastPackets)
+            errors_in = [uint64]$s.ReceivedPacketErrors
+            errors_out = [uint64]$s.OutboundPacketErrors
+        }} | ConvertTo-Json -Compress
+        "#,
+        adapter_name.replace("'", "''")
+    );
+
+    let stdout = run_powershell(&script)?;
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "Adapter not found: {}",
+            adapter_name
+        )));
+    }
+    Ok(serde_json::from_str(trimmed)?)
+}
+
+/// Read the adapter's counters and compute rates against the previous call
+#[tauri::command]
+pub fn get_adapter_throughput(adapter_name: String) -> Result<AdapterThroughput, AppError> {
+    let stats = get_adapter_statistics(adapter_name.clone())?;
+    let now = Instant::now();
+
+    let previous = THROUGHPUT_SAMPLES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(adapter_name, (stats, now));
+
+    Ok(match previous {
+        Some((previous, at)) => {
+            let elapsed = now.duration_since(at);
+            let (sent, received) = throughput_rates(&previous, &stats, elapsed);
+            AdapterThroughput {
+                stats,
+                bytes_sent_per_sec: Some(sent),
+                bytes_received_per_sec: Some(received),
+                interval_ms: Some(elapsed.as_millis() as u64),
+            }
+        }
+        None => AdapterThroughput {
+            stats,
+            bytes_sent_per_sec: None,
+            bytes_received_per_sec: None,
+            interval_ms: None,
+        },
+    })
+}
+
+/// File in the app data dir holding the latest backup per adapter
+const ADAPTER_BACKUPS_FILE: &str = "adapter_backups.json";
+
+/// Saved adapter configuration that can be re-applied later
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdapterConfigBackup {
+    pub adapter_name: String,
+    /// Unix timestamp (seconds)
+    pub captured_at: u64,
+    pub config: IPConfiguration,
+}
+
+fn adapter_backups_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to locate app data dir: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    Ok(dir.join(ADAPTER_BACKUPS_FILE))
+}
+
+fn read_adapter_backups(app: &AppHandle) -> Result<BTreeMap<String, AdapterConfigBackup>, String> {
+    let path = adapter_backups_path(app)?;
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", ADAPTER_BACKUPS_FILE, e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", ADAPTER_BACKUPS_FILE, e)),
+    }
+}
+
+/// Capture an adapter's current configuration
+/// With `persist`, the backup is also saved to disk (replacing that adapter's previous one)
+#[tauri::command]
+pub fn backup_adapter_config(
+    app: AppHandle,
+    adapter_name: String,
+    persist: Option<bool>,
+) -> Result<AdapterConfigBackup, AppError> {
+    ensure_adapter_exists(&adapter_name)?;
+
+    let backup = AdapterConfigBackup {
+        config: get_ip_configuration(adapter_name.clone())?,
+        adapter_name,
+        captured_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+
+    if persist.unwrap_or(false) {
+        let mut backups = read_adapter_backups(&app)?;
+        backups.insert(backup.adapter_name.clone(), backup.clone());
+        let json = serde_json::to_string_pretty(&backups)
+            .map_err(|e| format!("Failed to serialize backups: {}", e))?;
+        std::fs::write(adapter_backups_path(&app)?, json)
+            .map_err(|e| format!("Failed to write {}: {}", ADAPTER_BACKUPS_FILE, e))?;
+    }
+
+    Ok(backup)
+}
+
+/// List backups saved to disk
+#[tauri::command]
+pub fn list_adapter_backups(app: AppHandle) -> Result<Vec<AdapterConfigBackup>, AppError> {
+    Ok(read_adapter_backups(&app)?.into_values().collect())
+}
+
+/// Re-apply a backup through the regular DHCP / static IP paths
+#[tauri::command]
+pub fn restore_adapter_config(backup: AdapterConfigBackup) -> Result<String, AppError> {
+    ensure_adapter_exists(&backup.adapter_name).map_err(|_| {
+        AppError::NotFound(format!(
+            "Cannot restore backup: adapter '{}' no longer exists",
+            backup.adapter_name
+        ))
+    })?;
+
+    let config = backup.config;
+    let result = if config.dhcp_enabled {
+        apply_dhcp(backup.adapter_name.clone())
+    } else {
+        apply_static_ip(
+            backup.adapter_name.clone(),
+            config.ip_address,
+            config.subnet_mask,
+            config.gateway,
+            config.primary_dns,
+            config.secondary_dns,
+            false,
+        )
+    };
+
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&backup.adapter_name);
+    result
+}
+
+/// Run ipconfig command
+#[tauri::command]
+pub fn run_ipconfig(all: bool) -> Result<String, AppError> {
+    let args: &[&str] = if all { &["/all"] } else { &[] };
+    Ok(run_cmd("ipconfig", args)?.stdout)
+}
+
+/// One adapter section of `ipconfig /all`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct AdapterIpInfo {
+    /// Section heading, e.g. "Ethernet adapter Ethernet" gives "Ethernet"
+    pub name: String,
+    pub description: String,
+    pub physical_address: Option<String>,
+    pub dhcp_enabled: bool,
+    pub ipv4_addresses: Vec<String>,
+    pub subnet_masks: Vec<String>,
+    pub default_gateways: Vec<String>,
+    pub dns_servers: Vec<String>,
+    pub dhcp_server: Option<String>,
+    /// Lease times as printed by ipconfig (localized)
+    pub lease_obtained: Option<String>,
+    pub lease_expires: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IpconfigField {
+    Description,
+    PhysicalAddress,
+    DhcpEnabled,
+    Ipv4Address,
+    SubnetMask,
+    DefaultGateway,
+    DnsServers,
+    DhcpServer,
+    LeaseObtained,
+    LeaseExpires,
+}
+
+/// ipconfig labels in English, German, French and Spanish
+const IPCONFIG_LABELS: &[(IpconfigField, &[&str])] = &[
+    (IpconfigField::Description, &["Description", "Beschreibung", "Descripción"]),
+    (
+        IpconfigField::PhysicalAddress,
+        &["Physical Address", "Physische Adresse", "Adresse physique", "Dirección física"],
+    ),
+    (
+        IpconfigField::DhcpEnabled,
+        &["DHCP Enabled", "DHCP aktiviert", "DHCP activé", "DHCP habilitado"],
+    ),
+    (
+        IpconfigField::Ipv4Address,
+        &["IPv4 Address", "Autoconfiguration IPv4 Address", "IPv4-Adresse", "Adresse IPv4", "Dirección IPv4"],
+    ),
+    (
+        IpconfigField::SubnetMask,
+        &["Subnet Mask", "Subnetzmaske", "Masque de sous-réseau", "Máscara de subred"],
+    ),
+    (
+        IpconfigField::DefaultGateway,
+        &["Default Gateway", "Standardgateway", "Passerelle par défaut", "Puerta de enlace predeterminada"],
+    ),
+    (
+        IpconfigField::DnsServers,
+        &["DNS Servers", "DNS-Server", "Serveurs DNS", "Servidores DNS"],
+    ),
+    (
+        IpconfigField::DhcpServer,
+        &["DHCP Server", "DHCP-Server", "Serveur DHCP", "Servidor DHCP"],
+    ),
+    (
+        IpconfigField::LeaseObtained,
+        &["Lease Obtained", "Lease erhalten", "Bail obtenu", "Concesión obtenida"],
+    ),
+    (
+        IpconfigField::LeaseExpires,
+        &["Lease Expires", "Lease läuft ab", "Bail expirant", "La concesión expira"],
+    ),
+];
+
+/// Lowercase ASCII letters and digits only, so labels still match when accented
+/// characters were mangled by the console code page
+fn normalize_ipconfig_label(label: &str) -> String {
+    label
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn ipconfig_field(label: &str) -> Option<IpconfigField> {
+    let label = normalize_ipconfig_label(label.trim_end_matches(['.', ' ']));
+    IPCONFIG_LABELS
+        .iter()
+        .find(|(_, names)| names.iter().any(|name| normalize_ipconfig_label(name) == label))
+        .map(|(field, _)| *field)
+}
+
+fn set_ipconfig_field(info: &mut AdapterIpInfo, field: IpconfigField, value: &str) {
+    // "192.168.1.10(Preferred)" -> "192.168.1.10"
+    let address = || value.split('(').next().unwrap_or(value).trim().to_string();
+    match field {
+        IpconfigField::Description => info.description = value.to_string(),
+        IpconfigField::PhysicalAddress => info.physical_address = Some(value.to_string()),
+        IpconfigField::DhcpEnabled => {
+            info.dhcp_enabled = matches!(
+                normalize_ipconfig_label(value).as_str(),
+                "yes" | "ja" | "oui" | "si" | "s"
+            )
+        }
+        IpconfigField::Ipv4Address => info.ipv4_addresses.push(address()),
+        IpconfigField::SubnetMask => info.subnet_masks.push(address()),
+        IpconfigField::DefaultGateway => info.default_gateways.push(address()),
+        IpconfigField::DnsServers => info.dns_servers.push(address()),
+        IpconfigField::DhcpServer => info.dhcp_server = Some(address()),
+        IpconfigField::LeaseObtained => info.lease_obtained = Some(value.to_string()),
+        IpconfigField::LeaseExpires => info.lease_expires = Some(value.to_string()),
+    }
+}
+
+/// Parse `ipconfig /all` output; the global "Windows IP Configuration" block is skipped
+fn parse_ipconfig_all(output: &str) -> Vec<AdapterIpInfo> {
+    let mut adapters: Vec<AdapterIpInfo> = Vec::new();
+    let mut field = None;
+
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if !line.starts_with(' ') {
+            // Adapter headings end with ':' ("Carte Ethernet Ethernet :" in French)
+            field = None;
+            if let Some(heading) = line.trim_end().strip_suffix(':') {
+                let heading = heading.trim();
+                let name = heading
+                    .to_ascii_lowercase()
+                    .find("adapter ")
+                    .map_or(heading, |i| &heading[i + "adapter ".len()..]);
+                adapters.push(AdapterIpInfo {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+            }
+            continue;
+        }
+
+        let Some(info) = adapters.last_mut() else {
+            continue;
+        };
+        match line.find(" :") {
+            Some(separator) => {
+                field = ipconfig_field(line[..separator].trim());
+                let value = line[separator + 2..].trim();
+                if let Some(f) = field.filter(|_| !value.is_empty()) {
+                    set_ipconfig_field(info, f, value);
+                }
+            }
+            // Extra values of multi-value fields (gateways, DNS servers) sit on their own lines
+            None => {
+                if let Some(f) = field {
+                    set_ipconfig_field(info, f, line.trim());
+                }
+            }
+        }
+    }
+
+    adapters
+}
+
+/// `ipconfig /all` parsed per adapter
+#[tauri::command]
+pub fn get_ipconfig_structured() -> Result<Vec<AdapterIpInfo>, AppError> {
+    let output = run_cmd("ipconfig", &["/all"])?.into_result()?;
+    Ok(parse_ipconfig_all(&output))
+}
+
+/// Release IP address
+#[tauri::command]
+pub fn release_ip() -> Result<String, AppError> {
+    Ok(run_cmd("ipconfig", &["/release"])?.stdout)
+}
+
+/// Renew IP address
+#[tauri::command]
+pub fn renew_ip() -> Result<String, AppError> {
+    Ok(run_cmd("ipconfig", &["/renew"])?.stdout)
+}
+
+/// Run `ipconfig /release` or `/renew` for a single adapter
+fn run_ipconfig_for_adapter(action: &str, adapter_name: &str) -> Result<String, AppError> {
+    // ipconfig treats * and ? as wildcards, which could hit other adapters
+    if adapter_name.contains(['*', '?']) {
+        return Err(format!("Invalid adapter name: {}", adapter_name).into());
+    }
+    ensure_adapter_exists(adapter_name)?;
+
+    let operation = format!("{} the DHCP lease of {}", action, adapter_name);
+    let flag = format!("/{}", action);
+    let result = run_cmd_privileged(&operation, "ipconfig", &[&flag, adapter_name]);
+    crate::cache::NETWORK_CACHE.invalidate_adapter(adapter_name);
+    result
+}
+
+/// Release the DHCP lease of one adapter, leaving the others connected
+#[tauri::command]
+pub fn release_ip_adapter(adapter_name: String) -> Result<String, AppError> {
+    run_ipconfig_for_adapter("release", &adapter_name)
+}
+
+/// Renew the DHCP lease of one adapter
+#[tauri::command]
+pub fn renew_ip_adapter(adapter_name: String) -> Result<String, AppError> {
+    run_ipconfig_for_adapter("renew", &adapter_name)
+}
+
+/// Flush DNS cache
+#[tauri::command]
+pub fn flush_dns() -> Result<String, AppError> {
+    Ok(run_cmd("ipconfig", &["/flushdns"])?.stdout)
+}
+
+/// Result of a single step in `flush_all_caches`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FlushStepResult {
+    pub step: String,
+    pub success: bool,
+    pub detail: String,
+    pub requires_admin: bool,
+}
+
+fn run_flush_step(step: &str, requires_admin: bool, program: &str, args: &[&str]) -> FlushStepResult {
+    match run_cmd(program, args) {
+        Ok(output) => {
+            let detail = if output.stderr.trim().is_empty() {
+                output.stdout.trim().to_string()
+            } else {
+                output.stderr.trim().to_string()
+            };
+            FlushStepResult {
+                step: step.to_string(),
+                success: output.success,
+                detail,
+                requires_admin,
+            }
+        }
+        Err(e) => FlushStepResult {
+            step: step.to_string(),
+            success: false,
+            detail: e,
+            requires_admin,
+        },
+    }
+}
+
+/// Flush DNS, ARP, NetBIOS, destination (route) caches and the app's own
+/// network cache in one go. Every step runs even if an earlier one fails.
+#[tauri::command]
+pub fn flush_all_caches() -> Vec<FlushStepResult> {
+    let mut results = vec![
+        run_flush_step("DNS cache", false, "ipconfig", &["/flushdns"]),
+        run_flush_step(
+            "ARP cache",
+            true,
+            "netsh",
+            &["interface", "ip", "delete", "arpcache"],
+        ),
+        run_flush_step("NetBIOS name cache", true, "nbtstat", &["-R"]),
+        run_flush_step(
+            "Route destination cache",
+            true,
+            "netsh",
+            &["interface", "ip", "delete", "destinationcache"],
+        ),
+    ];
+
+    crate::cache::NETWORK_CACHE.invalidate_all();
+    results.push(FlushStepResult {
+        step: "App network cache".to_string(),
+        success: true,
+        detail: "All cached adapter configurations cleared".to_string(),
+        requires_admin: false,
+    });
+
+    results
+}
+
+/// Flush the DNS cache, then re-register this machine's DNS names (requires admin
+/// for the second step). Returns both steps' output.
+#[tauri::command]
+pub fn reset_dns_registration() -> Result<String, AppError> {
+    let flush = run_flush_step("Flush DNS", false, "ipconfig", &["/flushdns"]);
+    if !flush.success {
+        return Err(format!("Failed to flush DNS: {}", flush.detail).into());
+    }
+
+    let register = run_flush_step("Register DNS", true, "ipconfig", &["/registerdns"]);
+    if !register.success {
+        if !crate::admin::is_admin() {
+            return Err(AppError::NotElevated(
+                "DNS cache was flushed, but re-registering DNS requires administrator privileges".to_string(),
+            ));
+        }
+        return Err(format!(
+            "DNS cache was flushed, but re-registering DNS failed: {}",
+            register.detail
+        )
+        .into());
+    }
+
+    Ok(format!("{}\n\n{}", flush.detail, register.detail))
+}
+
+/// Display DNS cache
+#[tauri::command]
+pub fn display_dns() -> Result<String, AppError> {
+    Ok(run_cmd("ipconfig", &["/displaydns"])?.stdout)
+}
+
+/// One mapping line of the hosts file
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HostsEntry {
+    pub ip: String,
+    pub hostnames: Vec<String>,
+    /// False when the line is commented out
+    pub enabled: bool,
+    pub comment: Option<String>,
+}
+
+/// `%SystemRoot%\System32\drivers\etc\hosts`
+fn hosts_file_path() -> std::path::PathBuf {
+    let root = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string());
+    std::path::Path::new(&root).join("System32\\drivers\\etc\\hosts")
+}
+
+/// Parse one hosts line; plain comments and blank lines give `None`
+fn parse_hosts_line(line: &str) -> Option<HostsEntry> {
+    let line = line.trim_start_matches('\u{feff}').trim();
+    let (enabled, line) = match line.strip_prefix('#') {
+        Some(rest) => (false, rest.trim_start()),
+        None => (true, line),
+    };
+    let (mapping, comment) = match line.split_once('#') {
+        Some((mapping, comment)) => (mapping, Some(comment.trim().to_string()).filter(|c| !c.is_empty())),
+        None => (line, None),
+    };
+
+    let mut fields = mapping.split_whitespace();
+    let ip = fields.next()?;
+    ip.parse::<IpAddr>().ok()?;
+    let hostnames: Vec<String> = fields.map(str::to_string).collect();
+    if hostnames.is_empty() {
+        return None;
+    }
+
+    Some(HostsEntry {
+        ip: ip.to_string(),
+        hostnames,
+        enabled,
+        comment,
+    })
+}
+
+/// Every mapping in the hosts file, active or commented out, in file order
+fn parse_hosts(contents: &str) -> Vec<HostsEntry> {
+    contents.lines().filter_map(parse_hosts_line).collect()
+}
+
+/// Accept a single hostname made of letters, digits, `-`, `.` and `_`
+fn validate_hosts_name(hostname: &str) -> Result<(), String> {
+    let valid = !hostname.is_empty()
+        && hostname.len() <= 253
+        && hostname
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid hostname: {}", hostname))
+    }
+}
+
+/// Split raw hosts bytes into lines without their `\n` / `\r\n` endings
+fn hosts_lines(contents: &[u8]) -> Vec<&[u8]> {
+    let mut lines: Vec<&[u8]> = contents
+        .split(|&b| b == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .collect();
+    if contents.ends_with(b"\n") {
+        lines.pop();
+    }
+    lines
+}
+
+fn hosts_newline(contents: &[u8]) -> &'static [u8] {
+    if contents.windows(2).any(|w| w == b"\r\n") {
+        b"\r\n"
+    } else {
+        b"\n"
+    }
+}
+
+/// Drop `hostname` from every active line; lines left without a name are removed,
+/// everything else is kept byte for byte (the file may be ANSI rather than UTF-8).
+/// Returns `None` if no line mapped it.
+fn remove_hosts_name(contents: &[u8], hostname: &str) -> Option<Vec<u8>> {
+    let newline = hosts_newline(contents);
+    let mut found = false;
+    let mut lines: Vec<Vec<u8>> = Vec::new();
+
+    for line in hosts_lines(contents) {
+        let entry = parse_hosts_line(&String::from_utf8_lossy(line)).filter(|entry| entry.enabled);
+        let Some(entry) = entry.filter(|e| e.hostnames.iter().any(|h| h.eq_ignore_ascii_case(hostname))) else {
+            lines.push(line.to_vec());
+            continue;
+        };
+        found = true;
+
+        let remaining: Vec<&str> = entry
+            .hostnames
+            .iter()
+            .map(String::as_str)
+            .filter(|h| !h.eq_ignore_ascii_case(hostname))
+            .collect();
+        if remaining.is_empty() {
+            continue;
+        }
+        let mut rewritten = format!("{}\t{}", entry.ip, remaining.join(" "));
+        if let Some(comment) = &entry.comment {
+            rewritten.push_str(&format!("\t# {}", comment));
+        }
+        lines.push(rewritten.into_bytes());
+    }
+
+    found.then(|| {
+        let mut updated = lines.join(newline);
+        updated.extend_from_slice(newline);
+        updated
+    })
+}
+
+/// Point `hostname` at `ip`, replacing any other active mapping of it
+fn add_hosts_mapping(contents: &[u8], ip: &str, hostname: &str) -> Vec<u8> {
+    let newline = hosts_newline(contents);
+    let mut updated = remove_hosts_name(contents, hostname).unwrap_or_else(|| contents.to_vec());
+    if !updated.is_empty() && !updated.ends_with(b"\n") {
+        updated.extend_from_slice(newline);
+    }
+    updated.extend_from_slice(format!("{}\t{}", ip, hostname).as_bytes());
+    updated.extend_from_slice(newline);
+    updated
+}
+
+/// Copy the hosts file to `hosts.bak`, then replace it through a temp file in `etc`
+/// so a failed write never leaves a truncated hosts file behind
+fn write_hosts_file(contents: &[u8]) -> Result<(), AppError> {
+    let path = hosts_file_path();
+    let map_error = |e: std::io::Error| {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            AppError::not_elevated("edit the hosts file")
+        } else {
+            AppError::Other(format!("Failed to update the hosts file: {}", e))
+        }
+    };
+
+    std::fs::copy(&path, path.with_extension("bak")).map_err(map_error)?;
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    let temp = path.with_file_name(format!("hosts.{}-{}.tmp", std::process::id(), nanos));
+    let result = std::fs::write(&temp, contents).and_then(|_| std::fs::rename(&temp, &path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    result.map_err(map_error)
+}
+
+/// Raw hosts bytes plus a lossy UTF-8 view for parsing
+fn read_hosts_bytes() -> Result<(Vec<u8>, String), AppError> {
+    let bytes = std::fs::read(hosts_file_path())?;
+    let text = String::from_utf8_lossy(&bytes).into_owned();
+    Ok((bytes, text))
+}
+
+/// Parsed entries of the system hosts file, including commented-out mappings
+#[tauri::command]
+pub fn read_hosts_file() -> Result<Vec<HostsEntry>, AppError> {
+    let (_, text) = read_hosts_bytes()?;
+    Ok(parse_hosts(&text))
+}
+
+/// Map `hostname` to `ip` in the hosts file (requires admin)
+#[tauri::command]
+pub fn write_hosts_entry(ip: String, hostname: String) -> Result<Vec<HostsEntry>, AppError> {
+    let ip: IpAddr = ip
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid IP address: {}", ip))?;
+    let hostname = hostname.trim();
+    validate_hosts_name(hostname)?;
+
+    let (contents, text) = read_hosts_bytes()?;
+    let already_mapped = parse_hosts(&text).iter().any(|entry| {
+        entry.enabled
+            && entry.ip.parse::<IpAddr>().ok() == Some(ip)
+            && entry.hostnames.iter().any(|h| h.eq_ignore_ascii_case(hostname))
+    });
+    if !already_mapped {
+        write_hosts_file(&add_hosts_mapping(&contents, &ip.to_string(), hostname))?;
+    }
+
+    read_hosts_file()
+}
+
+/// Remove every active mapping of `hostname` from the hosts file (requires admin)
+#[tauri::command]
+pub fn remove_hosts_entry(hostname: String) -> Result<Vec<HostsEntry>, AppError> {
+    let hostname = hostname.trim();
+    let (contents, _) = read_hosts_bytes()?;
+    let updated = remove_hosts_name(&contents, hostname)
+        .ok_or_else(|| AppError::NotFound(format!("No hosts entry for {}", hostname)))?;
+    write_hosts_file(&updated)?;
+
+    read_hosts_file()
+}
+
+/// Open network connections
+#[tauri::command]
+pub fn open_network_connections() -> Result<String, AppError> {
+    spawn_cmd("cmd", &["/c", "ncpa.cpl"]).map_err(|e| format!("Failed to open: {}", e))?;
+    Ok("Opened Network Connections".to_string())
+}
+
+/// Open network settings
+#[tauri::command]
+pub fn open_network_settings() -> Result<String, AppError> {
+    spawn_cmd("cmd", &["/c", "start", "ms-settings:network"])
+        .map_err(|e| format!("Failed to open: {}", e))?;
+    Ok("Opened Network Settings".to_string())
+}
+
+/// Proxy auto-config (PAC/WPAD) detection result
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WpadStatus {
+    pub pac_url: Option<String>,
+    pub auto_detect_enabled: bool,
+    /// "registry" when the PAC URL is configured, "dns" when found by the WPAD probe
+    pub discovered_via: Option<String>,
+}
+
+const INTERNET_SETTINGS_KEY: &str =
+    "Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings";
+
+/// Flag in DefaultConnectionSettings byte 8 for "Automatically detect settings"
+const PROXY_TYPE_AUTO_DETECT: u8 = 0x08;
+
+/// Upper bound for each stage of the WPAD DNS probe
+const WPAD_PROBE_TIMEOUT_MS: u64 = 2000;
+
+/// Resolve `wpad` and fetch `/wpad.dat`, returning the PAC URL if served
+fn probe_wpad_dns() -> Option<String> {
+    let timeout = Duration::from_millis(WPAD_PROBE_TIMEOUT_MS);
+
+    // Name resolution has no timeout of its own, so bound it with a channel
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let addr = ("wpad", 80u16)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next());
+        let _ = tx.send(addr);
+    });
+    let addr = rx.recv_timeout(timeout).ok().flatten()?;
+
+    let mut stream = TcpStream::connect_timeout(&addr, timeout).ok()?;
+    stream.set_read_timeout(Some(timeout)).ok()?;
+    stream
+        .write_all(b"GET /wpad.dat HTTP/1.0\r\nHost: wpad\r\nConnection: close\r\n\r\n")
+        .ok()?;
+
+    let mut buf = [0u8; 64];
+    let n = stream.read(&mut buf).ok()?;
+    let status_line = String::from_utf8_lossy(&buf[..n]);
+    let status_ok = status_line
+        .split_whitespace()
+        .nth(1)
+        .map(|code| code == "200")
+        .unwrap_or(false);
+
+    if status_ok {
+        Some("http://wpad/wpad.dat".to_string())
+    } else {
+        None
+    }
+}
+
+/// Detect proxy auto-config in use
+///
+/// Reads the configured AutoConfigURL and the "Automatically detect settings"
+/// flag from the WinINET registry. When `probe` is true and no PAC URL is
+/// configured, also tries WPAD discovery over DNS (time-bounded).
+#[tauri::command]
+pub fn detect_wpad(probe: Option<bool>) -> Result<WpadStatus, AppError> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let settings = hkcu
+        .open_subkey(INTERNET_SETTINGS_KEY)
+        .map_err(|e| format!("Failed to open Internet Settings: {}", e))?;
+
+    let configured_url: Option<String> = settings
+        .get_value::<String, _>("AutoConfigURL")
+        .ok()
+        .filter(|url| !url.trim().is_empty());
+
+    let auto_detect_enabled = settings
+        .open_subkey("Connections")
+        .and_then(|connections| connections.get_raw_value("DefaultConnectionSettings"))
+        .map(|value| {
+            value
+                .bytes
+                .get(8)
+                .map(|flags| flags & PROXY_TYPE_AUTO_DETECT != 0)
+                .unwrap_or(false)
+        })
+        .unwrap_or(false);
+
+    if let Some(url) = configured_url {
+        return Ok(WpadStatus {
+            pac_url: Some(url),
+            auto_detect_enabled,
+            discovered_via: Some("registry".to_string()),
+        });
+    }
+
+    let discovered = if probe.unwrap_or(false) {
+        probe_wpad_dns()
+    } else {
+        None
+    };
+
+    Ok(WpadStatus {
+        discovered_via: discovered.as_ref().map(|_| "dns".to_string()),
+        pac_url: discovered,
+        auto_detect_enabled,
+    })
+}
+
+/// Manual WinINET proxy settings of the current user
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProxyConfig {
+    pub enabled: bool,
+    pub server: Option<String>,
+    pub port: Option<u16>,
+    /// Hosts that bypass the proxy; "<local>" means all intranet names
+    pub bypass_list: Vec<String>,
+}
+
+/// Split ProxyServer into host and port
+/// Handles "host:port" and per-protocol "http=host:port;https=..." (http preferred)
+/// Address of an `http=` entry in a per-protocol ProxyServer value (scheme matched case-insensitively)
+fn http_proxy_entry(entry: &str) -> Option<&str> {
+    entry
+        .split_once('=')
+        .filter(|(scheme, _)| scheme.trim().eq_ignore_ascii_case("http"))
+        .map(|(_, address)| address.trim())
+}
+
+fn parse_proxy_server(value: &str) -> (Option<String>, Option<u16>) {
+    let entries: Vec<&str> = value.split(';').map(str::trim).filter(|e| !e.is_empty()).collect();
+    let entry = entries
+        .iter()
+        .find_map(|e| http_proxy_entry(e))
+        .or_else(|| entries.first().map(|e| e.split_once('=').map_or(*e, |(_, v)| v)));
+    let Some(entry) = entry else {
+        return (None, None);
+    };
+
+    match entry.rsplit_once(':') {
+        Some((host, port)) if !host.ends_with(':') => match port.parse() {
+            Ok(port) => (Some(host.to_string()), Some(port)),
+            Err(_) => (Some(entry.to_string()), None),
+        },
+        _ => (Some(entry.to_string()), None),
+    }
+}
+
+/// New ProxyServer value for `server:port`; a per-protocol value keeps its other
+/// entries and only gets its `http=` entry replaced (or added)
+fn merge_proxy_server(current: &str, server: &str, port: u16) -> String {
+    let address = format!("{}:{}", server, port);
+    if !current.contains('=') {
+        return address;
+    }
+
+    let mut replaced = false;
+    let mut entries: Vec<String> = current
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            if http_proxy_entry(entry).is_some() {
+                replaced = true;
+                format!("http={}", address)
+            } else {
+                entry.to_string()
+            }
+        })
+        .collect();
+    if !replaced {
+        entries.insert(0, format!("http={}", address));
+    }
+    entries.join(";")
+}
+
+/// Read the manual proxy settings from the WinINET registry
+#[tauri::command]
+pub fn get_proxy_settings() -> Result<ProxyConfig, AppError> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let settings = hkcu
+        .open_subkey(INTERNET_SETTINGS_KEY)
+        .map_err(|e| format!("Failed to open Internet Settings: {}", e))?;
+
+    let enabled = settings.get_value::<u32, _>("ProxyEnable").unwrap_or(0) != 0;
+    let server_value: String = settings.get_value("ProxyServer").unwrap_or_default();
+    let (server, port) = parse_proxy_server(&server_value);
+    let bypass: String = settings.get_value("ProxyOverride").unwrap_or_default();
+
+    Ok(ProxyConfig {
+        enabled,
+        server,
+        port,
+        bypass_list: bypass
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_string)
+            .collect(),
+    })
+}
+
+/// Tell WinINET clients (browsers, most apps) to reload proxy settings
+fn notify_proxy_changed() {
+    use windows::Win32::Networking::WinInet::{
+        InternetSetOptionW, INTERNET_OPTION_REFRESH, INTERNET_OPTION_SETTINGS_CHANGED,
+    };
+
+    // SAFETY: both options take no buffer and apply to all WinINET sessions
+    unsafe {
+        if let Err(e) = InternetSetOptionW(None, INTERNET_OPTION_SETTINGS_CHANGED, None, 0) {
+            warn!("Failed to broadcast proxy settings change: {}", e);
+        }
+        let _ = InternetSetOptionW(None, INTERNET_OPTION_REFRESH, None, 0);
+    }
+}
+
+/// Write the manual proxy settings for the current user and notify running apps
+/// The server is kept when disabling so it can be switched back on later; a
+/// per-protocol ProxyServer only has its `http=` entry changed
+#[tauri::command]
+pub fn set_proxy_settings(
+    enabled: bool,
+    server: String,
+    port: u16,
+    bypass_list: Vec<String>,
+) -> Result<ProxyConfig, AppError> {
+    let server = server.trim();
+    if server.contains(|c: char| c.is_whitespace() || c == ';' || c == '=') {
+        return Err(format!("Invalid proxy server: {}", server).into());
+    }
+    if enabled && (server.is_empty() || port == 0) {
+        return Err("A proxy server and port are required to enable the proxy".into());
+    }
+
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let settings = hkcu
+        .open_subkey_with_flags(INTERNET_SETTINGS_KEY, KEY_SET_VALUE | KEY_QUERY_VALUE)
+        .map_err(|e| format!("Failed to open Internet Settings: {}", e))?;
+    let write_error = |e: std::io::Error| format!("Failed to write proxy settings: {}", e);
+
+    settings
+        .set_value("ProxyEnable", &u32::from(enabled))
+        .map_err(write_error)?;
+    if !server.is_empty() && port != 0 {
+        let current: String = settings.get_value("ProxyServer").unwrap_or_default();
+        settings
+            .set_value("ProxyServer", &merge_proxy_server(&current, server, port))
+            .map_err(write_error)?;
+    }
+    let bypass: Vec<&str> = bypass_list.iter().map(|e| e.trim()).filter(|e| !e.is_empty()).collect();
+    settings
+        .set_value("ProxyOverride", &bypass.join(";"))
+        .map_err(write_error)?;
+
+    notify_proxy_changed();
+    get_proxy_settings()
+}
+
+/// State of one IPv6 transition technology
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransitionTechState {
+    /// As reported by Windows (e.g. "Default", "Disabled", "qualified"),
+    /// or "NotAvailable" when this Windows build no longer ships it
+    pub state: String,
+    /// Teredo server / 6to4 relay / ISATAP router, when configured
+    pub server: Option<String>,
+}
+
+/// IPv6 transition technologies (Teredo, 6to4, ISATAP)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Ipv6TransitionStatus {
+    pub teredo: TransitionTechState,
+    pub sixtofour: TransitionTechState,
+    pub isatap: TransitionTechState,
+}
+
+/// IPv6 transition technology selector
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Ipv6Transition {
+    Teredo,
+    Sixtofour,
+    Isatap,
+}
+
+/// Get the state of Teredo, 6to4 and ISATAP
+#[tauri::command]
+pub fn get_ipv6_transition_status() -> Result<Ipv6TransitionStatus, AppError> {
+    let stdout = run_powershell(
+        r#"
+        function State($value) { if ($null -eq $value -or "$value" -eq '') { 'NotAvailable' } else { "$value" } }
+        function Server($value) { if ("$value" -eq '') { $null } else { "$value" } }
+
+        $teredo = Get-NetTeredoConfiguration -ErrorAction SilentlyContinue
+        $teredoState = Get-NetTeredoState -ErrorAction SilentlyContinue
+        $sixtofour = Get-Net6to4Configuration -ErrorAction SilentlyContinue
+        $isatap = Get-NetIsatapConfiguration -ErrorAction SilentlyContinue
+
+        # Teredo reports its configured type; show the live state once it has left "Disabled"
+        $teredoValue = if ($teredo -and $teredo.Type -ne 'Disabled' -and $teredoState) { $teredoState.State } else { $teredo.Type }
+
+        @{
+            teredo = @{ state = (State $teredoValue); server = (Server $teredo.ServerName) }
+            sixtofour = @{ state = (State $sixtofour.State); server = (Server $sixtofour.RelayName) }
+            isatap = @{ state = (State $isatap.State); server = (Server $isatap.Router) }
+        } | ConvertTo-Json -Compress
+        "#,
+    )?;
+
+    Ok(serde_json::from_str(stdout.trim())?)
+}
+
+/// Enable (restore the Windows default) or disable an IPv6 transition technology
+/// Requires administrator privileges
+#[tauri::command]
+pub fn set_ipv6_transition_enabled(
+    technology: Ipv6Transition,
+    enabled: bool,
+) -> Result<String, AppError> {
+    if !crate::admin::is_admin() {
+        return Err(AppError::not_elevated("change IPv6 transition settings"));
+    }
+
+    let script = match (technology, enabled) {
+        (Ipv6Transition::Teredo, true) => "Set-NetTeredoConfiguration -Type Default",
+        (Ipv6Transition::Teredo, false) => "Set-NetTeredoConfiguration -Type Disabled",
+        (Ipv6Transition::Sixtofour, true) => "Set-Net6to4Configuration -State Default",
+        (Ipv6Transition::Sixtofour, false) => "Set-Net6to4Configuration -State Disabled",
+        (Ipv6Transition::Isatap, true) => "Set-NetIsatapConfiguration -State Default",
+        (Ipv6Transition::Isatap, false) => "Set-NetIsatapConfiguration -State Disabled",
+    };
+    run_privileged(
+        "change IPv6 transition settings",
+        &format!("{} -ErrorAction Stop", script),
+    )?;
+
+    Ok(format!(
+        "{:?} {}",
+        technology,
+        if enabled { "restored to default" } else { "disabled" }
+    ))
+}
+
+/// A DHCP option received by the client
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DhcpOption {
+    pub option_code: u8,
+    pub name: String,
+    pub value: String,
+}
+
+/// DHCP option 252 (proxy auto-config URL)
+const DHCP_OPTION_WPAD: u32 = 252;
+
+/// Extract one option from the DhcpInterfaceOptions registry blob
+///
+/// The blob is undocumented: a run of records, each a little-endian header
+/// (option code, vendor flag, data length, then one or two reserved DWORDs
+/// depending on the Windows build) followed by the data padded to 4 bytes.
+/// Both header sizes are tried and a layout is only accepted if every record
+/// lines up exactly with the end of the blob.
+fn dhcp_blob_option(blob: &[u8], code: u32) -> Option<Vec<u8>> {
+    let read_u32 = |i: usize| -> Option<u32> {
+        blob.get(i..i + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+
+    [16usize, 20].iter().find_map(|&header_len| {
+        let mut found = None;
+        let mut i = 0;
+        while i < blob.len() {
+            let option = read_u32(i)?;
+            let len = read_u32(i + 8)? as usize;
+            if option > 255 {
+                return None;
+            }
+            let data = blob.get(i + header_len..i + header_len + len)?;
+            if option == code && found.is_none() {
+                found = Some(data.to_vec());
+            }
+            i += header_len + ((len + 3) & !3);
+        }
+        (i == blob.len()).then_some(found).flatten()
+    })
+}
+
+/// Registry key of the DHCP client state for an adapter
+fn adapter_tcpip_key(adapter_name: &str) -> Result<RegKey, String> {
+    let script = format!(
+        "(Get-NetAdapter -Name '{}' -ErrorAction Stop).InterfaceGuid",
+        adapter_name.replace("'", "''")
+    );
+    let guid = run_powershell(&script).map_err(|_| format!("Adapter not found: {}", adapter_name))?;
+
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(format!(
+            "SYSTEM\\CurrentControlSet\\Services\\Tcpip\\Parameters\\Interfaces\\{}",
+            guid.trim()
+        ))
+        .map_err(|e| format!("Failed to open adapter registry key: {}", e))
+}
+
+/// List the DHCP options the adapter received in its current lease
+/// Returns an empty list for adapters with a static configuration
+#[tauri::command]
+pub fn get_dhcp_option_details(adapter_name: String) -> Result<Vec<DhcpOption>, AppError> {
+    let key = adapter_tcpip_key(&adapter_name)?;
+
+    let dhcp_enabled: u32 = key.get_value("EnableDHCP").unwrap_or(0);
+    if dhcp_enabled == 0 {
+        return Ok(vec![]);
+    }
+
+    let string_value = |name: &str| -> Option<String> {
+        key.get_value::<String, _>(name)
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    let mut options = Vec::new();
+    let mut push = |option_code: u8, name: &str, value: Option<String>| {
+        if let Some(value) = value {
+            options.push(DhcpOption {
+                option_code,
+                name: name.to_string(),
+                value,
+            });
+        }
+    };
+
+    push(1, "Subnet Mask", string_value("DhcpSubnetMask"));
+    push(
+        3,
+        "Router",
+        key.get_value::<Vec<String>, _>("DhcpDefaultGateway")
+            .ok()
+            .map(|gateways| gateways.join(", "))
+            .filter(|v| !v.is_empty()),
+    );
+    push(
+        6,
+        "DNS Servers",
+        string_value("DhcpNameServer").map(|v| v.split_whitespace().collect::<Vec<_>>().join(", ")),
+    );
+    push(15, "Domain Name", string_value("DhcpDomain"));
+    push(
+        51,
+        "Lease Time",
+        key.get_value::<u32, _>("Lease").ok().map(|secs| format!("{} seconds", secs)),
+    );
+    push(54, "DHCP Server", string_value("DhcpServer"));
+    push(
+        252,
+        "WPAD URL",
+        key.get_raw_value("DhcpInterfaceOptions")
+            .ok()
+            .and_then(|raw| dhcp_blob_option(&raw.bytes, DHCP_OPTION_WPAD))
+            .map(|data| String::from_utf8_lossy(&data).trim_end_matches('\0').to_string())
+            .filter(|v| !v.is_empty()),
+    );
+
+    Ok(options)
+}
+
+/// Current DHCP lease of an adapter; every field is `None` on a static configuration
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DhcpLease {
+    /// DHCP server host name from reverse DNS, or its IP when it has none
+    pub server: Option<String>,
+    /// Unix timestamp (seconds)
+    pub lease_obtained: Option<u64>,
+    /// Unix timestamp (seconds)
+    pub lease_expires: Option<u64>,
+    pub dhcp_server_ip: Option<String>,
+}
+
+/// Lease times and DHCP server of an adapter, read from its Tcpip registry key
+#[tauri::command]
+pub fn get_dhcp_lease(adapter_name: String) -> Result<DhcpLease, AppError> {
+    let key = adapter_tcpip_key(&adapter_name)?;
+
+    let dhcp_enabled: u32 = key.get_value("EnableDHCP").unwrap_or(0);
+    if dhcp_enabled == 0 {
+        return Ok(DhcpLease::default());
+    }
+
+    let timestamp = |name: &str| {
+        key.get_value::<u32, _>(name)
+            .ok()
+            .filter(|secs| *secs != 0)
+            .map(u64::from)
+    };
+    let dhcp_server_ip = key
+        .get_value::<String, _>("DhcpServer")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty() && v != "255.255.255.255");
+
+    let server = dhcp_server_ip.as_ref().map(|ip| {
+        let script = format!(
+            "(Resolve-DnsName -Name '{}' -Type PTR -QuickTimeout -ErrorAction SilentlyContinue | Select-Object -First 1).NameHost",
+            ip
+        );
+        run_powershell(&script)
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| ip.clone())
+    });
+
+    Ok(DhcpLease {
+        server,
+        lease_obtained: timestamp("LeaseObtainedTime"),
+        lease_expires: timestamp("LeaseTerminatesTime"),
+        dhcp_server_ip,
+    })
+}
+
+/// One entry of the IP routing table
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Route {
+    /// "IPv4" or "IPv6"
+    pub address_family: String,
+    pub destination: String,
+    /// Dotted mask for IPv4, expanded mask (e.g. "ffff:ffff:ffff:ffff::") for IPv6
+    pub netmask: String,
+    pub prefix_length: u8,
+    /// Next hop; "0.0.0.0" or "::" means the destination is on-link
+    pub gateway: String,
+    pub interface: String,
+    pub interface_index: u32,
+    /// Route metric; Windows adds `interface_metric` to get the effective metric
+    pub metric: u32,
+    pub interface_metric: Option<u32>,
+}
+
+/// Route as emitted by the Get-NetRoute script, before the prefix is split
+#[derive(Debug, Deserialize)]
+struct RawRoute {
+    address_family: String,
+    destination_prefix: String,
+    gateway: String,
+    interface: String,
+    interface_index: u32,
+    metric: u32,
+    interface_metric: Option<u32>,
+}
+
+/// Split "a.b.c.d/n" or "x::/n" into destination, netmask and prefix length
+fn split_route_prefix(prefix: &str) -> Option<(String, String, u8)> {
+    let (addr, len) = prefix.trim().split_once('/')?;
+    let len: u8 = len.parse().ok()?;
+    let netmask = match addr.parse::<IpAddr>().ok()? {
+        IpAddr::V4(_) if len <= 32 => Ipv4Addr::from(prefix_mask(len)).to_string(),
+        IpAddr::V6(_) if len <= 128 => {
+            let mask = if len == 0 { 0 } else { u128::MAX << (128 - u32::from(len)) };
+            Ipv6Addr::from(mask).to_string()
+        }
+        _ => return None,
+    };
+    Some((addr.to_string(), netmask, len))
+}
+
+/// Get the IPv4 and IPv6 routing tables from Get-NetRoute
+#[tauri::command]
+pub fn get_route_table() -> Result<Vec<Route>, AppError> {
+    let stdout = run_powershell(
+        r#"
+        $metrics = @{}
+        Get-NetIPInterface -ErrorAction SilentlyContinue | ForEach-Object { $metrics["$($_.InterfaceIndex)/$($_.AddressFamily)"] = $_.InterfaceMetric }
+        $routes = @(Get-NetRoute -ErrorAction Stop | ForEach-Object {
+            @{
+                address_family = "$($_.AddressFamily)"
+                destination_prefix = $_.DestinationPrefix
+                gateway = $_.NextHop
+                interface = $_.InterfaceAlias
+                interface_index = [int]$_.InterfaceIndex
+                metric = [int]$_.RouteMetric
+                interface_metric = $metrics["$($_.InterfaceIndex)/$($_.AddressFamily)"]
+            }
+        })
+        ConvertTo-Json -InputObject $routes -Compress
+        "#,
+    )?;
+
+    let raw: Vec<RawRoute> = serde_json::from_str(stdout.trim())?;
+    Ok(raw
+        .into_iter()
+        .filter_map(|r| {
+            let (destination, netmask, prefix_length) = split_route_prefix(&r.destination_prefix)?;
+            Some(Route {
+                address_family: r.address_family,
+                destination,
+                netmask,
+                prefix_length,
+                gateway: r.gateway,
+                interface: r.interface,
+                interface_index: r.interface_index,
+                metric: r.metric,
+                interface_metric: r.interface_metric,
+            })
+        })
+        .collect())
+}
+
+/// Check a route's destination is CIDR and its next hop an address of the same family
+fn validate_route(destination_prefix: &str, next_hop: &str) -> Result<(), String> {
+    let (destination, _, _) = split_route_prefix(destination_prefix).ok_or_else(|| {
+        format!("Invalid destination (expected CIDR like 10.0.0.0/8): {}", destination_prefix)
+    })?;
+    let next_hop: IpAddr = next_hop
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid next hop address: {}", next_hop))?;
+    let destination: IpAddr = destination.parse().map_err(|_| "Invalid destination".to_string())?;
+    if destination.is_ipv4() != next_hop.is_ipv4() {
+        return Err(format!(
+            "Next hop {} is not the same address family as {}",
+            next_hop, destination_prefix
+        ));
+    }
+    Ok(())
+}
+
+/// Add a persistent static route through an adapter
+/// Requires administrator privileges
+#[tauri::command]
+pub fn add_route(
+    destination_prefix: String,
+    next_hop: String,
+    interface_name: String,
+    metric: Option<u32>,
+) -> Result<String, AppError> {
+    let destination_prefix = destination_prefix.trim().to_string();
+    let next_hop = next_hop.trim().to_string();
+    validate_route(&destination_prefix, &next_hop)?;
+    ensure_adapter_exists(&interface_name)?;
+
+    let metric_arg = metric.map(|m| format!(" -RouteMetric {}", m)).unwrap_or_default();
+    let script = format!(
+        "New-NetRoute -DestinationPrefix '{}' -InterfaceAlias '{}' -NextHop '{}'{} -ErrorAction Stop | Out-Null",
+        destination_prefix,
+        interface_name.replace("'", "''"),
+        next_hop,
+        metric_arg
+    );
+    run_privileged(&format!("add a route to {}", destination_prefix), &script)?;
+
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&interface_name);
+    Ok(format!(
+        "Added route to {} via {} on {}",
+        destination_prefix, next_hop, interface_name
+    ))
+}
+
+/// Delete the routes to a destination on an adapter
+/// Requires administrator privileges
+#[tauri::command]
+pub fn delete_route(destination_prefix: String, interface_name: String) -> Result<String, AppError> {
+    let destination_prefix = destination_prefix.trim().to_string();
+    if split_route_prefix(&destination_prefix).is_none() {
+        return Err(format!(
+            "Invalid destination (expected CIDR like 10.0.0.0/8): {}",
+            destination_prefix
+        )
+        .into());
+    }
+    let alias = interface_name.replace("'", "''");
+
+    let count = run_powershell(&format!(
+        "@(Get-NetRoute -DestinationPrefix '{}' -InterfaceAlias '{}' -ErrorAction SilentlyContinue).Count",
+        destination_prefix, alias
+    ))?;
+    if count.trim() == "0" {
+        return Err(AppError::NotFound(format!(
+            "No route to {} on {}",
+            destination_prefix, interface_name
+        )));
+    }
+
+    let script = format!(
+        "Remove-NetRoute -DestinationPrefix '{}' -InterfaceAlias '{}' -Confirm:$false -ErrorAction Stop",
+        destination_prefix, alias
+    );
+    run_privileged(&format!("delete the route to {}", destination_prefix), &script)?;
+
+    crate::cache::NETWORK_CACHE.invalidate_adapter(&interface_name);
+    Ok(format!("Deleted route to {} on {}", destination_prefix, interface_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_route_prefix() {
+        assert_eq!(
+            split_route_prefix("0.0.0.0/0"),
+            Some(("0.0.0.0".to_string(), "0.0.0.0".to_string(), 0))
+        );
+        assert_eq!(
+            split_route_prefix("192.168.1.0/24"),
+            Some(("192.168.1.0".to_string(), "255.255.255.0".to_string(), 24))
+        );
+        assert_eq!(
+            split_route_prefix("fe80::/64"),
+            Some(("fe80::".to_string(), "ffff:ffff:ffff:ffff::".to_string(), 64))
+        );
+        assert_eq!(split_route_prefix("10.0.0.0/33"), None);
+        assert_eq!(split_route_prefix("garbage"), None);
+    }
+
+    #[test]
+    fn test_validate_route() {
+        assert!(validate_route("10.0.0.0/8", "192.168.1.1").is_ok());
+        assert!(validate_route("2001:db8::/32", "fe80::1").is_ok());
+        assert!(validate_route("10.0.0.0", "192.168.1.1").is_err());
+        assert!(validate_route("10.0.0.0/8", "gateway").is_err());
+        assert!(validate_route("10.0.0.0/8", "fe80::1").is_err());
+    }
+
+    #[test]
+    fn test_validate_adapter_name() {
+        assert!(validate_adapter_name("WAN").is_ok());
+        assert!(validate_adapter_name("Ethernet 2 (Dock)").is_ok());
+        assert!(validate_adapter_name("  ").is_err());
+        assert!(validate_adapter_name("LAN*").is_err());
+        assert!(validate_adapter_name("a/b").is_err());
+    }
+
+    #[test]
+    fn test_normalize_mac() {
+        assert_eq!(normalize_mac("02:1a:2b:3c:4d:5e"), Ok("02-1A-2B-3C-4D-5E".to_string()));
+        assert_eq!(normalize_mac("021A2B3C4D5E"), Ok("02-1A-2B-3C-4D-5E".to_string()));
+        assert!(normalize_mac("01-00-5E-00-00-01").is_err());
+        assert!(normalize_mac("00-00-00-00-00-00").is_err());
+        assert!(normalize_mac("02-1A-2B-3C-4D").is_err());
+        assert!(normalize_mac("02-1A-2B-3C-4D-ZZ").is_err());
+
+        assert!(is_locally_administered("02-1A-2B-3C-4D-5E"));
+        assert!(!is_locally_administered("00-1A-2B-3C-4D-5E"));
+    }
+
+    #[test]
+    fn test_parse_hosts() {
+        let contents = "# Copyright (c) 1993-2009 Microsoft Corp.\r\n\
+                        #\r\n\
+                        #      102.54.94.97     rhino.acme.com          # source server\r\n\
+                        127.0.0.1 localhost dev.local # loopback\r\n\
+                        \r\n\
+                        ::1\tlocalhost\r\n";
+        let entries = parse_hosts(contents);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0],
+            HostsEntry {
+                ip: "102.54.94.97".to_string(),
+                hostnames: vec!["rhino.acme.com".to_string()],
+                enabled: false,
+                comment: Some("source server".to_string()),
+            }
+        );
+        assert!(entries[1].enabled);
+        assert_eq!(entries[1].hostnames, vec!["localhost", "dev.local"]);
+        assert_eq!(entries[1].comment.as_deref(), Some("loopback"));
+        assert_eq!(entries[2].ip, "::1");
+    }
+
+    #[test]
+    fn test_edit_hosts() {
+        let contents = "# sample\r\n# 10.0.0.1 app.test\r\n127.0.0.1 localhost app.test # dev\r\n";
+
+        let contents = contents.as_bytes();
+
+        let removed = remove_hosts_name(contents, "APP.test").unwrap();
+        assert_eq!(
+            removed,
+            b"# sample\r\n# 10.0.0.1 app.test\r\n127.0.0.1\tlocalhost\t# dev\r\n"
+        );
+        assert_eq!(remove_hosts_name(contents, "missing.test"), None);
+
+        let added = add_hosts_mapping(b"# sample", "10.0.0.2", "app.test");
+        assert_eq!(added, b"# sample\n10.0.0.2\tapp.test\n");
+        let replaced = add_hosts_mapping(contents, "10.0.0.2", "app.test");
+        assert!(replaced.starts_with(b"# sample\r\n# 10.0.0.1 app.test\r\n"));
+        assert!(replaced.ends_with(b"127.0.0.1\tlocalhost\t# dev\r\n10.0.0.2\tapp.test\r\n"));
+
+        // ANSI comment bytes survive an edit untouched
+        let ansi = b"# caf\xe9\r\n10.0.0.1 app.test\r\n";
+        assert_eq!(remove_hosts_name(ansi, "app.test").unwrap(), b"# caf\xe9\r\n");
+
+        assert!(validate_hosts_name("my-host.local").is_ok());
+        assert!(validate_hosts_name("bad host").is_err());
+        assert!(validate_hosts_name("").is_err());
+    }
+
+    #[test]
+    fn test_merge_proxy_server() {
+        assert_eq!(merge_proxy_server("", "proxy.corp", 8080), "proxy.corp:8080");
+        assert_eq!(merge_proxy_server("old:3128", "proxy.corp", 8080), "proxy.corp:8080");
+        assert_eq!(
+            merge_proxy_server("http=a:80;https=b:443", "proxy.corp", 8080),
+            "http=proxy.corp:8080;https=b:443"
+        );
+        assert_eq!(
+            merge_proxy_server("HTTP=a:80;https=b:443", "proxy.corp", 8080),
+            "http=proxy.corp:8080;https=b:443"
+        );
+        assert_eq!(
+            merge_proxy_server("https=b:443;socks=c:1080", "proxy.corp", 8080),
+            "http=proxy.corp:8080;https=b:443;socks=c:1080"
+        );
+    }
+
+    #[test]
+    fn test_parse_proxy_server() {
+        assert_eq!(
+            parse_proxy_server("proxy.corp:8080"),
+            (Some("proxy.corp".to_string()), Some(8080))
+        );
+        assert_eq!(
+            parse_proxy_server("https=secure.corp:443;http=web.corp:3128"),
+            (Some("web.corp".to_string()), Some(3128))
+        );
+        assert_eq!(
+            parse_proxy_server("https=secure.corp:443;HTTP=web.corp:3128"),
+            (Some("web.corp".to_string()), Some(3128))
+        );
+        assert_eq!(
+            parse_proxy_server("socks=socks.corp:1080"),
+            (Some("socks.corp".to_string()), Some(1080))
+        );
+        assert_eq!(parse_proxy_server("proxy.corp"), (Some("proxy.corp".to_string()), None));
+        assert_eq!(parse_proxy_server(""), (None, None));
+    }
+
+    #[test]
+    fn test_parse_link_speed() {
+        assert_eq!(parse_link_speed("1 Gbps"), Some(1000));
+        assert_eq!(parse_link_speed("2.5 Gbps"), Some(2500));
+        assert_eq!(parse_link_speed("100 Mbps"), Some(100));
+        assert_eq!(parse_link_speed("866.7 Mbps"), Some(867));
+        assert_eq!(parse_link_speed("0 bps"), Some(0));
+        assert_eq!(parse_link_speed(""), None);
+        assert_eq!(parse_link_speed("fast"), None);
+    }
+
+    #[test]
+    fn test_is_on_link() {
+        let addresses = [(Ipv4Addr::new(192, 168, 1, 10), 24), (Ipv4Addr::new(10, 0, 0, 5), 30)];
+        assert!(is_on_link(Ipv4Addr::new(192, 168, 1, 200), &addresses));
+        assert!(is_on_link(Ipv4Addr::new(10, 0, 0, 6), &addresses));
+        assert!(!is_on_link(Ipv4Addr::new(10, 0, 0, 9), &addresses));
+        assert!(!is_on_link(Ipv4Addr::new(192, 168, 2, 1), &addresses));
+        assert!(!is_on_link(Ipv4Addr::new(192, 168, 1, 1), &[]));
+    }
+
+    #[test]
+    fn test_parse_live_neighbor() {
+        assert_eq!(
+            parse_live_neighbor("00-11-22-aa-bb-cc Reachable\r\n"),
+            Some("00-11-22-AA-BB-CC".to_string())
+        );
+        assert_eq!(parse_live_neighbor("00-11-22-AA-BB-CC Stale"), None);
+        assert_eq!(parse_live_neighbor("00-00-00-00-00-00 Unreachable"), None);
+        assert_eq!(parse_live_neighbor("00-00-00-00-00-00 Incomplete"), None);
+        assert_eq!(parse_live_neighbor(""), None);
+    }
+
+    #[test]
+    fn test_parse_ipconfig_all() {
+        let output = [
+            "",
+            "Windows IP Configuration",
+            "",
+            "   Host Name . . . . . . . . . . . . : DESKTOP",
+            "",
+            "Ethernet adapter Ethernet:",
+            "",
+            "   Connection-specific DNS Suffix  . : home",
+            "   Description . . . . . . . . . . . : Intel(R) Ethernet Connection",
+            "   Physical Address. . . . . . . . . : 00-11-22-33-44-55",
+            "   DHCP Enabled. . . . . . . . . . . : Yes",
+            "   Link-local IPv6 Address . . . . . : fe80::1%12(Preferred)",
+            "   IPv4 Address. . . . . . . . . . . : 192.168.1.10(Preferred)",
+            "   Subnet Mask . . . . . . . . . . . : 255.255.255.0",
+            "   Lease Obtained. . . . . . . . . . : Monday, January 1, 2024 10:00:00 AM",
+            "   Default Gateway . . . . . . . . . : fe80::1%12",
+            "                                       192.168.1.1",
+            "   DHCP Server . . . . . . . . . . . : 192.168.1.1",
+            "   DNS Servers . . . . . . . . . . . : 192.168.1.1",
+            "                                       8.8.8.8",
+            "   NetBIOS over Tcpip. . . . . . . . : Enabled",
+            "",
+            "Drahtlos-LAN-Adapter WLAN:",
+            "",
+            "   Beschreibung. . . . . . . . . . . : Wi-Fi 6 AX201",
+            "   DHCP aktiviert. . . . . . . . . . : Nein",
+            "   Standardgateway . . . . . . . . . : ",
+            "   M\u{fffd}scara de subred . . . . . . . : 255.255.0.0",
+        ]
+        .join("\r\n");
+
+        let adapters = parse_ipconfig_all(&output);
+        assert_eq!(adapters.len(), 2);
+
+        let ethernet = &adapters[0];
+        assert_eq!(ethernet.name, "Ethernet");
+        assert_eq!(ethernet.description, "Intel(R) Ethernet Connection");
+        assert_eq!(ethernet.physical_address.as_deref(), Some("00-11-22-33-44-55"));
+        assert!(ethernet.dhcp_enabled);
+        assert_eq!(ethernet.ipv4_addresses, vec!["192.168.1.10"]);
+        assert_eq!(ethernet.subnet_masks, vec!["255.255.255.0"]);
+        assert_eq!(ethernet.default_gateways, vec!["fe80::1%12", "192.168.1.1"]);
+        assert_eq!(ethernet.dns_servers, vec!["192.168.1.1", "8.8.8.8"]);
+        assert_eq!(ethernet.dhcp_server.as_deref(), Some("192.168.1.1"));
+        assert_eq!(
+            ethernet.lease_obtained.as_deref(),
+            Some("Monday, January 1, 2024 10:00:00 AM")
+        );
+        assert_eq!(ethernet.lease_expires, None);
+
+        let wlan = &adapters[1];
+        assert_eq!(wlan.name, "WLAN");
+        assert_eq!(wlan.description, "Wi-Fi 6 AX201");
+        assert!(!wlan.dhcp_enabled);
+        assert!(wlan.default_gateways.is_empty());
+        assert_eq!(wlan.subnet_masks, vec!["255.255.0.0"]);
+    }
+
+    #[test]
+    fn test_parse_wol_state() {
+        assert_eq!(parse_wol_state("Enabled\r\n"), Some(true));
+        assert_eq!(parse_wol_state("disabled"), Some(false));
+        assert_eq!(parse_wol_state("Unsupported"), None);
+        assert_eq!(parse_wol_state("Inactive"), None);
+        assert_eq!(parse_wol_state(""), None);
+    }
+
+    #[test]
+    fn test_validate_mtu() {
+        assert!(validate_mtu(1500).is_ok());
+        assert!(validate_mtu(MIN_MTU).is_ok());
+        assert!(validate_mtu(MAX_MTU).is_ok());
+        assert!(validate_mtu(575).is_err());
+        assert!(validate_mtu(9001).is_err());
+    }
+
+    #[test]
+    fn test_validate_dns_servers() {
+        let servers = vec![" 1.1.1.1".to_string(), "2606:4700:4700::1111".to_string()];
+        assert_eq!(
+            validate_dns_servers(&servers),
+            Ok(vec!["1.1.1.1".to_string(), "2606:4700:4700::1111".to_string()])
+        );
+        assert!(validate_dns_servers(&[]).is_err());
+        assert!(validate_dns_servers(&["8.8.8".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_dns_preset_servers() {
+        assert_eq!(dns_preset_servers("Cloudflare").unwrap(), vec!["1.1.1.1", "1.0.0.1"]);
+        assert!(dns_preset_servers("bogus").unwrap_err().contains("quad9"));
+    }
+
+    #[test]
+    fn test_throughput_rates() {
+        let previous = AdapterStats {
+            bytes_sent: 1_000,
+            bytes_received: 10_000,
+            ..Default::default()
+        };
+        let current = AdapterStats {
+            bytes_sent: 3_000,
+            bytes_received: 5_000,
+            ..Default::default()
+        };
+        assert_eq!(throughput_rates(&previous, &current, Duration::from_secs(2)), (1_000.0, 0.0));
+        assert_eq!(throughput_rates(&previous, &current, Duration::ZERO), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_validate_static_ipv4() {
+        assert_eq!(
+            validate_static_ipv4("192.168.1.10", "255.255.255.0", "192.168.1.1", "8.8.8.8", ""),
+            Ok(None)
+        );
+        assert_eq!(validate_static_ipv4("192.168.1.10", "255.255.255.0", "", "", ""), Ok(None));
+
+        assert_eq!(
+            validate_static_ipv4("192.168.1.300", "255.255.255.0", "", "", ""),
+            Err("Invalid IP address: 192.168.1.300".to_string())
+        );
+        assert!(validate_static_ipv4("192.168.1.10", "255.0.255.0", "", "", "").is_err());
+        assert!(validate_static_ipv4("192.168.1.10", "255.255.255.0", "", "", "8.8.8").is_err());
+
+        let warning = validate_static_ipv4("192.168.1.10", "255.255.255.0", "192.168.2.1", "", "")
+            .unwrap()
+            .unwrap();
+        assert!(warning.contains("outside the subnet of 192.168.1.10/24"));
+    }
+
+    #[test]
+    fn test_validate_static_ipv6() {
+        assert_eq!(validate_static_ipv6("2001:db8::10", 64, "fe80::1", "2001:4860:4860::8888", ""), Ok(()));
+        assert_eq!(validate_static_ipv6("2001:db8::10", 64, "", "", ""), Ok(()));
+
+        assert_eq!(
+            validate_static_ipv6("2001:db8::10", 64, "fe80::1\"; x", "", ""),
+            Err("Invalid IPv6 gateway: fe80::1\"; x".to_string())
+        );
+        assert!(validate_static_ipv6("2001:db8::zz", 64, "", "", "").is_err());
+        assert!(validate_static_ipv6("2001:db8::10", 129, "", "", "").is_err());
+        assert!(validate_static_ipv6("2001:db8::10", 64, "", "", "8.8.8.8").is_err());
+    }
+
+    fn dhcp_record(code: u32, data: &[u8], header_len: usize) -> Vec<u8> {
+        let mut record = Vec::new();
+        record.extend_from_slice(&code.to_le_bytes());
+        record.extend_from_slice(&0u32.to_le_bytes());
+        record.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        record.resize(header_len, 0);
+        record.extend_from_slice(data);
+        record.resize(record.len() + ((4 - data.len() % 4) % 4), 0);
+        record
+    }
+
+    #[test]
+    fn test_dhcp_blob_option() {
+        for header_len in [16, 20] {
+            let mut blob = dhcp_record(1, &[255, 255, 255, 0], header_len);
+            blob.extend(dhcp_record(252, b"http://wpad/wpad.dat\0", header_len));
+            blob.extend(dhcp_record(15, b"corp.local", header_len));
+
+            assert_eq!(
+                dhcp_blob_option(&blob, 252).as_deref(),
+                Some(&b"http://wpad/wpad.dat\0"[..])
+            );
+            assert_eq!(dhcp_blob_option(&blob, 6), None);
+        }
+
+        assert_eq!(dhcp_blob_option(&[1, 2, 3], 252), None);
+    }
+
+    #[test]
+    fn test_cidr_to_range_24() {
+        let range = cidr_to_range("192.168.1.77/24".to_string()).unwrap();
+        assert_eq!(range.first_ip, "192.168.1.0");
+        assert_eq!(range.last_ip, "192.168.1.255");
+    }
+
+    #[test]
+    fn test_cidr_to_range_edges() {
+        let all = cidr_to_range("10.1.2.3/0".to_string()).unwrap();
+        assert_eq!(all.first_ip, "0.0.0.0");
+        assert_eq!(all.last_ip, "255.255.255.255");
+
+        let host = cidr_to_range("10.1.2.3/32".to_string()).unwrap();
+        assert_eq!(host.first_ip, "10.1.2.3");
+        assert_eq!(host.last_ip, "10.1.2.3");
+    }
+
+    #[test]
+    fn test_cidr_to_range_invalid() {
+        assert!(cidr_to_range("10.0.0.0".to_string()).is_err());
+        assert!(cidr_to_range("10.0.0.0/33".to_string()).is_err());
+        assert!(cidr_to_range("10.0.0.300/24".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_range_to_cidrs_aligned() {
+        let cidrs = range_to_cidrs("192.168.1.0".to_string(), "192.168.1.255".to_string()).unwrap();
+        assert_eq!(cidrs, vec!["192.168.1.0/24"]);
+    }
+
+    #[test]
+    fn test_range_to_cidrs_unaligned() {
+        let cidrs = range_to_cidrs("192.168.1.10".to_string(), "192.168.1.20".to_string()).unwrap();
+        assert_eq!(
+            cidrs,
+            vec!["192.168.1.10/31", "192.168.1.12/30", "192.168.1.16/30", "192.168.1.20/32"]
+        );
+    }
+
+    #[test]
+    fn test_range_to_cidrs_full_space() {
+        let cidrs = range_to_cidrs("0.0.0.0".to_string(), "255.255.255.255".to_string()).unwrap();
+        assert_eq!(cidrs, vec!["0.0.0.0/0"]);
+    }
+
+    #[test]
+    fn test_range_to_cidrs_single_and_reversed() {
+        let cidrs = range_to_cidrs("10.0.0.5".to_string(), "10.0.0.5".to_string()).unwrap();
+        assert_eq!(cidrs, vec!["10.0.0.5/32"]);
+        assert!(range_to_cidrs("10.0.0.6".to_string(), "10.0.0.5".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_is_apipa() {
+        assert!(is_apipa("169.254.12.34"));
+        assert!(!is_apipa("192.168.1.10"));
+        assert!(!is_apipa(""));
+    }
 }
@@ -1,14 +1,109 @@
 //! Unified network configuration query
 //! Combines all 4 PowerShell queries into a single script for efficiency
 
-use serde::Deserialize;
-use std::os::windows::process::CommandExt;
-use std::process::Command;
+use lazy_static::lazy_static;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
 
 use crate::cache::NETWORK_CACHE;
-use crate::network::IPConfiguration;
+use crate::error::AppError;
+use crate::network::{get_network_adapters, IPConfiguration};
+use crate::ps::run_powershell;
 
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+/// Worker threads used by `get_all_ip_configurations`
+const BATCH_WORKERS: usize = 4;
+
+lazy_static! {
+    /// Adapters with a background refresh in progress
+    static ref REFRESHING: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+
+    /// Unified queries currently running, shared by concurrent callers
+    static ref IN_FLIGHT: SingleFlight<IPConfiguration> = SingleFlight::new();
+}
+
+/// A query in progress; waiters block on `done` until `result` is filled
+struct Call<V> {
+    result: Mutex<Option<Result<V, String>>>,
+    done: Condvar,
+}
+
+/// Per-key deduplication: concurrent callers for the same key share one execution
+struct SingleFlight<V> {
+    calls: Mutex<HashMap<String, Arc<Call<V>>>>,
+}
+
+/// Publishes the leader's outcome when dropped, so waiters are woken and the key
+/// is released even if `fetch` panics
+struct LeaderGuard<'a, V> {
+    flight: &'a SingleFlight<V>,
+    key: &'a str,
+    call: Arc<Call<V>>,
+}
+
+impl<V> Drop for LeaderGuard<'_, V> {
+    fn drop(&mut self) {
+        {
+            let mut result = self.call.result.lock().unwrap_or_else(|e| e.into_inner());
+            if result.is_none() {
+                *result = Some(Err("Query failed before producing a result".to_string()));
+            }
+        }
+        self.flight
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(self.key);
+        self.call.done.notify_all();
+    }
+}
+
+impl<V: Clone> SingleFlight<V> {
+    fn new() -> Self {
+        Self {
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run `fetch` for `key`, or wait for the run already in progress and share its result
+    fn run(&self, key: &str, fetch: impl FnOnce() -> Result<V, String>) -> Result<V, String> {
+        let (call, leader) = {
+            let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+            match calls.get(key) {
+                Some(call) => (Arc::clone(call), false),
+                None => {
+                    let call = Arc::new(Call {
+                        result: Mutex::new(None),
+                        done: Condvar::new(),
+                    });
+                    calls.insert(key.to_string(), Arc::clone(&call));
+                    (call, true)
+                }
+            }
+        };
+
+        if leader {
+            let guard = LeaderGuard {
+                flight: self,
+                key,
+                call,
+            };
+            let result = fetch();
+            *guard.call.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(result.clone());
+            return result;
+        }
+
+        let mut result = call.result.lock().unwrap_or_else(|e| e.into_inner());
+        while result.is_none() {
+            result = call.done.wait(result).unwrap_or_else(|e| e.into_inner());
+        }
+        result
+            .clone()
+            .unwrap_or_else(|| Err("Query produced no result".to_string()))
+    }
+}
 
 /// Unified PowerShell script that fetches all network config in one call
 /// This reduces 4 process spawns to 1, saving ~300-600ms
@@ -22,6 +117,9 @@ $result = @{
     primary_dns = ""
     secondary_dns = ""
     dhcp_enabled = $true
+    ipv6_address = ""
+    ipv6_prefix_length = 0
+    ipv6_gateway = ""
     error = $null
 }
 
@@ -55,6 +153,20 @@ try {
     if ($interface) {
         $result.dhcp_enabled = ($interface.Dhcp -eq 'Enabled') -or ($interface.Dhcp -eq 1)
     }
+
+    # Get IPv6 Address (skip link-local and temporary privacy addresses)
+    $ipv6Info = Get-NetIPAddress -InterfaceAlias $AdapterName -AddressFamily IPv6 -ErrorAction SilentlyContinue |
+        Where-Object { $_.IPAddress -notlike 'fe80*' -and $_.SuffixOrigin -ne 'Random' } | Select-Object -First 1
+    if ($ipv6Info) {
+        $result.ipv6_address = $ipv6Info.IPAddress
+        $result.ipv6_prefix_length = $ipv6Info.PrefixLength
+    }
+
+    # Get IPv6 Default Gateway
+    $route6 = Get-NetRoute -InterfaceAlias $AdapterName -DestinationPrefix "::/0" -ErrorAction SilentlyContinue | Select-Object -First 1
+    if ($route6) {
+        $result.ipv6_gateway = $route6.NextHop
+    }
 }
 catch {
     $result.error = $_.Exception.Message
@@ -72,6 +184,12 @@ struct UnifiedPSResponse {
     primary_dns: String,
     secondary_dns: String,
     dhcp_enabled: bool,
+    #[serde(default)]
+    ipv6_address: String,
+    #[serde(default)]
+    ipv6_prefix_length: u8,
+    #[serde(default)]
+    ipv6_gateway: String,
     error: Option<String>,
 }
 
@@ -98,53 +216,18 @@ fn prefix_to_subnet(prefix: u8) -> String {
     )
 }
 
-/// Get IP configuration using unified query with caching
-/// 
-/// # Flow:
-/// 1. Check cache for valid entry
-/// 2. If cache hit, return immediately
-/// 3. If cache miss, run unified PS script
-/// 4. Parse response and update cache
-/// 5. Return configuration
-#[tauri::command]
-pub fn get_ip_configuration_unified(adapter_name: String) -> Result<IPConfiguration, String> {
-    // Step 1: Check cache first (fast path)
-    if let Some(cached) = NETWORK_CACHE.get_ip_config(&adapter_name) {
-        return Ok(cached);
-    }
-
-    // Step 2: Run unified PowerShell script
+/// Run the unified script for one adapter (no caching)
+fn fetch_ip_configuration(adapter_name: &str) -> Result<IPConfiguration, String> {
+    // Run unified PowerShell script
     let ps_command = format!(
         r#"& {{ {} }} -AdapterName '{}'"#,
         UNIFIED_PS_SCRIPT,
         adapter_name.replace("'", "''") // Escape single quotes
     );
 
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args([
-            "-NoProfile",
-            "-NonInteractive",
-            "-ExecutionPolicy", "Bypass",
-            "-Command",
-            &ps_command,
-        ])
-        .output()
-        .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        
-        // Try to return stale cache data if available
-        if let Some((stale_data, _)) = NETWORK_CACHE.get_ip_config_stale(&adapter_name) {
-            return Ok(stale_data);
-        }
-        
-        return Err(format!("PowerShell error: {}", stderr));
-    }
+    let stdout = run_powershell(&ps_command)?;
 
-    // Step 3: Parse JSON response
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Parse JSON response
     let trimmed = stdout.trim();
     
     if trimmed.is_empty() {
@@ -161,22 +244,148 @@ pub fn get_ip_configuration_unified(adapter_name: String) -> Result<IPConfigurat
         }
     }
 
-    // Step 4: Build and cache the result
+    // Build the result
     let subnet_mask = prefix_to_subnet(response.prefix_length);
     
-    let config = IPConfiguration {
+    Ok(IPConfiguration {
         ip_address: response.ip_address,
         subnet_mask,
         gateway: response.gateway,
         primary_dns: response.primary_dns,
         secondary_dns: response.secondary_dns,
         dhcp_enabled: response.dhcp_enabled,
-    };
+        ipv6_address: response.ipv6_address,
+        ipv6_prefix_length: response.ipv6_prefix_length,
+        ipv6_gateway: response.ipv6_gateway,
+    })
+}
+
+/// Query the adapter and store the result in the cache
+/// Concurrent calls for the same adapter share a single query
+fn refresh_ip_configuration(adapter_name: &str) -> Result<IPConfiguration, String> {
+    IN_FLIGHT.run(adapter_name, || {
+        let config = fetch_ip_configuration(adapter_name)?;
+        NETWORK_CACHE.set_ip_config(adapter_name, config.clone());
+        Ok(config)
+    })
+}
 
-    // Step 5: Update cache
-    NETWORK_CACHE.set_ip_config(&adapter_name, config.clone());
+/// Refresh an adapter's cache entry on a background thread, unless one is already running
+fn spawn_background_refresh(adapter_name: &str) {
+    if !REFRESHING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(adapter_name.to_string())
+    {
+        return;
+    }
 
-    Ok(config)
+    let adapter_name = adapter_name.to_string();
+    std::thread::spawn(move || {
+        if let Err(e) = refresh_ip_configuration(&adapter_name) {
+            warn!("Background refresh failed for {}: {}", adapter_name, e);
+        }
+        REFRESHING
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&adapter_name);
+    });
+}
+
+/// Get IP configuration using unified query with caching
+/// 
+/// # Flow:
+/// 1. Check cache for valid entry
+/// 2. If cache hit, return immediately
+/// 3. If the entry expired and `use_stale` (default true), return it now and
+///    refresh it in the background
+/// 4. Otherwise run the unified PS script and update the cache, falling back
+///    to stale data if the script fails
+#[tauri::command]
+pub fn get_ip_configuration_unified(
+    adapter_name: String,
+    use_stale: Option<bool>,
+) -> Result<IPConfiguration, AppError> {
+    // Step 1-2: Check cache first (fast path)
+    if let Some(cached) = NETWORK_CACHE.get_ip_config(&adapter_name) {
+        return Ok(cached);
+    }
+
+    // Step 3: Stale-while-revalidate
+    if use_stale.unwrap_or(true) {
+        if let Some((stale, _)) = NETWORK_CACHE.get_ip_config_stale(&adapter_name) {
+            spawn_background_refresh(&adapter_name);
+            return Ok(stale);
+        }
+    }
+
+    // Step 4: Query now
+    refresh_ip_configuration(&adapter_name).or_else(|e| {
+        NETWORK_CACHE
+            .get_ip_config_stale(&adapter_name)
+            .map(|(stale, _)| stale)
+            .ok_or(AppError::from(e))
+    })
+}
+
+/// One adapter's entry in a batch query; `error` is set when its config couldn't be read
+#[derive(Debug, Serialize, Clone)]
+pub struct AdapterIPConfiguration {
+    pub adapter_name: String,
+    pub config: Option<IPConfiguration>,
+    pub error: Option<String>,
+}
+
+/// Apply `f` to every item on up to `workers` threads, keeping input order
+pub(crate) fn map_concurrent<T: Sync, R: Send>(
+    items: &[T],
+    workers: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(index) else { break };
+                let result = f(item);
+                results.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Get the IP configuration of every adapter through the cached unified path
+/// Adapters that fail are returned with an error instead of failing the batch
+#[tauri::command]
+pub fn get_all_ip_configurations() -> Result<Vec<AdapterIPConfiguration>, AppError> {
+    let names: Vec<String> = get_network_adapters()?
+        .into_iter()
+        .map(|adapter| adapter.name)
+        .collect();
+
+    let query = |name: &String| match get_ip_configuration_unified(name.clone(), None) {
+        Ok(config) => AdapterIPConfiguration {
+            adapter_name: name.clone(),
+            config: Some(config),
+            error: None,
+        },
+        Err(e) => AdapterIPConfiguration {
+            adapter_name: name.clone(),
+            config: None,
+            error: Some(e.to_string()),
+        },
+    };
+    Ok(map_concurrent(&names, BATCH_WORKERS, query))
 }
 
 /// Invalidate cache for adapter (call after applying changes)
@@ -197,9 +406,48 @@ pub fn get_network_cache_stats() -> crate::cache::CacheStats {
     NETWORK_CACHE.stats()
 }
 
+/// Reset the cache hit/miss counters (e.g. before a benchmark run)
+#[tauri::command]
+pub fn reset_cache_stats() {
+    NETWORK_CACHE.reset_stats();
+}
+
+/// Set the TTL (1-3600 seconds) used for newly cached configurations
+#[tauri::command]
+pub fn set_cache_ttl(seconds: u64) -> Result<(), AppError> {
+    Ok(crate::cache::set_cache_ttl_secs(seconds)?)
+}
+
+/// Get the TTL (seconds) used for newly cached configurations
+#[tauri::command]
+pub fn get_cache_ttl() -> u64 {
+    crate::cache::cache_ttl_secs()
+}
+
+/// Stress-test the cache lock under concurrent access (debug builds with `cache-stress`)
+#[cfg(feature = "cache-stress")]
+#[tauri::command]
+pub fn stress_cache(
+    threads: usize,
+    ops_per_thread: usize,
+) -> Result<crate::cache::StressReport, AppError> {
+    if threads == 0 || threads > 64 || ops_per_thread == 0 || ops_per_thread > 100_000 {
+        return Err("threads must be 1-64 and ops_per_thread 1-100000".into());
+    }
+    Ok(crate::cache::run_stress_test(threads, ops_per_thread))
+}
+
+/// Stress-test stub when the `cache-stress` feature is disabled
+#[cfg(not(feature = "cache-stress"))]
+#[tauri::command]
+pub fn stress_cache(_threads: usize, _ops_per_thread: usize) -> Result<(), AppError> {
+    Err("Cache stress testing requires building with the `cache-stress` feature".into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::mpsc;
 
     #[test]
     fn test_prefix_to_subnet_24() {
@@ -235,4 +483,96 @@ mod tests {
     fn test_prefix_to_subnet_30() {
         assert_eq!(prefix_to_subnet(30), "255.255.255.252");
     }
-}
\ No newline at end of file
+
+    /// Block until a follower holds the in-flight call for `key`
+    /// (the map and the leader's guard hold the other two references)
+    fn wait_for_follower<V>(flight: &SingleFlight<V>, key: &str) {
+        loop {
+            let followers = flight
+                .calls
+                .lock()
+                .unwrap()
+                .get(key)
+                .map_or(0, |call| Arc::strong_count(call) - 2);
+            if followers > 0 {
+                return;
+            }
+            std::thread::yield_now();
+        }
+    }
+
+    /// Start a leader running `fetch` for "Ethernet", then a follower once the
+    /// leader's call is registered; returns both threads' results
+    fn run_leader_and_follower(
+        flight: &Arc<SingleFlight<u32>>,
+        fetch: impl FnOnce() -> Result<u32, String> + Send + 'static,
+    ) -> (std::thread::Result<Result<u32, String>>, Result<u32, String>) {
+        let (started_tx, started_rx) = mpsc::channel();
+        let leader = {
+            let flight = flight.clone();
+            std::thread::spawn(move || {
+                let inner = flight.clone();
+                flight.run("Ethernet", move || {
+                    started_tx.send(()).unwrap();
+                    wait_for_follower(&inner, "Ethernet");
+                    fetch()
+                })
+            })
+        };
+
+        started_rx.recv().unwrap();
+        let follower = {
+            let flight = flight.clone();
+            std::thread::spawn(move || flight.run("Ethernet", || Ok(0)))
+        };
+
+        (leader.join(), follower.join().unwrap())
+    }
+
+    #[test]
+    fn test_single_flight_shares_one_fetch() {
+        let flight = Arc::new(SingleFlight::<u32>::new());
+        let fetches = Arc::new(AtomicUsize::new(0));
+
+        let counter = fetches.clone();
+        let (leader, follower) = run_leader_and_follower(&flight, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(42)
+        });
+        assert_eq!(leader.unwrap(), Ok(42));
+        assert_eq!(follower, Ok(42));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        // Once finished, the next call runs a fresh fetch
+        assert_eq!(
+            flight.run("Ethernet", || Err("offline".to_string())),
+            Err("offline".to_string())
+        );
+    }
+
+    #[test]
+    fn test_single_flight_leader_panic_releases_waiters() {
+        let flight = Arc::new(SingleFlight::<u32>::new());
+
+        let (leader, follower) = run_leader_and_follower(&flight, || panic!("fetch panicked"));
+        assert!(leader.is_err());
+        assert!(follower.is_err());
+
+        // The key was released, so later callers run their own fetch instead of hanging
+        assert_eq!(flight.run("Ethernet", || Ok(7)), Ok(7));
+    }
+
+    #[test]
+    fn test_map_concurrent_keeps_order() {
+        let items: Vec<u32> = (0..10).collect();
+        let calls = AtomicUsize::new(0);
+        let doubled = map_concurrent(&items, BATCH_WORKERS, |n| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            n * 2
+        });
+        assert_eq!(doubled, (0..10).map(|n| n * 2).collect::<Vec<_>>());
+        assert_eq!(calls.load(Ordering::SeqCst), 10);
+
+        assert!(map_concurrent(&[] as &[u32], BATCH_WORKERS, |n| *n).is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! Shared printer support
+//! Lists printers shared from this machine and checks connectivity to remote printer shares
+
+use serde::{Deserialize, Serialize};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+use crate::ps::run_powershell;
+
+/// Timeout for the SMB port check against the print server
+const PRINT_SERVER_TIMEOUT_MS: u64 = 3000;
+
+/// Marker printed by the listing script when the spooler isn't running
+const SPOOLER_STOPPED: &str = "SPOOLER_STOPPED";
+
+/// Printer shared from this machine
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NetworkPrinter {
+    pub name: String,
+    pub port: String,
+    pub driver: String,
+    pub shared_name: String,
+}
+
+/// Result of testing a remote printer share
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrinterConnectionTest {
+    pub server: String,
+    pub printer: String,
+    /// SMB port (445) on the print server accepted a connection
+    pub reachable: bool,
+    /// The print server reports a printer with that name
+    pub printer_found: bool,
+    pub latency_ms: Option<u64>,
+}
+
+/// Split `\\server\printer` into its parts
+fn parse_unc_printer(path: &str) -> Result<(String, String), String> {
+    let trimmed = path.trim().trim_start_matches('\\');
+    match trimmed.split_once('\\') {
+        Some((server, printer)) if !server.is_empty() && !printer.is_empty() => {
+            Ok((server.to_string(), printer.trim_end_matches('\\').to_string()))
+        }
+        _ => Err(format!("Invalid printer path (expected \\\\server\\printer): {}", path)),
+    }
+}
+
+/// List printers shared from this machine
+#[tauri::command]
+pub fn list_network_printers() -> Result<Vec<NetworkPrinter>, AppError> {
+    let stdout = run_powershell(
+        r#"
+        $spooler = Get-Service -Name Spooler -ErrorAction SilentlyContinue
+        if (-not $spooler -or $spooler.Status -ne 'Running') { 'SPOOLER_STOPPED'; return }
+        $printers = @(Get-Printer -ErrorAction SilentlyContinue | Where-Object { $_.Shared } | ForEach-Object {
+            @{ name = $_.Name; port = "$($_.PortName)"; driver = "$($_.DriverName)"; shared_name = "$($_.ShareName)" }
+        })
+        ConvertTo-Json -InputObject $printers -Compress
+        "#,
+    )?;
+
+    let trimmed = stdout.trim();
+    if trimmed == SPOOLER_STOPPED {
+        return Err("Print Spooler service is not running".into());
+    }
+    if trimmed.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(serde_json::from_str(trimmed)?)
+}
+
+/// Check that a printer share like `\\server\printer` is reachable
+#[tauri::command]
+pub fn test_printer_connection(
+    unc_printer_path: String,
+) -> Result<PrinterConnectionTest, AppError> {
+    let (server, printer) = parse_unc_printer(&unc_printer_path)?;
+    let timeout = Duration::from_millis(PRINT_SERVER_TIMEOUT_MS);
+
+    let addr = (server.as_str(), 445)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", server, e))?
+        .next()
+        .ok_or_else(|| format!("No address found for {}", server))?;
+
+    let start_time = Instant::now();
+    let reachable = TcpStream::connect_timeout(&addr, timeout).is_ok();
+    let latency_ms = reachable.then(|| start_time.elapsed().as_millis() as u64);
+
+    let printer_found = reachable && {
+        let script = format!(
+            "if (Get-Printer -ComputerName '{}' -Name '{}' -ErrorAction SilentlyContinue) {{ 'true' }} else {{ 'false' }}",
+            server.replace("'", "''"),
+            printer.replace("'", "''")
+        );
+        run_powershell(&script)
+            .map(|stdout| stdout.trim().eq_ignore_ascii_case("true"))
+            .unwrap_or(false)
+    };
+
+    Ok(PrinterConnectionTest {
+        server,
+        printer,
+        reachable,
+        printer_found,
+        latency_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unc_printer() {
+        let (server, printer) = parse_unc_printer(r"\\printsrv\HP LaserJet").unwrap();
+        assert_eq!(server, "printsrv");
+        assert_eq!(printer, "HP LaserJet");
+
+        assert!(parse_unc_printer(r"\\printsrv").is_err());
+        assert!(parse_unc_printer("").is_err());
+    }
+}
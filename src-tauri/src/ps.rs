@@ -0,0 +1,409 @@
+//! PowerShell and console program execution helpers
+//! Probes PowerShell availability once and reports missing/blocked PowerShell
+//! as a distinct error instead of a generic spawn failure. Every process is
+//! started without a console window.
+
+use lazy_static::lazy_static;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{ErrorKind, Write};
+use std::os::windows::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use crate::error::AppError;
+
+pub(crate) const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Result of the PowerShell availability probe
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PowerShellStatus {
+    pub available: bool,
+    pub version: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Which PowerShell executable to use
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PowerShellPreference {
+    /// Prefer `pwsh` (PowerShell 7) and fall back to Windows PowerShell
+    Auto,
+    /// Only use `pwsh`
+    Pwsh,
+    /// Only use Windows PowerShell (`powershell.exe`)
+    #[serde(rename = "powershell")]
+    WindowsPowerShell,
+}
+
+/// PowerShell edition in use
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PowerShellEdition {
+    pub executable: Option<String>,
+    /// "Core" for pwsh, "Desktop" for Windows PowerShell
+    pub edition: Option<String>,
+    pub version: Option<String>,
+    pub preference: PowerShellPreference,
+}
+
+/// Probe result together with the executable it was obtained from
+#[derive(Debug, Clone)]
+struct ResolvedShell {
+    executable: &'static str,
+    status: PowerShellStatus,
+}
+
+/// Lowercased stderr fragments that mean a cmdlet was refused for lack of elevation
+/// (CIM cmdlets report "Access denied" with HRESULT 0x80070005, .NET APIs
+/// throw UnauthorizedAccessException)
+const ACCESS_DENIED_MARKERS: &[&str] = &[
+    "access is denied",
+    "access denied",
+    "unauthorizedaccess",
+    "permissiondenied",
+    "0x80070005",
+    "requires elevation",
+    "administrator privileges",
+];
+
+/// Whether a failed script's stderr says it needs elevation
+fn is_access_denied(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    ACCESS_DENIED_MARKERS.iter().any(|marker| stderr.contains(marker))
+}
+
+/// Prepended to every script so both editions emit JSON the same way
+/// (`ConvertTo-Json` truncates at depth 2 by default; pwsh also warns on stderr)
+const SCRIPT_PRELUDE: &str = "$PSDefaultParameterValues['ConvertTo-Json:Depth'] = 5; $ProgressPreference = 'SilentlyContinue'; ";
+
+/// Errors from the PowerShell helper
+#[derive(Debug, Clone)]
+pub enum PsError {
+    /// PowerShell is missing or blocked by policy
+    PowerShellUnavailable(String),
+    /// PowerShell ran but the script failed
+    Failed(String),
+    /// The script failed because the process isn't elevated
+    AccessDenied(String),
+}
+
+impl fmt::Display for PsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PsError::PowerShellUnavailable(reason) => {
+                write!(f, "PowerShellUnavailable: PowerShell is not available on this system ({})", reason)
+            }
+            PsError::Failed(stderr) => write!(f, "PowerShell error: {}", stderr),
+            PsError::AccessDenied(stderr) => write!(f, "Access denied: {}", stderr),
+        }
+    }
+}
+
+impl From<PsError> for String {
+    fn from(e: PsError) -> Self {
+        e.to_string()
+    }
+}
+
+lazy_static! {
+    /// Cached probe result, filled on first use
+    static ref PS_STATUS: Mutex<Option<ResolvedShell>> = Mutex::new(None);
+
+    /// User override for which PowerShell to run
+    static ref PS_PREFERENCE: Mutex<PowerShellPreference> = Mutex::new(PowerShellPreference::Auto);
+}
+
+fn probe_powershell(executable: &str) -> PowerShellStatus {
+    let output = Command::new(executable)
+        .creation_flags(CREATE_NO_WINDOW)
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "$PSVersionTable.PSVersion.ToString()",
+        ])
+        .output();
+
+    match output {
+        Ok(o) => {
+            let version = String::from_utf8_lossy(&o.stdout).trim().to_string();
+            if o.status.success() && !version.is_empty() {
+                info!("{} {} available", executable, version);
+                PowerShellStatus {
+                    available: true,
+                    version: Some(version),
+                    error: None,
+                }
+            } else {
+                // Runs but refuses to execute commands (e.g. blocked by AppLocker/policy)
+                let stderr = String::from_utf8_lossy(&o.stderr).trim().to_string();
+                warn!("{} present but not usable: {}", executable, stderr);
+                PowerShellStatus {
+                    available: false,
+                    version: None,
+                    error: Some(if stderr.is_empty() {
+                        "PowerShell is blocked by policy".to_string()
+                    } else {
+                        stderr
+                    }),
+                }
+            }
+        }
+        Err(e) => {
+            warn!("{} could not be started: {}", executable, e);
+            PowerShellStatus {
+                available: false,
+                version: None,
+                error: Some(e.to_string()),
+            }
+        }
+    }
+}
+
+fn preference() -> PowerShellPreference {
+    *PS_PREFERENCE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Pick the first usable executable allowed by the current preference
+fn resolve_shell() -> ResolvedShell {
+    let candidates: &[&'static str] = match preference() {
+        PowerShellPreference::Auto => &["pwsh", "powershell"],
+        PowerShellPreference::Pwsh => &["pwsh"],
+        PowerShellPreference::WindowsPowerShell => &["powershell"],
+    };
+
+    let mut last = None;
+    for &executable in candidates {
+        let status = probe_powershell(executable);
+        if status.available {
+            return ResolvedShell { executable, status };
+        }
+        last = Some(ResolvedShell { executable, status });
+    }
+    last.expect("at least one PowerShell candidate")
+}
+
+fn resolved_shell() -> ResolvedShell {
+    let mut cached = PS_STATUS.lock().unwrap_or_else(|e| e.into_inner());
+    cached.get_or_insert_with(resolve_shell).clone()
+}
+
+/// Check whether PowerShell can be used (probed once, then cached)
+#[tauri::command]
+pub fn check_powershell_available() -> PowerShellStatus {
+    resolved_shell().status
+}
+
+/// Report which PowerShell edition commands run under
+#[tauri::command]
+pub fn get_powershell_edition() -> PowerShellEdition {
+    let shell = resolved_shell();
+    let available = shell.status.available;
+    PowerShellEdition {
+        executable: available.then(|| shell.executable.to_string()),
+        edition: available.then(|| {
+            if shell.executable == "pwsh" { "Core" } else { "Desktop" }.to_string()
+        }),
+        version: shell.status.version,
+        preference: preference(),
+    }
+}
+
+/// Override which PowerShell to use; the next command re-probes
+#[tauri::command]
+pub fn set_powershell_preference(preference: PowerShellPreference) -> PowerShellEdition {
+    *PS_PREFERENCE.lock().unwrap_or_else(|e| e.into_inner()) = preference;
+    *PS_STATUS.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    info!("PowerShell preference set to {:?}", preference);
+    get_powershell_edition()
+}
+
+fn mark_unavailable(reason: &str) {
+    let mut cached = PS_STATUS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(shell) = cached.as_mut() {
+        shell.status = PowerShellStatus {
+            available: false,
+            version: None,
+            error: Some(reason.to_string()),
+        };
+    }
+}
+
+/// Run a PowerShell script and return its stdout
+///
+/// Uses `pwsh` when available (unless overridden) since it starts faster.
+/// Fails fast with `PsError::PowerShellUnavailable` once PowerShell is known
+/// to be missing, so callers can degrade to native-only features.
+pub fn run_powershell(script: &str) -> Result<String, PsError> {
+    run_powershell_inner(script, None)
+}
+
+/// Run a PowerShell script with `input` written to its stdin, for secrets that
+/// must not appear on the command line; the script reads it with `[Console]::In.ReadLine()`
+pub fn run_powershell_with_stdin(script: &str, input: &str) -> Result<String, PsError> {
+    run_powershell_inner(script, Some(input))
+}
+
+fn run_powershell_inner(script: &str, input: Option<&str>) -> Result<String, PsError> {
+    let shell = resolved_shell();
+    if !shell.status.available {
+        return Err(PsError::PowerShellUnavailable(
+            shell.status.error.unwrap_or_else(|| "unknown reason".to_string()),
+        ));
+    }
+
+    let script = format!("{}{}", SCRIPT_PRELUDE, script);
+    let mut command = Command::new(shell.executable);
+    command
+        .creation_flags(CREATE_NO_WINDOW)
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+    let spawn_error = |e: std::io::Error| match e.kind() {
+        ErrorKind::NotFound | ErrorKind::PermissionDenied => {
+            mark_unavailable(&e.to_string());
+            PsError::PowerShellUnavailable(e.to_string())
+        }
+        _ => PsError::Failed(format!("Failed to execute PowerShell: {}", e)),
+    };
+
+    let output = match input {
+        None => command.output().map_err(spawn_error)?,
+        Some(input) => {
+            let mut child = command
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .map_err(spawn_error)?;
+            // Dropping stdin after the write closes the pipe so the script sees EOF
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin
+                    .write_all(format!("{}\n", input).as_bytes())
+                    .map_err(|e| PsError::Failed(format!("Failed to write to PowerShell: {}", e)))?;
+            }
+            child
+                .wait_with_output()
+                .map_err(|e| PsError::Failed(format!("Failed to execute PowerShell: {}", e)))?
+        }
+    };
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if is_access_denied(&stderr) {
+            Err(PsError::AccessDenied(stderr))
+        } else {
+            Err(PsError::Failed(stderr))
+        }
+    }
+}
+
+/// Run a script that changes system state, naming `operation` (e.g. "enable
+/// Ethernet") in the error: an access-denied failure becomes `AppError::NotElevated`,
+/// any other failure reads "Failed to <operation>: ..." with the original stderr
+pub fn run_privileged(operation: &str, script: &str) -> Result<String, AppError> {
+    match run_powershell(script) {
+        Ok(stdout) => Ok(stdout),
+        Err(PsError::AccessDenied(_)) => Err(AppError::not_elevated(operation)),
+        Err(PsError::Failed(stderr)) => Err(AppError::CommandFailed {
+            stderr: format!("Failed to {}: {}", operation, stderr),
+        }),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Captured output of a console program
+#[derive(Debug, Clone)]
+pub struct CmdOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CmdOutput {
+    /// stdout on success; otherwise stderr (or stdout when stderr is empty)
+    /// since many tools print their errors to stdout
+    pub fn into_result(self) -> Result<String, String> {
+        if self.success {
+            Ok(self.stdout)
+        } else if self.stderr.trim().is_empty() {
+            Err(self.stdout.trim().to_string())
+        } else {
+            Err(self.stderr.trim().to_string())
+        }
+    }
+}
+
+/// Run a console program and capture its output
+///
+/// Only fails when the program can't be started; check `success` for the
+/// exit status.
+pub fn run_cmd(program: &str, args: &[&str]) -> Result<CmdOutput, String> {
+    let output = Command::new(program)
+        .creation_flags(CREATE_NO_WINDOW)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    Ok(CmdOutput {
+        success: output.status.success(),
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+    })
+}
+
+/// Run a console program that changes system state, mapping failures like `run_privileged`
+pub fn run_cmd_privileged(operation: &str, program: &str, args: &[&str]) -> Result<String, AppError> {
+    match run_cmd(program, args)?.into_result() {
+        Ok(stdout) => Ok(stdout),
+        Err(message) if is_access_denied(&message) => Err(AppError::not_elevated(operation)),
+        Err(message) => Err(AppError::CommandFailed {
+            stderr: format!("Failed to {}: {}", operation, message),
+        }),
+    }
+}
+
+/// Start a program without waiting for it (e.g. a Control Panel page)
+pub fn spawn_cmd(program: &str, args: &[&str]) -> Result<(), String> {
+    Command::new(program)
+        .creation_flags(CREATE_NO_WINDOW)
+        .args(args)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to start {}: {}", program, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmd_output_into_result() {
+        let output = |success: bool, stdout: &str, stderr: &str| CmdOutput {
+            success,
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+        };
+        assert_eq!(output(true, "ok\r\n", "").into_result(), Ok("ok\r\n".to_string()));
+        assert_eq!(output(false, "out", " err \r\n").into_result(), Err("err".to_string()));
+        assert_eq!(output(false, "System error 5\r\n", "").into_result(), Err("System error 5".to_string()));
+    }
+
+    #[test]
+    fn test_is_access_denied() {
+        assert!(is_access_denied(
+            "Enable-NetAdapter : Access denied\r\n    + CategoryInfo          : PermissionDenied: (MSFT_NetAdapter) [Enable-NetAdapter], CimException"
+        ));
+        assert!(is_access_denied(
+            "Set-NetFirewallProfile : Access is denied. (Exception from HRESULT: 0x80070005)"
+        ));
+        assert!(is_access_denied(
+            "FullyQualifiedErrorId : UnauthorizedAccessException"
+        ));
+        assert!(!is_access_denied(
+            "New-NetIPAddress : The object already exists."
+        ));
+        assert!(!is_access_denied(""));
+    }
+}
@@ -0,0 +1,182 @@
+//! Network diagnostics report
+//! Gathers adapters, IP configs, firewall, SMB settings and `ipconfig /all`
+//! into one file a user can send to support
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::diagnostics::get_hostname;
+use crate::error::AppError;
+use crate::firewall::{get_firewall_status, FirewallStatus};
+use crate::network::{get_network_adapters, run_ipconfig, IPConfiguration, NetworkAdapter};
+use crate::network_unified::get_ip_configuration_unified;
+use crate::smb::{get_smb_settings, SMBSettings};
+
+/// Everything in an exported report; sections that couldn't be read are `None`
+/// and the reason is listed in `errors`
+#[derive(Serialize)]
+pub struct NetworkReport {
+    /// Unix timestamp (seconds)
+    pub generated_at: u64,
+    pub hostname: String,
+    pub adapters: Option<Vec<NetworkAdapter>>,
+    /// Keyed by adapter name
+    pub ip_configs: BTreeMap<String, IPConfiguration>,
+    pub firewall: Option<FirewallStatus>,
+    pub smb: Option<SMBSettings>,
+    pub ipconfig_all: Option<String>,
+    pub errors: Vec<String>,
+}
+
+/// Keep the value, or record why the section is missing
+fn section<T>(name: &str, result: Result<T, AppError>, errors: &mut Vec<String>) -> Option<T> {
+    result
+        .map_err(|e| errors.push(format!("{}: {}", name, e)))
+        .ok()
+}
+
+/// Result of a scoped collection thread, treating a panic as a failed section
+fn joined<T>(name: &str, result: std::thread::Result<Result<T, AppError>>) -> Result<T, AppError> {
+    result.unwrap_or_else(|_| Err(AppError::Other(format!("{} collection panicked", name))))
+}
+
+fn gather_report() -> NetworkReport {
+    let (hostname, adapters, firewall, smb, ipconfig_all) = std::thread::scope(|scope| {
+        let hostname = scope.spawn(get_hostname);
+        let firewall = scope.spawn(get_firewall_status);
+        let smb = scope.spawn(get_smb_settings);
+        let ipconfig_all = scope.spawn(|| run_ipconfig(true));
+        let adapters = get_network_adapters();
+
+        (
+            joined("hostname", hostname.join()),
+            adapters,
+            joined("firewall", firewall.join()),
+            joined("smb", smb.join()),
+            joined("ipconfig", ipconfig_all.join()),
+        )
+    });
+
+    let mut errors = Vec::new();
+    let hostname = section("hostname", hostname, &mut errors).unwrap_or_default();
+    let adapters = section("adapters", adapters, &mut errors);
+
+    // One config lookup per adapter, run in parallel
+    let ip_configs = std::thread::scope(|scope| {
+        let lookups: Vec<_> = adapters
+            .iter()
+            .flatten()
+            .map(|adapter| {
+                let name = adapter.name.clone();
+                (name.clone(), scope.spawn(move || get_ip_configuration_unified(name, Some(false))))
+            })
+            .collect();
+        lookups
+            .into_iter()
+            .filter_map(|(name, handle)| {
+                let label = format!("ip config ({})", name);
+                let result = joined(&label, handle.join());
+                section(&label, result, &mut errors).map(|config| (name, config))
+            })
+            .collect()
+    });
+
+    NetworkReport {
+        generated_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        hostname,
+        adapters,
+        ip_configs,
+        firewall: section("firewall", firewall, &mut errors),
+        smb: section("smb", smb, &mut errors),
+        ipconfig_all: section("ipconfig /all", ipconfig_all, &mut errors),
+        errors,
+    }
+}
+
+fn json_block<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+}
+
+/// Render the report as Markdown: a header, one JSON block per section and the raw ipconfig output
+fn render_markdown(report: &NetworkReport) -> Result<String, String> {
+    let mut out = format!(
+        "# Network Report: {}\n\nGenerated at (Unix time): {}\n",
+        report.hostname, report.generated_at
+    );
+
+    for (title, body) in [
+        ("Adapters", json_block(&report.adapters)?),
+        ("IP Configuration", json_block(&report.ip_configs)?),
+        ("Firewall", json_block(&report.firewall)?),
+        ("SMB Settings", json_block(&report.smb)?),
+    ] {
+        out.push_str(&format!("\n## {}\n\n```json\n{}\n```\n", title, body));
+    }
+
+    out.push_str(&format!(
+        "\n## ipconfig /all\n\n```\n{}\n```\n",
+        report.ipconfig_all.as_deref().unwrap_or("(unavailable)").trim()
+    ));
+
+    if !report.errors.is_empty() {
+        out.push_str("\n## Errors\n\n");
+        for error in &report.errors {
+            out.push_str(&format!("- {}\n", error));
+        }
+    }
+    Ok(out)
+}
+
+/// Write a network diagnostics report to `path` (Markdown for `.md`, JSON otherwise)
+/// and return the path written
+#[tauri::command]
+pub fn export_network_report(path: String) -> Result<String, AppError> {
+    let report = gather_report();
+
+    let markdown = Path::new(&path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+    let contents = if markdown {
+        render_markdown(&report)?
+    } else {
+        serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?
+    };
+
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown() {
+        let report = NetworkReport {
+            generated_at: 1_700_000_000,
+            hostname: "DESKTOP-1".to_string(),
+            adapters: Some(vec![]),
+            ip_configs: BTreeMap::new(),
+            firewall: Some(FirewallStatus {
+                domain: true,
+                private: true,
+                public: false,
+            }),
+            smb: None,
+            ipconfig_all: None,
+            errors: vec!["smb: access denied".to_string()],
+        };
+
+        let markdown = render_markdown(&report).unwrap();
+        assert!(markdown.starts_with("# Network Report: DESKTOP-1\n"));
+        assert!(markdown.contains("\"public\": false"));
+        assert!(markdown.contains("## SMB Settings\n\n```json\nnull\n```"));
+        assert!(markdown.contains("(unavailable)"));
+        assert!(markdown.ends_with("- smb: access denied\n"));
+    }
+}
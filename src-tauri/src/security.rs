@@ -0,0 +1,237 @@
+//! Security posture scoring
+//! Combines the SMB and firewall readers into a deterministic score and a
+//! checklist of findings, each pointing at the command that fixes it
+
+use serde::{Deserialize, Serialize};
+
+use crate::firewall::{default_inbound_actions, get_firewall_status, FirewallStatus};
+use crate::smb::get_smb_settings;
+
+/// Finding severity, ordered from most to least severe
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl Severity {
+    /// Points deducted from the score for one finding of this severity
+    fn penalty(self) -> u8 {
+        match self {
+            Severity::Critical => 30,
+            Severity::High => 20,
+            Severity::Medium => 10,
+            Severity::Low => 5,
+        }
+    }
+}
+
+/// A single security issue and how to fix it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SecurityFinding {
+    pub severity: Severity,
+    pub title: String,
+    pub recommendation: String,
+    /// Tauri command that remediates this finding, if one exists
+    pub remediation_command: Option<String>,
+}
+
+/// Overall score (0-100, higher is better) and findings
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SecurityPosture {
+    pub score: u8,
+    pub findings: Vec<SecurityFinding>,
+}
+
+/// Raw security state; `None` means the value couldn't be read and is skipped
+#[derive(Debug, Clone, Default)]
+pub struct PostureInputs {
+    pub smb1_enabled: Option<bool>,
+    pub guest_auth_enabled: Option<bool>,
+    pub client_signature_required: Option<bool>,
+    pub server_signature_required: Option<bool>,
+    pub firewall: Option<FirewallStatus>,
+    /// (profile, default inbound action)
+    pub default_inbound_actions: Vec<(String, String)>,
+}
+
+fn finding(severity: Severity, title: &str, recommendation: &str, command: Option<&str>) -> SecurityFinding {
+    SecurityFinding {
+        severity,
+        title: title.to_string(),
+        recommendation: recommendation.to_string(),
+        remediation_command: command.map(|c| c.to_string()),
+    }
+}
+
+/// Score the given state. Pure and deterministic so it can be unit-tested.
+pub fn evaluate_posture(inputs: &PostureInputs) -> SecurityPosture {
+    let mut findings = Vec::new();
+
+    if inputs.smb1_enabled == Some(true) {
+        findings.push(finding(
+            Severity::Critical,
+            "SMB1 protocol is enabled",
+            "Disable SMB1; it is obsolete and exploited by worms such as WannaCry",
+            Some("set_smb1_enabled"),
+        ));
+    }
+    if inputs.guest_auth_enabled == Some(true) {
+        findings.push(finding(
+            Severity::High,
+            "Insecure SMB guest logons are allowed",
+            "Disable guest authentication so shares require credentials",
+            Some("set_smb_guest_auth"),
+        ));
+    }
+    if inputs.client_signature_required == Some(false) {
+        findings.push(finding(
+            Severity::Medium,
+            "SMB client signing is not required",
+            "Require SMB client signing to prevent relay and tampering attacks",
+            Some("set_smb_client_signature"),
+        ));
+    }
+    if inputs.server_signature_required == Some(false) {
+        findings.push(finding(
+            Severity::Medium,
+            "SMB server signing is not required",
+            "Require SMB server signing to prevent relay and tampering attacks",
+            Some("set_smb_server_signature"),
+        ));
+    }
+
+    if let Some(firewall) = &inputs.firewall {
+        for (profile, enabled) in [
+            ("Domain", firewall.domain),
+            ("Private", firewall.private),
+            ("Public", firewall.public),
+        ] {
+            if !enabled {
+                findings.push(finding(
+                    Severity::High,
+                    &format!("{} firewall profile is disabled", profile),
+                    &format!("Enable the {} firewall profile", profile),
+                    Some("set_firewall_profile"),
+                ));
+            }
+        }
+    }
+
+    for (profile, action) in &inputs.default_inbound_actions {
+        if action.eq_ignore_ascii_case("allow") {
+            findings.push(finding(
+                Severity::High,
+                &format!("{} profile allows unsolicited inbound traffic", profile),
+                &format!("Set the {} profile's default inbound action to Block", profile),
+                None,
+            ));
+        }
+    }
+
+    // Most severe first; stable sort keeps the check order within a severity
+    findings.sort_by_key(|f| f.severity);
+
+    let penalty: u32 = findings.iter().map(|f| f.severity.penalty() as u32).sum();
+    let score = 100u32.saturating_sub(penalty) as u8;
+
+    SecurityPosture { score, findings }
+}
+
+/// Compute the current machine's security posture score
+#[tauri::command]
+pub fn get_security_posture() -> SecurityPosture {
+    let smb = get_smb_settings().ok();
+    let inputs = PostureInputs {
+        smb1_enabled: smb.as_ref().and_then(|s| s.smb1_enabled),
+        guest_auth_enabled: smb.as_ref().map(|s| s.guest_auth_enabled),
+        client_signature_required: smb.as_ref().map(|s| s.client_signature_required),
+        server_signature_required: smb.as_ref().map(|s| s.server_signature_required),
+        firewall: get_firewall_status().ok(),
+        default_inbound_actions: default_inbound_actions().unwrap_or_default(),
+    };
+
+    evaluate_posture(&inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hardened() -> PostureInputs {
+        PostureInputs {
+            smb1_enabled: Some(false),
+            guest_auth_enabled: Some(false),
+            client_signature_required: Some(true),
+            server_signature_required: Some(true),
+            firewall: Some(FirewallStatus {
+                domain: true,
+                private: true,
+                public: true,
+            }),
+            default_inbound_actions: vec![
+                ("Domain".to_string(), "NotConfigured".to_string()),
+                ("Private".to_string(), "Block".to_string()),
+                ("Public".to_string(), "Block".to_string()),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_hardened_machine_scores_100() {
+        let posture = evaluate_posture(&hardened());
+        assert_eq!(posture.score, 100);
+        assert!(posture.findings.is_empty());
+    }
+
+    #[test]
+    fn test_findings_sorted_and_scored() {
+        let mut inputs = hardened();
+        inputs.client_signature_required = Some(false);
+        inputs.smb1_enabled = Some(true);
+        inputs.firewall.as_mut().unwrap().public = false;
+
+        let posture = evaluate_posture(&inputs);
+        // 100 - 30 (critical) - 20 (high) - 10 (medium)
+        assert_eq!(posture.score, 40);
+        let severities: Vec<Severity> = posture.findings.iter().map(|f| f.severity).collect();
+        assert_eq!(severities, vec![Severity::Critical, Severity::High, Severity::Medium]);
+        assert_eq!(
+            posture.findings[0].remediation_command.as_deref(),
+            Some("set_smb1_enabled")
+        );
+        assert_eq!(
+            posture.findings[1].remediation_command.as_deref(),
+            Some("set_firewall_profile")
+        );
+    }
+
+    #[test]
+    fn test_unknown_values_are_skipped() {
+        let posture = evaluate_posture(&PostureInputs::default());
+        assert_eq!(posture.score, 100);
+        assert!(posture.findings.is_empty());
+    }
+
+    #[test]
+    fn test_score_floors_at_zero() {
+        let inputs = PostureInputs {
+            smb1_enabled: Some(true),
+            guest_auth_enabled: Some(true),
+            client_signature_required: Some(false),
+            server_signature_required: Some(false),
+            firewall: Some(FirewallStatus {
+                domain: false,
+                private: false,
+                public: false,
+            }),
+            default_inbound_actions: vec![("Public".to_string(), "Allow".to_string())],
+        };
+        let posture = evaluate_posture(&inputs);
+        assert_eq!(posture.score, 0);
+        assert_eq!(posture.findings.len(), 8);
+    }
+}
@@ -1,24 +1,79 @@
-use std::process::Command;
-use std::os::windows::process::CommandExt;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
 use winreg::enums::*;
 use winreg::RegKey;
 
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+use crate::error::AppError;
+use crate::ps::{run_cmd, run_powershell, run_powershell_with_stdin, run_privileged, spawn_cmd};
+
+/// SMB client and server settings
+const LANMAN_WORKSTATION_KEY: &str =
+    "SYSTEM\\CurrentControlSet\\Services\\LanmanWorkstation\\Parameters";
+const LANMAN_SERVER_KEY: &str = "SYSTEM\\CurrentControlSet\\Services\\LanmanServer\\Parameters";
+
+/// Administrative Template policy for insecure guest logons, which takes precedence
+/// over `AllowInsecureGuestAuth` in the workstation key above. The signing settings
+/// are Security Options that Group Policy writes straight into the Parameters keys,
+/// so they have no separate policy value to conflict with.
+const LANMAN_WORKSTATION_POLICY_KEY: &str =
+    "SOFTWARE\\Policies\\Microsoft\\Windows\\LanmanWorkstation";
+
+/// Default SMB port
+const SMB_PORT: u16 = 445;
+
+/// Default and maximum connect timeout for `test_smb_connection`
+const SMB_TEST_TIMEOUT_MS: u64 = 2000;
+const MAX_SMB_TEST_TIMEOUT_MS: u64 = 60_000;
+
+/// Timeout for each step of the SMB negotiate probe
+const SMB_PROBE_TIMEOUT_MS: u64 = 3000;
+
+/// Dialects offered by the negotiate probe (SMB 2.0.2 - 3.0.2)
+/// 3.1.1 is left out since offering it requires negotiate contexts
+const SMB_PROBE_DIALECTS: [u16; 4] = [0x0202, 0x0210, 0x0300, 0x0302];
+
+/// Per-attempt timeout when deciding whether a mapping's server is gone
+const DEAD_MAPPING_TIMEOUT_MS: u64 = 5000;
+
+/// A mapping is only treated as dead if every attempt fails
+const DEAD_MAPPING_ATTEMPTS: u32 = 3;
 
 #[derive(serde::Serialize)]
 pub struct SMBSettings {
     pub guest_auth_enabled: bool,
     pub client_signature_required: bool,
     pub server_signature_required: bool,
+    /// None when the server configuration can't be read
+    pub smb1_enabled: Option<bool>,
+    /// Group Policy enforces a value that differs from the local one shown here,
+    /// so local changes have no effect
+    pub policy_override: bool,
+}
+
+/// A policy value conflicts when it is set and differs from the local value
+fn policy_conflicts(policy: Option<u32>, local: u32) -> bool {
+    policy.is_some_and(|policy| policy != local)
+}
+
+/// Whether Group Policy enforces a guest logon setting other than the local one
+fn smb_policy_override(hklm: &RegKey, guest_auth: u32) -> bool {
+    let policy = hklm
+        .open_subkey(LANMAN_WORKSTATION_POLICY_KEY)
+        .and_then(|key| key.get_value::<u32, _>("AllowInsecureGuestAuth"))
+        .ok();
+    policy_conflicts(policy, guest_auth)
 }
 
 #[tauri::command]
-pub fn get_smb_settings() -> Result<SMBSettings, String> {
+pub fn get_smb_settings() -> Result<SMBSettings, AppError> {
     let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
     
     // Check Guest Auth
     let lanman_workstation = hklm
-        .open_subkey("SYSTEM\\CurrentControlSet\\Services\\LanmanWorkstation\\Parameters")
+        .open_subkey(LANMAN_WORKSTATION_KEY)
         .map_err(|e| e.to_string())?;
     let guest_auth: u32 = lanman_workstation
         .get_value("AllowInsecureGuestAuth")
@@ -31,7 +86,7 @@ pub fn get_smb_settings() -> Result<SMBSettings, String> {
 
     // Check Server Signature (RequireSecuritySignature)
     let lanman_server = hklm
-        .open_subkey("SYSTEM\\CurrentControlSet\\Services\\LanmanServer\\Parameters")
+        .open_subkey(LANMAN_SERVER_KEY)
         .map_err(|e| e.to_string())?;
     let server_signature: u32 = lanman_server
         .get_value("RequireSecuritySignature")
@@ -41,62 +96,110 @@ pub fn get_smb_settings() -> Result<SMBSettings, String> {
         guest_auth_enabled: guest_auth == 1,
         client_signature_required: client_signature == 1,
         server_signature_required: server_signature == 1,
+        smb1_enabled: smb1_enabled(),
+        policy_override: smb_policy_override(&hklm, guest_auth),
     })
 }
 
-#[tauri::command]
-pub fn set_smb_guest_auth(enabled: bool) -> Result<String, String> {
-    let value = if enabled { 1u32 } else { 0u32 };
-    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-    let key = hklm
-        .open_subkey_with_flags(
-            "SYSTEM\\CurrentControlSet\\Services\\LanmanWorkstation\\Parameters",
-            KEY_WRITE,
-        )
-        .map_err(|e| format!("Failed to open registry key: {}", e))?;
+/// Read whether the SMB1 protocol is enabled on the server side
+/// Returns None when the configuration can't be read
+fn smb1_enabled() -> Option<bool> {
+    run_powershell("(Get-SmbServerConfiguration).EnableSMB1Protocol")
+        .ok()
+        .and_then(|stdout| match stdout.trim().to_lowercase().as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        })
+}
 
-    key.set_value("AllowInsecureGuestAuth", &value)
-        .map_err(|e| format!("Failed to write registry value: {}", e))?;
+/// Whether the SMB1 protocol is enabled on the server side
+#[tauri::command]
+pub fn is_smb1_enabled() -> Result<bool, AppError> {
+    smb1_enabled()
+        .ok_or_else(|| AppError::Other("Failed to read the SMB server configuration".to_string()))
+}
 
-    Ok(format!("Guest Auth set to {}", enabled))
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct Smb1Update {
+    pub enabled: bool,
+    /// Running sessions keep their protocol until LanmanServer restarts (or a reboot)
+    pub restart_required: bool,
 }
 
+/// Enable or disable the SMB1 server protocol
+/// Requires administrator privileges
 #[tauri::command]
-pub fn set_smb_client_signature(enabled: bool) -> Result<String, String> {
+pub fn set_smb1_enabled(enabled: bool) -> Result<Smb1Update, AppError> {
+    let script = format!(
+        "Set-SmbServerConfiguration -EnableSMB1Protocol {} -Force -ErrorAction Stop",
+        if enabled { "$true" } else { "$false" }
+    );
+    run_privileged("change SMB1", &script)?;
+
+    Ok(Smb1Update {
+        enabled,
+        restart_required: true,
+    })
+}
+
+/// Compare a registry value read back after a write against what was written
+fn verify_written(name: &str, attempted: u32, actual: Option<u32>) -> Result<(), String> {
+    match actual {
+        Some(actual) if actual == attempted => Ok(()),
+        Some(actual) => Err(format!(
+            "{} did not take effect: attempted {}, registry holds {} (a policy may be overriding it)",
+            name, attempted, actual
+        )),
+        None => Err(format!(
+            "{} did not take effect: attempted {}, value is missing after the write",
+            name, attempted
+        )),
+    }
+}
+
+/// Write a 0/1 SMB registry flag and read it back to confirm it stuck
+fn write_smb_flag(subkey: &str, name: &str, enabled: bool) -> Result<(), AppError> {
     let value = if enabled { 1u32 } else { 0u32 };
     let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
     let key = hklm
-        .open_subkey_with_flags(
-            "SYSTEM\\CurrentControlSet\\Services\\LanmanWorkstation\\Parameters",
-            KEY_WRITE,
-        )
-        .map_err(|e| format!("Failed to open registry key: {}", e))?;
+        .open_subkey_with_flags(subkey, KEY_WRITE)
+        .map_err(|e| match e.kind() {
+            ErrorKind::PermissionDenied => AppError::not_elevated(&format!("change {}", name)),
+            _ => AppError::Other(format!("Failed to open registry key: {}", e)),
+        })?;
 
-    key.set_value("RequireSecuritySignature", &value)
+    key.set_value(name, &value)
         .map_err(|e| format!("Failed to write registry value: {}", e))?;
 
-    Ok(format!("Client Signature set to {}", enabled))
+    // Re-open so the read goes through a fresh handle rather than the one just written
+    let actual = hklm
+        .open_subkey(subkey)
+        .and_then(|key| key.get_value::<u32, _>(name))
+        .ok();
+    Ok(verify_written(name, value, actual)?)
 }
 
 #[tauri::command]
-pub fn set_smb_server_signature(enabled: bool) -> Result<String, String> {
-    let value = if enabled { 1u32 } else { 0u32 };
-    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-    let key = hklm
-        .open_subkey_with_flags(
-            "SYSTEM\\CurrentControlSet\\Services\\LanmanServer\\Parameters",
-            KEY_WRITE,
-        )
-        .map_err(|e| format!("Failed to open registry key: {}", e))?;
+pub fn set_smb_guest_auth(enabled: bool) -> Result<String, AppError> {
+    write_smb_flag(LANMAN_WORKSTATION_KEY, "AllowInsecureGuestAuth", enabled)?;
+    Ok(format!("Guest Auth set to {}", enabled))
+}
 
-    key.set_value("RequireSecuritySignature", &value)
-        .map_err(|e| format!("Failed to write registry value: {}", e))?;
+#[tauri::command]
+pub fn set_smb_client_signature(enabled: bool) -> Result<String, AppError> {
+    write_smb_flag(LANMAN_WORKSTATION_KEY, "RequireSecuritySignature", enabled)?;
+    Ok(format!("Client Signature set to {}", enabled))
+}
 
+#[tauri::command]
+pub fn set_smb_server_signature(enabled: bool) -> Result<String, AppError> {
+    write_smb_flag(LANMAN_SERVER_KEY, "RequireSecuritySignature", enabled)?;
     Ok(format!("Server Signature set to {}", enabled))
 }
 
 #[tauri::command]
-pub fn reset_smb_settings() -> Result<String, String> {
+pub fn reset_smb_settings() -> Result<String, AppError> {
     // 1. Disable Guest Auth (Secure default)
     set_smb_guest_auth(false)?;
     
@@ -110,23 +213,110 @@ pub fn reset_smb_settings() -> Result<String, String> {
 }
 
 #[tauri::command]
-pub fn restart_smb_service() -> Result<String, String> {
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args([
-            "-NoProfile",
-            "-Command",
-            "Restart-Service LanmanWorkstation -Force; Restart-Service LanmanServer -Force",
-        ])
-        .output()
-        .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
-
-    if output.status.success() {
-        Ok("SMB Services restarted successfully".to_string())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Failed to restart services: {}", stderr))
+pub fn restart_smb_service() -> Result<String, AppError> {
+    run_privileged(
+        "restart SMB services",
+        "Restart-Service LanmanWorkstation -Force; Restart-Service LanmanServer -Force",
+    )?;
+
+    Ok("SMB Services restarted successfully".to_string())
+}
+
+/// SMB session/durability settings relevant to NAS reliability
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct SmbDurabilitySettings {
+    /// Client: seconds before an unresponsive session is dropped
+    pub session_timeout_secs: u32,
+    /// Client: opportunistic locking
+    pub client_oplocks_enabled: bool,
+    /// Server: oplocks granted to clients
+    pub server_oplocks_enabled: bool,
+    /// Server: how long durable handles survive a disconnect
+    pub durable_handle_timeout_secs: u32,
+    /// Server: idle minutes before a session is auto-disconnected
+    pub auto_disconnect_minutes: u32,
+}
+
+/// Result of updating durability settings
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct SmbDurabilityUpdate {
+    pub effective: SmbDurabilitySettings,
+    /// Server-side oplock changes only apply after restarting LanmanServer
+    pub restart_required: bool,
+}
+
+#[tauri::command]
+pub fn get_smb_durability_settings() -> Result<SmbDurabilitySettings, AppError> {
+    let stdout = run_powershell(
+        r#"
+        $client = Get-SmbClientConfiguration
+        $server = Get-SmbServerConfiguration
+        @{
+            session_timeout_secs = [int]$client.SessionTimeout
+            client_oplocks_enabled = [bool]$client.UseOpportunisticLocking
+            server_oplocks_enabled = [bool]$server.EnableOplocks
+            durable_handle_timeout_secs = [int]$server.DurableHandleV2TimeoutInSeconds
+            auto_disconnect_minutes = [int]$server.AutoDisconnectTimeout
+        } | ConvertTo-Json -Compress
+        "#,
+    )?;
+
+    Ok(serde_json::from_str(stdout.trim())?)
+}
+
+/// Update SMB durability settings; only the provided values are changed
+#[tauri::command]
+pub fn set_smb_durability_settings(
+    session_timeout_secs: Option<u32>,
+    client_oplocks_enabled: Option<bool>,
+    server_oplocks_enabled: Option<bool>,
+    durable_handle_timeout_secs: Option<u32>,
+    auto_disconnect_minutes: Option<u32>,
+) -> Result<SmbDurabilityUpdate, AppError> {
+    let ps_bool = |b: bool| if b { "$true" } else { "$false" };
+    let mut client_args = Vec::new();
+    let mut server_args = Vec::new();
+
+    if let Some(secs) = session_timeout_secs {
+        if !(10..=3600).contains(&secs) {
+            return Err("Session timeout must be between 10 and 3600 seconds".into());
+        }
+        client_args.push(format!("-SessionTimeout {}", secs));
+    }
+    if let Some(enabled) = client_oplocks_enabled {
+        client_args.push(format!("-UseOpportunisticLocking {}", ps_bool(enabled)));
+    }
+    if let Some(enabled) = server_oplocks_enabled {
+        server_args.push(format!("-EnableOplocks {}", ps_bool(enabled)));
+    }
+    if let Some(secs) = durable_handle_timeout_secs {
+        if secs > 3600 {
+            return Err("Durable handle timeout must be at most 3600 seconds".into());
+        }
+        server_args.push(format!("-DurableHandleV2TimeoutInSeconds {}", secs));
+    }
+    if let Some(minutes) = auto_disconnect_minutes {
+        if minutes > 65535 {
+            return Err("Auto-disconnect timeout must be at most 65535 minutes".into());
+        }
+        server_args.push(format!("-AutoDisconnectTimeout {}", minutes));
+    }
+
+    let mut script = String::new();
+    if !client_args.is_empty() {
+        script.push_str(&format!("Set-SmbClientConfiguration {} -Force -ErrorAction Stop; ", client_args.join(" ")));
+    }
+    if !server_args.is_empty() {
+        script.push_str(&format!("Set-SmbServerConfiguration {} -Force -ErrorAction Stop; ", server_args.join(" ")));
+    }
+    if !script.is_empty() {
+        run_privileged("update SMB configuration", &script)?;
     }
+
+    Ok(SmbDurabilityUpdate {
+        effective: get_smb_durability_settings()?,
+        restart_required: server_oplocks_enabled.is_some(),
+    })
 }
 
 #[derive(serde::Serialize)]
@@ -136,189 +326,1242 @@ pub struct SmbShare {
     pub description: String,
 }
 
+#[derive(serde::Serialize)]
+pub struct SmbConnectionTest {
+    pub host: String,
+    pub port: u16,
+    pub latency_ms: u64,
+    pub message: String,
+}
+
+/// Connect to `host:port`, returning how long the successful connect took
+/// Every resolved address is tried; the last connect error is reported if all fail
+fn tcp_connect_latency(host: &str, port: u16, timeout: Duration) -> Result<Duration, String> {
+    let addrs: Vec<_> = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .collect();
+
+    let mut last_error = format!("No address found for {}", host);
+    for addr in addrs {
+        let started = Instant::now();
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(_) => return Ok(started.elapsed()),
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+    Err(last_error)
+}
+
+/// Test whether a host accepts SMB connections with a direct TCP connect
+/// (no ping pre-check, since many hosts block ICMP)
 #[tauri::command]
-pub fn test_smb_connection(host: String) -> Result<String, String> {
-    // Simple ping check first
-    let output = Command::new("ping")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-n", "1", "-w", "2000", &host])
-        .output()
-        .map_err(|e| format!("Failed to execute ping: {}", e))?;
-
-    if !output.status.success() {
-        return Err(format!("Host {} is unreachable", host));
-    }
-
-    // Check port 445 (SMB) using PowerShell
-    let cmd = format!(
-        "Test-NetConnection -ComputerName {} -Port 445 -InformationLevel Quiet",
-        host
-    );
-    
-    let output = Command::new("powershell")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["-NoProfile", "-Command", &cmd])
-        .output()
-        .map_err(|e| format!("Failed to execute PowerShell: {}", e))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    if stdout.trim().to_lowercase() == "true" {
-        Ok(format!("Successfully connected to SMB on {}", host))
+pub fn test_smb_connection(
+    host: String,
+    timeout_ms: Option<u64>,
+    port: Option<u16>,
+) -> Result<SmbConnectionTest, AppError> {
+    let port = port.unwrap_or(SMB_PORT);
+    if port == 0 {
+        return Err("Port must be between 1 and 65535".into());
+    }
+    let timeout_ms = timeout_ms.unwrap_or(SMB_TEST_TIMEOUT_MS);
+    if timeout_ms == 0 || timeout_ms > MAX_SMB_TEST_TIMEOUT_MS {
+        return Err(format!(
+            "Timeout must be between 1 and {} ms",
+            MAX_SMB_TEST_TIMEOUT_MS
+        )
+        .into());
+    }
+
+    let latency_ms = tcp_connect_latency(&host, port, Duration::from_millis(timeout_ms))
+        .map_err(|e| format!("SMB port ({}) on {} is unreachable: {}", port, host, e))?
+        .as_millis() as u64;
+
+    Ok(SmbConnectionTest {
+        message: format!(
+            "Successfully connected to SMB on {}:{} ({} ms)",
+            host, port, latency_ms
+        ),
+        host,
+        port,
+        latency_ms,
+    })
+}
+
+#[derive(serde::Serialize)]
+pub struct SmbSecurityProbe {
+    pub host: String,
+    pub dialect: String,
+    pub signing_required: bool,
+    pub encryption_supported: bool,
+}
+
+/// Build an SMB2 NEGOTIATE request wrapped in a NetBIOS session header
+fn build_smb2_negotiate() -> Vec<u8> {
+    let mut smb = Vec::with_capacity(64 + 36 + SMB_PROBE_DIALECTS.len() * 2);
+
+    // SMB2 header
+    smb.extend_from_slice(b"\xFESMB");
+    smb.extend_from_slice(&64u16.to_le_bytes()); // StructureSize
+    smb.extend_from_slice(&0u16.to_le_bytes()); // CreditCharge
+    smb.extend_from_slice(&0u32.to_le_bytes()); // Status
+    smb.extend_from_slice(&0u16.to_le_bytes()); // Command: NEGOTIATE
+    smb.extend_from_slice(&1u16.to_le_bytes()); // CreditRequest
+    smb.extend_from_slice(&0u32.to_le_bytes()); // Flags
+    smb.extend_from_slice(&0u32.to_le_bytes()); // NextCommand
+    smb.extend_from_slice(&0u64.to_le_bytes()); // MessageId
+    smb.extend_from_slice(&0u32.to_le_bytes()); // Reserved
+    smb.extend_from_slice(&0u32.to_le_bytes()); // TreeId
+    smb.extend_from_slice(&0u64.to_le_bytes()); // SessionId
+    smb.extend_from_slice(&[0u8; 16]); // Signature
+
+    // NEGOTIATE request body
+    smb.extend_from_slice(&36u16.to_le_bytes()); // StructureSize
+    smb.extend_from_slice(&(SMB_PROBE_DIALECTS.len() as u16).to_le_bytes());
+    smb.extend_from_slice(&1u16.to_le_bytes()); // SecurityMode: signing enabled
+    smb.extend_from_slice(&0u16.to_le_bytes()); // Reserved
+    smb.extend_from_slice(&0x40u32.to_le_bytes()); // Capabilities: encryption
+    smb.extend_from_slice(&[0x4Eu8; 16]); // ClientGuid
+    smb.extend_from_slice(&0u64.to_le_bytes()); // ClientStartTime
+    for dialect in SMB_PROBE_DIALECTS {
+        smb.extend_from_slice(&dialect.to_le_bytes());
+    }
+
+    let len = smb.len() as u32;
+    let mut packet = vec![0u8, (len >> 16) as u8, (len >> 8) as u8, len as u8];
+    packet.extend_from_slice(&smb);
+    packet
+}
+
+fn smb_dialect_name(dialect: u16) -> String {
+    match dialect {
+        0x0202 => "2.0.2".to_string(),
+        0x0210 => "2.1".to_string(),
+        0x0300 => "3.0".to_string(),
+        0x0302 => "3.0.2".to_string(),
+        0x0311 => "3.1.1".to_string(),
+        other => format!("0x{:04X}", other),
+    }
+}
+
+/// Parse an SMB2 NEGOTIATE response (without the NetBIOS header)
+/// into (dialect, signing_required, encryption_supported)
+fn parse_smb2_negotiate_response(smb: &[u8]) -> Result<(u16, bool, bool), String> {
+    let u16_at = |i: usize| u16::from_le_bytes([smb[i], smb[i + 1]]);
+    let u32_at = |i: usize| u32::from_le_bytes([smb[i], smb[i + 1], smb[i + 2], smb[i + 3]]);
+
+    if smb.len() < 4 || &smb[..4] != b"\xFESMB" {
+        if smb.starts_with(b"\xFFSMB") {
+            return Err("Server only speaks SMB1".to_string());
+        }
+        return Err("Response is not SMB2".to_string());
+    }
+    if smb.len() < 64 + 28 {
+        return Err("Truncated SMB2 negotiate response".to_string());
+    }
+
+    let status = u32_at(8);
+    if status != 0 {
+        return Err(format!("Server rejected negotiate (status 0x{:08X})", status));
+    }
+
+    let body = 64;
+    let security_mode = u16_at(body + 2);
+    let dialect = u16_at(body + 4);
+    let capabilities = u32_at(body + 24);
+
+    Ok((dialect, security_mode & 0x02 != 0, capabilities & 0x40 != 0))
+}
+
+/// Negotiate SMB2 with a host to read its dialect and signing/encryption requirements
+#[tauri::command]
+pub fn probe_smb_security(host: String) -> Result<SmbSecurityProbe, AppError> {
+    let timeout = Duration::from_millis(SMB_PROBE_TIMEOUT_MS);
+    let addr = (host.as_str(), SMB_PORT)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("No address found for {}", host))?;
+
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)
+        .map_err(|_| format!("SMB port (445) on {} is unreachable", host))?;
+    stream.set_read_timeout(Some(timeout)).ok();
+    stream.set_write_timeout(Some(timeout)).ok();
+
+    stream
+        .write_all(&build_smb2_negotiate())
+        .map_err(|e| format!("Failed to send SMB negotiate: {}", e))?;
+
+    let mut header = [0u8; 4];
+    stream
+        .read_exact(&mut header)
+        .map_err(|e| format!("No SMB response from {}: {}", host, e))?;
+    let len = ((header[1] as usize) << 16) | ((header[2] as usize) << 8) | header[3] as usize;
+    let mut smb = vec![0u8; len.min(64 * 1024)];
+    stream
+        .read_exact(&mut smb)
+        .map_err(|e| format!("Incomplete SMB response from {}: {}", host, e))?;
+
+    let (dialect, signing_required, encryption_supported) = parse_smb2_negotiate_response(&smb)?;
+
+    Ok(SmbSecurityProbe {
+        host,
+        dialect: smb_dialect_name(dialect),
+        signing_required,
+        encryption_supported,
+    })
+}
+
+/// Active SMB connection from this machine to a share
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct SmbConnectionInfo {
+    pub share: String,
+    /// Human-readable protocol version, e.g. "SMB 3.1.1"
+    pub dialect: String,
+    pub signed: bool,
+    pub encrypted: bool,
+}
+
+/// Turn `Get-SmbConnection`'s dialect ("1.5", "2.1", "3.1.1") into "SMB 1" / "SMB 2.1" / ...
+fn smb_connection_dialect_name(raw: &str) -> String {
+    let raw = raw.trim();
+    if raw.starts_with('1') {
+        "SMB 1".to_string()
+    } else if raw.is_empty() {
+        "Unknown".to_string()
     } else {
-        Err(format!("SMB port (445) on {} is closed or blocked", host))
+        format!("SMB {}", raw)
     }
 }
 
+/// List active SMB connections to `host`; empty if there are none
 #[tauri::command]
-pub fn list_smb_shares(host: String, _username: Option<String>, _password: Option<String>) -> Result<Vec<SmbShare>, String> {
-    // Note: net view doesn't support credentials directly in args
-    // For authenticated access, use 'net use' first to establish connection
-    // TODO: Implement credential handling via net use before net view
-
-    // Use net view
-    let output = Command::new("net")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["view", &format!("\\\\{}", host)])
-        .output()
-        .map_err(|e| format!("Failed to execute net view: {}", e))?;
-
-    // Note: 'net view' output parsing is tricky and locale-dependent.
-    // For a robust solution, we might need WMI or PowerShell Get-SmbShare (but that works locally).
-    // For remote listing without domain, 'net view' is standard but requires parsing.
-    
-    // Alternative: PowerShell Get-SmbShare -CimSession (requires WinRM)
-    // Fallback: Just return raw output for now or simple parsing
-    
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    if !output.status.success() {
-        return Err(stdout.to_string());
+pub fn get_smb_connection_info(host: String) -> Result<Vec<SmbConnectionInfo>, AppError> {
+    let script = format!(
+        r#"
+        $connections = @(Get-SmbConnection -ServerName '{}' -ErrorAction SilentlyContinue | ForEach-Object {{
+            @{{ share = "$($_.ShareName)"; dialect = "$($_.Dialect)"; signed = [bool]$_.Signed; encrypted = [bool]$_.Encrypted }}
+        }})
+        ConvertTo-Json -InputObject $connections -Compress
+        "#,
+        host.replace("'", "''")
+    );
+
+    let stdout = run_powershell(&script)?;
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut connections: Vec<SmbConnectionInfo> =
+        serde_json::from_str(trimmed).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    for connection in &mut connections {
+        connection.dialect = smb_connection_dialect_name(&connection.dialect);
+    }
+    Ok(connections)
+}
+
+/// `net` system error codes that mean the server rejected the credentials
+const NET_ACCESS_DENIED_CODES: [u32; 6] = [5, 86, 1326, 1327, 1331, 1909];
+
+/// `net` system error codes that mean the server couldn't be reached
+const NET_UNREACHABLE_CODES: [u32; 5] = [51, 53, 64, 67, 1231];
+
+/// Run `net` with the given arguments, returning success and the combined output
+fn run_net(args: &[&str]) -> Result<(bool, String), String> {
+    let output = run_cmd("net", args)?;
+    Ok((output.success, output.stdout + &output.stderr))
+}
+
+/// System error code from `net` output such as "System error 5 has occurred."
+/// The message is localized, but the code is always the first number
+fn net_error_code(output: &str) -> Option<u32> {
+    output
+        .split(|c: char| !c.is_ascii_digit())
+        .find(|part| !part.is_empty())
+        .and_then(|code| code.parse().ok())
+}
+
+/// Turn a failed `net` call into an error that says whether access was denied or the host is unreachable
+fn describe_net_error(host: &str, output: &str) -> String {
+    let detail = output.trim();
+    match net_error_code(output) {
+        Some(code) if NET_ACCESS_DENIED_CODES.contains(&code) => {
+            format!("Access denied by {}: {}", host, detail)
+        }
+        Some(code) if NET_UNREACHABLE_CODES.contains(&code) => {
+            format!("Host {} is unreachable: {}", host, detail)
+        }
+        _ => detail.to_string(),
     }
+}
+
+/// Whether `net use` output lists a connection to `\\host` itself or one of its shares
+fn net_use_has_host(output: &str, host: &str) -> bool {
+    let target = format!("\\\\{}", host).to_lowercase();
+    let share_prefix = format!("{}\\", target);
+    output.split_whitespace().any(|field| {
+        let field = field.to_lowercase();
+        field == target || field.starts_with(&share_prefix)
+    })
+}
+
+/// Authenticated `\\host\IPC$` session. A session this created is deleted on
+/// drop so a failed enumeration never leaves it behind; one the user already had
+/// is reused and left alone.
+struct NetSession {
+    target: String,
+    created: bool,
+}
+
+impl NetSession {
+    fn open(host: &str, username: &str, password: &str) -> Result<Self, String> {
+        use windows::core::{HSTRING, PWSTR};
+        use windows::Win32::Foundation::NO_ERROR;
+        use windows::Win32::NetworkManagement::WNet::{
+            WNetAddConnection2W, NETRESOURCEW, NET_CONNECT_FLAGS, RESOURCETYPE_ANY,
+        };
+
+        let target = format!("\\\\{}\\IPC$", host);
+        let (listed, existing) = run_net(&["use"])?;
+        if listed && net_use_has_host(&existing, host) {
+            return Ok(Self { target, created: false });
+        }
 
-    // Parse net view output - format is column-based:
-    // "Share name       Type    Used as  Comment"
-    // "ShareName        Disk             Description here"
-    // The share name column is typically up to the "Type" column (Disk/Print/IPC)
+        let mut remote: Vec<u16> = target.encode_utf16().chain(Some(0)).collect();
+        let resource = NETRESOURCEW {
+            dwType: RESOURCETYPE_ANY,
+            lpRemoteName: PWSTR(remote.as_mut_ptr()),
+            ..Default::default()
+        };
+        // Credentials go straight to the API so they never appear on a command line
+        let (username, password) = (HSTRING::from(username), HSTRING::from(password));
+
+        // SAFETY: `resource` points into `remote`, and the credential strings are
+        // owned by `username`/`password`; all outlive the call
+        let result = unsafe { WNetAddConnection2W(&resource, &password, &username, NET_CONNECT_FLAGS(0)) };
+        if result != NO_ERROR {
+            return Err(wnet_failure(result.0, &target, Some(&target)));
+        }
+        Ok(Self { target, created: true })
+    }
+}
+
+impl Drop for NetSession {
+    fn drop(&mut self) {
+        use windows::core::HSTRING;
+        use windows::Win32::Foundation::BOOL;
+        use windows::Win32::NetworkManagement::WNet::{WNetCancelConnection2W, NET_CONNECT_FLAGS};
+
+        if self.created {
+            // SAFETY: the name is a valid, null-terminated wide string for the duration of the call
+            let _ = unsafe {
+                WNetCancelConnection2W(&HSTRING::from(self.target.as_str()), NET_CONNECT_FLAGS(0), BOOL::from(true))
+            };
+        }
+    }
+}
+
+/// Enumerate shares with `Get-SmbShare` over a CIM session (requires WinRM on the host)
+fn list_smb_shares_cim(host: &str, username: &str, password: &str) -> Result<Vec<SmbShare>, String> {
+    #[derive(serde::Deserialize)]
+    struct CimShare {
+        name: String,
+        description: String,
+    }
+
+    // The password arrives on stdin so it never appears in the script text
+    let script = format!(
+        r#"
+        $ErrorActionPreference = 'Stop'
+        $secure = ConvertTo-SecureString ([Console]::In.ReadLine()) -AsPlainText -Force
+        $cred = New-Object System.Management.Automation.PSCredential('{username}', $secure)
+        $session = New-CimSession -ComputerName '{host}' -Credential $cred -OperationTimeoutSec 5
+        try {{
+            $shares = @(Get-SmbShare -CimSession $session | Where-Object {{ -not $_.Special }} | ForEach-Object {{
+                @{{ name = $_.Name; description = "$($_.Description)" }}
+            }})
+            ConvertTo-Json -InputObject $shares -Compress
+        }} finally {{
+            Remove-CimSession $session
+        }}
+        "#,
+        username = username.replace("'", "''"),
+        host = host.replace("'", "''"),
+    );
+
+    let stdout = run_powershell_with_stdin(&script, password)?;
+    let shares: Vec<CimShare> =
+        serde_json::from_str(stdout.trim()).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+    Ok(shares
+        .into_iter()
+        .map(|share| SmbShare {
+            path: format!("\\\\{}\\{}", host, share.name),
+            name: share.name,
+            description: share.description,
+        })
+        .collect())
+}
+
+/// Split a `net view` row into its columns, which are separated by runs of 2+ spaces
+fn net_view_columns(line: &str) -> Vec<&str> {
+    line.split("  ")
+        .map(str::trim)
+        .filter(|column| !column.is_empty())
+        .collect()
+}
+
+/// A drive letter like `Z:` in the "Used as" column
+fn is_drive_column(column: &str) -> bool {
+    let bytes = column.as_bytes();
+    bytes.len() == 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+/// Parse the column-based output of `net view \\host`
+///
+/// Headers, type names ("Disk", "Platte", ...) and the completion message are
+/// localized, so rows are found structurally: data starts after the dashed
+/// separator line and every share row has at least a name and a type column.
+/// The completion message is a single sentence without column gaps and is skipped.
+fn parse_net_view(host: &str, stdout: &str) -> Vec<SmbShare> {
     let mut shares = Vec::new();
     let mut found_separator = false;
-    
+
     for line in stdout.lines() {
         let trimmed = line.trim();
-        
-        // Skip empty lines and header lines
-        if trimmed.is_empty() ||
-           trimmed.starts_with("Shared resources") ||
-           trimmed.starts_with("Share name") ||
-           trimmed.contains("command completed") {
+        if trimmed.is_empty() {
             continue;
         }
-        
+
         // Look for the separator line (------) to know data starts after it
-        if trimmed.starts_with("---") || trimmed.starts_with("───") {
+        if trimmed.chars().all(|c| c == '-' || c == '─') {
             found_separator = true;
             continue;
         }
-        
-        // Only parse lines after the separator
         if !found_separator {
             continue;
         }
-        
-        // net view output format: "ShareName       Disk           Comment text here"
-        // The "Disk" (or "Print", "IPC") keyword marks the type column
-        // Share name is everything before the type keyword
-        
-        // Find the type column (Disk, Print, IPC) which indicates end of share name
-        let type_patterns = ["  Disk", "  Print", "  IPC"];
-        let mut share_name = String::new();
-        let mut description = String::new();
-        
-        for pattern in &type_patterns {
-            if let Some(pos) = line.find(pattern) {
-                // Share name is everything before the type, trimmed
-                share_name = line[..pos].trim().to_string();
-                // Description is after the type (skip "Disk" + spaces)
-                let after_type = &line[pos + pattern.len()..];
-                description = after_type.trim().to_string();
-                break;
+
+        // Columns: share name, type, optional "used as" drive letter, comment
+        let columns = net_view_columns(trimmed);
+        if columns.len() < 2 {
+            continue;
+        }
+        let share_name = columns[0].to_string();
+        let description = columns[2..]
+            .iter()
+            .skip_while(|column| is_drive_column(column))
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        shares.push(SmbShare {
+            path: format!("\\\\{}\\{}", host, share_name),
+            name: share_name,
+            description,
+        });
+    }
+
+    shares
+}
+
+/// Host part of a target like `nas`, `\\nas` or `\\nas\share`, lowercased
+fn smb_credential_host(target: &str) -> Result<String, String> {
+    let host = target
+        .trim()
+        .trim_start_matches(['\\', '/'])
+        .split(['\\', '/'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    if host.is_empty() {
+        return Err(format!("Invalid SMB target: {}", target));
+    }
+    Ok(host)
+}
+
+/// Save a username and password for an SMB host in Windows Credential Manager
+/// Stored as a domain credential for the host (like `cmdkey /add`), so the SMB client
+/// uses it natively when `list_smb_shares` or `map_network_drive` get no password,
+/// and the password is never read back or passed to another process.
+#[tauri::command]
+pub fn save_smb_credential(target: String, username: String, password: String) -> Result<String, AppError> {
+    use windows::core::PWSTR;
+    use windows::Win32::Security::Credentials::{
+        CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE, CRED_TYPE_DOMAIN_PASSWORD,
+    };
+
+    let host = smb_credential_host(&target)?;
+    let username = username.trim();
+    if username.is_empty() {
+        return Err("Username is required".into());
+    }
+
+    let mut target_name: Vec<u16> = host.encode_utf16().chain(Some(0)).collect();
+    let mut user: Vec<u16> = username.encode_utf16().chain(Some(0)).collect();
+    let mut blob: Vec<u8> = password.encode_utf16().flat_map(u16::to_le_bytes).collect();
+
+    let credential = CREDENTIALW {
+        Type: CRED_TYPE_DOMAIN_PASSWORD,
+        TargetName: PWSTR(target_name.as_mut_ptr()),
+        UserName: PWSTR(user.as_mut_ptr()),
+        CredentialBlobSize: blob.len() as u32,
+        CredentialBlob: blob.as_mut_ptr(),
+        Persist: CRED_PERSIST_LOCAL_MACHINE,
+        ..Default::default()
+    };
+
+    // SAFETY: every pointer in `credential` borrows a buffer that outlives the call
+    unsafe { CredWriteW(&credential, 0) }
+        .map_err(|e| format!("Failed to save credential for {}: {}", host, e.message()))?;
+
+    Ok(format!("Saved credential for {}", host))
+}
+
+/// Remove the saved credential of an SMB host
+#[tauri::command]
+pub fn delete_smb_credential(target: String) -> Result<String, AppError> {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::ERROR_NOT_FOUND;
+    use windows::Win32::Security::Credentials::{CredDeleteW, CRED_TYPE_DOMAIN_PASSWORD};
+
+    let host = smb_credential_host(&target)?;
+    let name = HSTRING::from(host.as_str());
+
+    // SAFETY: `name` is a valid, null-terminated wide string for the duration of the call
+    match unsafe { CredDeleteW(&name, CRED_TYPE_DOMAIN_PASSWORD, 0) } {
+        Ok(()) => Ok(format!("Deleted credential for {}", host)),
+        Err(e) if e.code() == ERROR_NOT_FOUND.to_hresult() => {
+            Err(AppError::NotFound(format!("No saved credential for {}", host)))
+        }
+        Err(e) => Err(format!("Failed to delete credential for {}: {}", host, e.message()).into()),
+    }
+}
+
+/// List shares on a host. With a username and password, tries `Get-SmbShare` over CIM
+/// first, then falls back to `net view` inside an authenticated `IPC$` session.
+/// Otherwise Windows applies any credential saved with `save_smb_credential`.
+#[tauri::command]
+pub fn list_smb_shares(
+    host: String,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<Vec<SmbShare>, AppError> {
+    if !smb_port_reachable(&host, Duration::from_millis(SMB_PROBE_TIMEOUT_MS)) {
+        return Err(format!(
+            "Host {} is unreachable: SMB port (445) did not respond",
+            host
+        )
+        .into());
+    }
+
+    let username = username.filter(|u| !u.is_empty());
+    let password = password.filter(|p| !p.is_empty());
+    // Held until enumeration finishes; dropping it deletes a session it opened on every path
+    let _session = match username.zip(password) {
+        Some((username, password)) => {
+            if let Ok(shares) = list_smb_shares_cim(&host, &username, &password) {
+                return Ok(shares);
             }
+            Some(NetSession::open(&host, &username, &password)?)
         }
-        
-        // If no type pattern found, try splitting by multiple spaces (fallback)
-        if share_name.is_empty() && !trimmed.is_empty() {
-            // Use regex-like splitting: split by 2+ spaces
-            let parts: Vec<&str> = trimmed.split("  ")
-                .filter(|s| !s.is_empty())
-                .collect();
-            if !parts.is_empty() {
-                share_name = parts[0].trim().to_string();
-                if parts.len() > 2 {
-                    description = parts[2..].join(" ").trim().to_string();
-                }
+        None => None,
+    };
+
+    let (success, output) = run_net(&["view", &format!("\\\\{}", host)])?;
+    if !success {
+        return Err(describe_net_error(&host, &output).into());
+    }
+
+    Ok(parse_net_view(&host, &output))
+}
+
+/// Readable message for a WNet error code when connecting `local` to `remote`,
+/// or when disconnecting `local` if `remote` is `None`
+fn wnet_error_message(code: u32, local: &str, remote: Option<&str>) -> String {
+    let target = remote.unwrap_or(local);
+    match code {
+        // ERROR_LOGON_FAILURE
+        1326 => format!("The username or password for {} is incorrect", target),
+        // ERROR_BAD_NETPATH
+        53 => format!("Network path {} was not found; check the server name and that it's reachable", target),
+        // ERROR_BAD_NET_NAME
+        67 => format!("Share {} doesn't exist on the server", target),
+        // ERROR_ALREADY_ASSIGNED, ERROR_DEVICE_ALREADY_REMEMBERED
+        85 | 1202 => format!("{} is already mapped to another share", local),
+        // ERROR_SESSION_CREDENTIAL_CONFLICT
+        1219 => format!(
+            "Already connected to the server of {} with different credentials; disconnect those connections first",
+            target
+        ),
+        // ERROR_BAD_DEVICE
+        1200 => format!("Invalid drive letter: {}", local),
+        // ERROR_NOT_CONNECTED
+        2250 => format!("{} is not a network connection", local),
+        // ERROR_OPEN_FILES, ERROR_DEVICE_IN_USE
+        2401 | 2404 => format!("{} has open files; close them and try again", local),
+        // ERROR_NO_NETWORK
+        1222 => "The network is not available".to_string(),
+        code if NET_ACCESS_DENIED_CODES.contains(&code) => {
+            format!("Access denied to {}: {}", target, std::io::Error::from_raw_os_error(code as i32))
+        }
+        code if NET_UNREACHABLE_CODES.contains(&code) => {
+            format!("Host of {} is unreachable: {}", target, std::io::Error::from_raw_os_error(code as i32))
+        }
+        code => match remote {
+            Some(remote) => format!(
+                "Failed to connect {} to {}: {}",
+                local,
+                remote,
+                std::io::Error::from_raw_os_error(code as i32)
+            ),
+            None => format!("Failed to disconnect {} ({})", local, std::io::Error::from_raw_os_error(code as i32)),
+        },
+    }
+}
+
+/// Like `wnet_error_message`, but resolves ERROR_EXTENDED_ERROR (1208) to the
+/// network provider's own message via `WNetGetLastErrorW`
+fn wnet_failure(code: u32, local: &str, remote: Option<&str>) -> String {
+    use windows::Win32::Foundation::NO_ERROR;
+    use windows::Win32::NetworkManagement::WNet::WNetGetLastErrorW;
+
+    if code == 1208 {
+        let mut provider_code = 0u32;
+        let mut message = [0u16; 512];
+        let mut provider = [0u16; 256];
+        // SAFETY: both buffers are writable for their full length and the
+        // function null-terminates what it writes into them
+        let result = unsafe { WNetGetLastErrorW(&mut provider_code, &mut message, &mut provider) };
+        if result == NO_ERROR {
+            let text = |buf: &[u16]| {
+                let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+                String::from_utf16_lossy(&buf[..len]).trim().to_string()
+            };
+            let (message, provider) = (text(&message), text(&provider));
+            if !message.is_empty() {
+                return if provider.is_empty() { message } else { format!("{} ({})", message, provider) };
             }
         }
-        
-        if !share_name.is_empty() {
-            shares.push(SmbShare {
-                name: share_name.clone(),
-                path: format!("\\\\{}\\{}", host, share_name),
-                description,
+    }
+
+    wnet_error_message(code, local, remote)
+}
+
+/// Map a drive letter to a share. `persistent` (default false) keeps the mapping
+/// across reboots; note that a persistent mapping made with explicit credentials
+/// may prompt for them again when Windows reconnects it.
+/// Without credentials, Windows applies any credential saved with `save_smb_credential`.
+#[tauri::command]
+pub fn map_network_drive(
+    drive_letter: String,
+    path: String,
+    username: Option<String>,
+    password: Option<String>,
+    persistent: Option<bool>,
+) -> Result<String, AppError> {
+    use windows::core::{HSTRING, PCWSTR, PWSTR};
+    use windows::Win32::Foundation::NO_ERROR;
+    use windows::Win32::NetworkManagement::WNet::{
+        WNetAddConnection2W, CONNECT_UPDATE_PROFILE, NETRESOURCEW, NET_CONNECT_FLAGS, RESOURCETYPE_DISK,
+    };
+
+    let persistent = persistent.unwrap_or(false);
+
+    let mut local: Vec<u16> = drive_letter.encode_utf16().chain(Some(0)).collect();
+    let mut remote: Vec<u16> = path.encode_utf16().chain(Some(0)).collect();
+    let resource = NETRESOURCEW {
+        dwType: RESOURCETYPE_DISK,
+        lpLocalName: PWSTR(local.as_mut_ptr()),
+        lpRemoteName: PWSTR(remote.as_mut_ptr()),
+        ..Default::default()
+    };
+
+    // Credentials go straight to the API instead of a `net use` command line,
+    // where they'd be visible in the process list. Null means the current logon.
+    let username = username.filter(|u| !u.is_empty()).map(HSTRING::from);
+    let password = password.map(HSTRING::from);
+    let wide = |s: &Option<HSTRING>| s.as_ref().map_or(PCWSTR::null(), |s| PCWSTR(s.as_ptr()));
+    let flags = if persistent { CONNECT_UPDATE_PROFILE } else { NET_CONNECT_FLAGS(0) };
+
+    // SAFETY: `resource` points into `local` and `remote`, and the credential
+    // strings are owned by `username`/`password`; all outlive the call
+    let result = unsafe { WNetAddConnection2W(&resource, wide(&password), wide(&username), flags) };
+    if result != NO_ERROR {
+        return Err(wnet_failure(result.0, &drive_letter, Some(&path)).into());
+    }
+
+    Ok(format!(
+        "Mapped {} to {} ({})",
+        drive_letter,
+        path,
+        if persistent { "persistent" } else { "until sign-out" }
+    ))
+}
+
+/// Disconnect a mapped drive (or a deviceless `\\server\share` connection),
+/// forgetting it if it was persistent
+#[tauri::command]
+pub fn unmap_network_drive(drive_letter: String) -> Result<String, AppError> {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::{BOOL, NO_ERROR};
+    use windows::Win32::NetworkManagement::WNet::{WNetCancelConnection2W, CONNECT_UPDATE_PROFILE};
+
+    // SAFETY: the name is a valid, null-terminated wide string for the duration of the call
+    let result = unsafe {
+        WNetCancelConnection2W(&HSTRING::from(drive_letter.as_str()), CONNECT_UPDATE_PROFILE, BOOL::from(true))
+    };
+    if result != NO_ERROR {
+        return Err(wnet_failure(result.0, &drive_letter, None).into());
+    }
+
+    Ok(format!("Unmapped {}", drive_letter))
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct MappedDrive {
+    /// Drive letter (e.g. "Z:"), empty for deviceless connections
+    pub local_path: String,
+    pub remote_path: String,
+    pub status: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct DeadMappingCleanup {
+    pub dry_run: bool,
+    /// Mappings that were removed (or would be, in a dry run)
+    pub removed: Vec<MappedDrive>,
+    /// Dead mappings that could not be removed, with the error
+    pub failed: Vec<(MappedDrive, String)>,
+    pub kept: usize,
+}
+
+/// Server name from a UNC path like `\\server\share`
+pub(crate) fn unc_host(remote_path: &str) -> Option<&str> {
+    let host = remote_path.trim_start_matches('\\').split('\\').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+/// Check whether a host accepts connections on the SMB port
+pub(crate) fn smb_port_reachable(host: &str, timeout: Duration) -> bool {
+    tcp_connect_latency(host, SMB_PORT, timeout).is_ok()
+}
+
+/// List SMB drive mappings for the current user
+#[tauri::command]
+pub fn list_mapped_drives() -> Result<Vec<MappedDrive>, AppError> {
+    let stdout = run_powershell(
+        r#"
+        $mappings = @(Get-SmbMapping -ErrorAction SilentlyContinue | ForEach-Object {
+            @{ local_path = "$($_.LocalPath)"; remote_path = "$($_.RemotePath)"; status = "$($_.Status)" }
+        })
+        ConvertTo-Json -InputObject $mappings -Compress
+        "#,
+    )?;
+
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(serde_json::from_str(trimmed)?)
+}
+
+/// Remove mappings whose server no longer answers on port 445
+/// Each server gets several generous attempts so slow links aren't mistaken for dead ones
+#[tauri::command]
+pub fn cleanup_dead_mappings(dry_run: bool) -> Result<DeadMappingCleanup, AppError> {
+    let mappings = list_mapped_drives()?;
+
+    // Check each distinct server once, in parallel
+    let timeout = Duration::from_millis(DEAD_MAPPING_TIMEOUT_MS);
+    let mut hosts: Vec<String> = mappings
+        .iter()
+        .filter_map(|m| unc_host(&m.remote_path))
+        .map(|h| h.to_lowercase())
+        .collect();
+    hosts.sort();
+    hosts.dedup();
+
+    let reachability: HashMap<String, bool> = hosts
+        .into_iter()
+        .map(|host| {
+            let handle = std::thread::spawn({
+                let host = host.clone();
+                move || (0..DEAD_MAPPING_ATTEMPTS).any(|_| smb_port_reachable(&host, timeout))
             });
+            (host, handle)
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|(host, handle)| (host, handle.join().unwrap_or(true)))
+        .collect();
+
+    let mut result = DeadMappingCleanup {
+        dry_run,
+        removed: vec![],
+        failed: vec![],
+        kept: 0,
+    };
+
+    for mapping in mappings {
+        let dead = unc_host(&mapping.remote_path)
+            .and_then(|h| reachability.get(&h.to_lowercase()))
+            .map(|reachable| !reachable)
+            .unwrap_or(false);
+
+        if !dead {
+            result.kept += 1;
+            continue;
+        }
+
+        if dry_run {
+            result.removed.push(mapping);
+            continue;
+        }
+
+        let target = if mapping.local_path.is_empty() {
+            mapping.remote_path.clone()
+        } else {
+            mapping.local_path.clone()
+        };
+        match unmap_network_drive(target) {
+            Ok(_) => result.removed.push(mapping),
+            Err(e) => result.failed.push((mapping, e.to_string())),
         }
     }
 
-    Ok(shares)
+    Ok(result)
 }
 
+/// Characters Windows doesn't allow in share names
+const INVALID_SHARE_NAME_CHARS: &[char] = &['\\', '/', ':', '*', '?', '"', '<', '>', '|'];
+
+fn validate_share_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Share name cannot be empty".to_string());
+    }
+    if name.chars().count() > 80 {
+        return Err("Share name must be at most 80 characters".to_string());
+    }
+    if name.contains(INVALID_SHARE_NAME_CHARS) {
+        return Err(format!("Share name has invalid characters: {}", name));
+    }
+    Ok(())
+}
+
+/// Quote accounts as a PowerShell string array: @('DOMAIN\user','Everyone')
+fn ps_string_array(values: &[String]) -> String {
+    let quoted: Vec<String> = values
+        .iter()
+        .map(|v| format!("'{}'", v.replace('\'', "''")))
+        .collect();
+    format!("@({})", quoted.join(","))
+}
+
+/// Whether a local share with this name exists
+fn share_exists(name: &str) -> Result<bool, String> {
+    let existing = run_powershell(&format!(
+        "Get-SmbShare -Name '{}' -ErrorAction SilentlyContinue | Select-Object -ExpandProperty Name",
+        name.replace('\'', "''")
+    ))?;
+    Ok(!existing.trim().is_empty())
+}
+
+/// Create a local SMB share and return its UNC path
+/// Requires administrator privileges
 #[tauri::command]
-pub fn map_network_drive(drive_letter: String, path: String, username: Option<String>, password: Option<String>) -> Result<String, String> {
-    let mut args = vec!["use".to_string(), drive_letter.clone(), path.clone()];
-    
-    // Handle credentials
-    // Note: passing password in args is visible in process list, but for local tool it's acceptable-ish.
-    // Better way is using WNetAddConnection2 API via FFI, but that's complex.
-    if let (Some(u), Some(p)) = (username, password) {
-        args.push(p);
-        args.push(format!("/user:{}", u));
-    }
-
-    let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-    let output = Command::new("net")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(&args_refs)
-        .output()
-        .map_err(|e| format!("Failed to map drive: {}", e))?;
-
-    if output.status.success() {
-        Ok(format!("Mapped {} to {}", drive_letter, path))
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(stderr.to_string())
+pub fn create_smb_share(
+    name: String,
+    path: String,
+    full_access: Vec<String>,
+    read_access: Vec<String>,
+) -> Result<String, AppError> {
+    validate_share_name(&name)?;
+    if !std::path::Path::new(&path).is_dir() {
+        return Err(AppError::NotFound(format!(
+            "Folder does not exist: {}",
+            path
+        )));
+    }
+
+    if share_exists(&name)? {
+        return Err(format!("A share named '{}' already exists", name).into());
+    }
+
+    let mut script = format!(
+        "New-SmbShare -Name '{}' -Path '{}' -ErrorAction Stop",
+        name.replace('\'', "''"),
+        path.replace('\'', "''")
+    );
+    if !full_access.is_empty() {
+        script.push_str(&format!(" -FullAccess {}", ps_string_array(&full_access)));
     }
+    if !read_access.is_empty() {
+        script.push_str(&format!(" -ReadAccess {}", ps_string_array(&read_access)));
+    }
+    script.push_str(" | Out-Null");
+    run_privileged(&format!("create share '{}'", name), &script)?;
+
+    let hostname = crate::diagnostics::get_hostname().unwrap_or_else(|_| "localhost".to_string());
+    Ok(format!("\\\\{}\\{}", hostname, name))
 }
 
+/// Remove a local SMB share (the folder itself is kept)
+/// Requires administrator privileges
 #[tauri::command]
-pub fn unmap_network_drive(drive_letter: String) -> Result<String, String> {
-    let output = Command::new("net")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["use", &drive_letter, "/delete", "/y"])
-        .output()
-        .map_err(|e| format!("Failed to unmap drive: {}", e))?;
-
-    if output.status.success() {
-        Ok(format!("Unmapped {}", drive_letter))
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(stderr.to_string())
+pub fn remove_smb_share(name: String) -> Result<String, AppError> {
+    validate_share_name(&name)?;
+    let script = format!(
+        "Remove-SmbShare -Name '{}' -Force -ErrorAction Stop",
+        name.replace('\'', "''")
+    );
+    run_privileged(&format!("remove share '{}'", name), &script)?;
+    Ok(format!("Share '{}' removed", name))
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct SharePermission {
+    pub account: String,
+    /// Full, Change, Read or Custom
+    pub access_right: String,
+    /// Allow or Deny
+    pub access_control_type: String,
+}
+
+/// List who can access a local share (`Get-SmbShareAccess`)
+#[tauri::command]
+pub fn get_share_permissions(share_name: String) -> Result<Vec<SharePermission>, AppError> {
+    validate_share_name(&share_name)?;
+    if !share_exists(&share_name)? {
+        return Err(AppError::NotFound(format!(
+            "Share '{}' does not exist",
+            share_name
+        )));
     }
+
+    let script = format!(
+        r#"
+        $access = @(Get-SmbShareAccess -Name '{}' -ErrorAction Stop | ForEach-Object {{
+            @{{ account = "$($_.AccountName)"; access_right = "$($_.AccessRight)"; access_control_type = "$($_.AccessControlType)" }}
+        }})
+        ConvertTo-Json -InputObject $access -Compress
+        "#,
+        share_name.replace('\'', "''")
+    );
+    let stdout = run_powershell(&script)?;
+
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Ok(vec![]);
+    }
+    Ok(serde_json::from_str(trimmed)?)
 }
 
 /// Open Advanced Sharing Settings
 #[tauri::command]
-pub fn open_advanced_sharing() -> Result<String, String> {
-    Command::new("cmd")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args(["/c", "control", "/name", "Microsoft.NetworkAndSharingCenter", "/page", "Advanced"])
-        .spawn()
-        .map_err(|e| format!("Failed to open Advanced Sharing Settings: {}", e))?;
+pub fn open_advanced_sharing() -> Result<String, AppError> {
+    spawn_cmd(
+        "cmd",
+        &["/c", "control", "/name", "Microsoft.NetworkAndSharingCenter", "/page", "Advanced"],
+    )
+    .map_err(|e| format!("Failed to open Advanced Sharing Settings: {}", e))?;
     Ok("Opened Advanced Sharing Settings".to_string())
 }
+
+/// File in the app config dir holding saved SMB hosts
+const SMB_FAVORITES_FILE: &str = "smb_favorites.json";
+
+/// A saved server (and optional share) for `open_smb_path`; never holds credentials
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct SmbFavorite {
+    pub name: String,
+    pub server: String,
+    pub share: Option<String>,
+}
+
+/// Trim the fields, accepting a leading `\\` on the server and an empty share
+fn new_smb_favorite(name: &str, server: &str, share: Option<&str>) -> Result<SmbFavorite, String> {
+    let name = name.trim();
+    let server = server.trim().trim_start_matches(['\\', '/']);
+    if name.is_empty() {
+        return Err("Favorite name is required".to_string());
+    }
+    if server.is_empty() {
+        return Err("Server address is required".to_string());
+    }
+
+    Ok(SmbFavorite {
+        name: name.to_string(),
+        server: server.to_string(),
+        share: share
+            .map(|s| s.trim().trim_matches(['\\', '/']))
+            .filter(|s| !s.is_empty())
+            .map(str::to_string),
+    })
+}
+
+fn smb_favorites_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to locate app config dir: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app config dir: {}", e))?;
+    Ok(dir.join(SMB_FAVORITES_FILE))
+}
+
+fn read_smb_favorites(app: &AppHandle) -> Result<BTreeMap<String, SmbFavorite>, String> {
+    let path = smb_favorites_path(app)?;
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", SMB_FAVORITES_FILE, e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", SMB_FAVORITES_FILE, e)),
+    }
+}
+
+fn write_smb_favorites(app: &AppHandle, favorites: &BTreeMap<String, SmbFavorite>) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(favorites)
+        .map_err(|e| format!("Failed to serialize favorites: {}", e))?;
+    std::fs::write(smb_favorites_path(app)?, json)
+        .map_err(|e| format!("Failed to write {}: {}", SMB_FAVORITES_FILE, e))
+}
+
+/// Save a server/share under `name`, replacing any favorite with the same name
+#[tauri::command]
+pub fn save_smb_favorite(
+    app: AppHandle,
+    name: String,
+    server: String,
+    share: Option<String>,
+) -> Result<SmbFavorite, AppError> {
+    let favorite = new_smb_favorite(&name, &server, share.as_deref())?;
+
+    let mut favorites = read_smb_favorites(&app)?;
+    favorites.insert(favorite.name.clone(), favorite.clone());
+    write_smb_favorites(&app, &favorites)?;
+
+    Ok(favorite)
+}
+
+/// Saved SMB favorites, sorted by name
+#[tauri::command]
+pub fn list_smb_favorites(app: AppHandle) -> Result<Vec<SmbFavorite>, AppError> {
+    Ok(read_smb_favorites(&app)?.into_values().collect())
+}
+
+/// Delete a saved favorite by name
+#[tauri::command]
+pub fn remove_smb_favorite(app: AppHandle, name: String) -> Result<String, AppError> {
+    let mut favorites = read_smb_favorites(&app)?;
+    if favorites.remove(name.trim()).is_none() {
+        return Err(AppError::NotFound(format!("No SMB favorite named {}", name)));
+    }
+    write_smb_favorites(&app, &favorites)?;
+
+    Ok(format!("Removed favorite {}", name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn negotiate_response(status: u32, security_mode: u16, dialect: u16, capabilities: u32) -> Vec<u8> {
+        let mut smb = vec![0u8; 64 + 65];
+        smb[..4].copy_from_slice(b"\xFESMB");
+        smb[8..12].copy_from_slice(&status.to_le_bytes());
+        smb[64..66].copy_from_slice(&65u16.to_le_bytes());
+        smb[66..68].copy_from_slice(&security_mode.to_le_bytes());
+        smb[68..70].copy_from_slice(&dialect.to_le_bytes());
+        smb[88..92].copy_from_slice(&capabilities.to_le_bytes());
+        smb
+    }
+
+    #[test]
+    fn test_wnet_error_message() {
+        assert_eq!(
+            wnet_error_message(1326, "Z:", Some("\\\\nas\\media")),
+            "The username or password for \\\\nas\\media is incorrect"
+        );
+        assert_eq!(
+            wnet_error_message(85, "Z:", Some("\\\\nas\\media")),
+            "Z: is already mapped to another share"
+        );
+        assert!(wnet_error_message(53, "Z:", Some("\\\\nas\\media")).starts_with("Network path \\\\nas\\media was not found"));
+        assert!(wnet_error_message(5, "Z:", Some("\\\\nas\\media")).starts_with("Access denied to \\\\nas\\media: "));
+        assert!(wnet_error_message(1231, "Z:", Some("\\\\nas\\media")).starts_with("Host of \\\\nas\\media is unreachable: "));
+        assert!(wnet_error_message(1231, "Z:", None).starts_with("Host of Z: is unreachable: "));
+        assert!(wnet_error_message(1, "Z:", None).starts_with("Failed to disconnect Z: ("));
+        assert!(wnet_error_message(1, "Z:", Some("\\\\nas\\media")).starts_with("Failed to connect Z: to "));
+    }
+
+    #[test]
+    fn test_smb_credential_host() {
+        assert_eq!(smb_credential_host("NAS").unwrap(), "nas");
+        assert_eq!(smb_credential_host("\\\\NAS\\media").unwrap(), "nas");
+        assert_eq!(smb_credential_host(" //192.168.1.20/share ").unwrap(), "192.168.1.20");
+        assert!(smb_credential_host("\\\\").is_err());
+    }
+
+    #[test]
+    fn test_new_smb_favorite() {
+        assert_eq!(
+            new_smb_favorite(" NAS ", "\\\\192.168.1.20", Some("\\media\\")).unwrap(),
+            SmbFavorite {
+                name: "NAS".to_string(),
+                server: "192.168.1.20".to_string(),
+                share: Some("media".to_string()),
+            }
+        );
+        assert_eq!(new_smb_favorite("Backup", "backup-srv", Some(" ")).unwrap().share, None);
+        assert!(new_smb_favorite("", "nas", None).is_err());
+        assert!(new_smb_favorite("NAS", "\\\\", None).is_err());
+    }
+
+    #[test]
+    fn test_policy_conflicts() {
+        assert!(!policy_conflicts(None, 1));
+        assert!(!policy_conflicts(Some(1), 1));
+        assert!(policy_conflicts(Some(0), 1));
+        assert!(policy_conflicts(Some(1), 0));
+    }
+
+    #[test]
+    fn test_verify_written() {
+        assert!(verify_written("AllowInsecureGuestAuth", 0, Some(0)).is_ok());
+        let err = verify_written("RequireSecuritySignature", 0, Some(1)).unwrap_err();
+        assert!(err.contains("attempted 0, registry holds 1"));
+        assert!(verify_written("RequireSecuritySignature", 1, None).is_err());
+    }
+
+    #[test]
+    fn test_validate_share_name() {
+        assert!(validate_share_name("Public").is_ok());
+        assert!(validate_share_name("Scans$").is_ok());
+        assert!(validate_share_name(" ").is_err());
+        assert!(validate_share_name("a/b").is_err());
+        assert!(validate_share_name(&"x".repeat(81)).is_err());
+    }
+
+    #[test]
+    fn test_ps_string_array() {
+        let accounts = vec!["Everyone".to_string(), "O'Brien".to_string()];
+        assert_eq!(ps_string_array(&accounts), "@('Everyone','O''Brien')");
+    }
+
+    #[test]
+    fn test_smb_connection_dialect_name() {
+        assert_eq!(smb_connection_dialect_name("3.1.1"), "SMB 3.1.1");
+        assert_eq!(smb_connection_dialect_name("2.1"), "SMB 2.1");
+        assert_eq!(smb_connection_dialect_name("1.5"), "SMB 1");
+        assert_eq!(smb_connection_dialect_name(""), "Unknown");
+    }
+
+    #[test]
+    fn test_parse_net_view() {
+        let output = "Shared resources at \\\\nas\r\n\r\n\r\n\
+Share name  Type  Used as  Comment\r\n\r\n\
+-------------------------------------------------------------------------------\r\n\
+Media       Disk           Movies and music\r\n\
+Public      Disk\r\n\
+The command completed successfully.\r\n";
+        let shares = parse_net_view("nas", output);
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares[0].name, "Media");
+        assert_eq!(shares[0].path, "\\\\nas\\Media");
+        assert_eq!(shares[0].description, "Movies and music");
+        assert_eq!(shares[1].name, "Public");
+    }
+
+    #[test]
+    fn test_parse_net_view_localized() {
+        let german = "Freigegebene Ressourcen auf \\\\nas\r\n\r\n\
+Freigabename  Typ     Verwendet als  Kommentar\r\n\r\n\
+-------------------------------------------------------------------------------\r\n\
+Daten         Platte  Z:             Gemeinsame Dateien\r\n\
+Drucker       Druck\r\n\
+Der Befehl wurde erfolgreich ausgeführt.\r\n";
+        let shares = parse_net_view("nas", german);
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares[0].name, "Daten");
+        assert_eq!(shares[0].description, "Gemeinsame Dateien");
+        assert_eq!(shares[1].name, "Drucker");
+        assert_eq!(shares[1].description, "");
+
+        let japanese = "\\\\nas の共有リソース\r\n\r\n\
+共有名  タイプ  使用  コメント\r\n\r\n\
+-------------------------------------------------------------------------------\r\n\
+共有フォルダー  Disk          チーム用\r\n\
+コマンドは正常に終了しました。\r\n";
+        let shares = parse_net_view("nas", japanese);
+        assert_eq!(shares.len(), 1);
+        assert_eq!(shares[0].name, "共有フォルダー");
+        assert_eq!(shares[0].path, "\\\\nas\\共有フォルダー");
+        assert_eq!(shares[0].description, "チーム用");
+    }
+
+    #[test]
+    fn test_describe_net_error() {
+        assert_eq!(net_error_code("System error 1326 has occurred.\r\n"), Some(1326));
+        assert_eq!(net_error_code("no code here"), None);
+        assert!(describe_net_error("nas", "System error 5 has occurred.\r\n\r\nAccess is denied.")
+            .starts_with("Access denied by nas"));
+        assert!(describe_net_error("nas", "Systemfehler 53 aufgetreten.")
+            .starts_with("Host nas is unreachable"));
+    }
+
+    #[test]
+    fn test_net_use_has_host() {
+        let output = [
+            "New connections will be remembered.",
+            "",
+            "Status       Local     Remote                    Network",
+            "-------------------------------------------------------------------------------",
+            "OK                     \\\\NAS\\IPC$                Microsoft Windows Network",
+            "OK           Z:        \\\\nas2\\media              Microsoft Windows Network",
+            "The command completed successfully.",
+        ]
+        .join("\r\n");
+        assert!(net_use_has_host(&output, "nas"));
+        assert!(net_use_has_host(&output, "NAS2"));
+        assert!(!net_use_has_host(&output, "nas3"));
+        assert!(!net_use_has_host("There are no entries in the list.", "nas"));
+    }
+
+    #[test]
+    fn test_negotiate_request_framing() {
+        let packet = build_smb2_negotiate();
+        let len = ((packet[1] as usize) << 16) | ((packet[2] as usize) << 8) | packet[3] as usize;
+        assert_eq!(len, packet.len() - 4);
+        assert_eq!(&packet[4..8], b"\xFESMB");
+        assert_eq!(len, 64 + 36 + SMB_PROBE_DIALECTS.len() * 2);
+    }
+
+    #[test]
+    fn test_parse_negotiate_response() {
+        let smb = negotiate_response(0, 0x03, 0x0302, 0x40);
+        assert_eq!(parse_smb2_negotiate_response(&smb).unwrap(), (0x0302, true, true));
+
+        let smb = negotiate_response(0, 0x01, 0x0210, 0);
+        assert_eq!(parse_smb2_negotiate_response(&smb).unwrap(), (0x0210, false, false));
+
+        assert!(parse_smb2_negotiate_response(&negotiate_response(0xC000_0022, 1, 0, 0)).is_err());
+        assert!(parse_smb2_negotiate_response(b"\xFFSMBr").is_err());
+        assert!(parse_smb2_negotiate_response(&[0xFE, b'S', b'M', b'B']).is_err());
+    }
+
+    #[test]
+    fn test_unc_host() {
+        assert_eq!(unc_host(r"\\nas\media"), Some("nas"));
+        assert_eq!(unc_host(r"\\10.0.0.5\share\sub"), Some("10.0.0.5"));
+        assert_eq!(unc_host(""), None);
+    }
+
+    #[test]
+    fn test_dialect_names() {
+        assert_eq!(smb_dialect_name(0x0300), "3.0");
+        assert_eq!(smb_dialect_name(0x0abc), "0x0ABC");
+    }
+}
@@ -0,0 +1,240 @@
+//! Network state snapshots
+//! Captures adapters, IP configs, routes, firewall and connection profiles at a
+//! point in time and reports what changed between two captures
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::AppError;
+use crate::firewall::{get_firewall_status, invalidate_firewall_cache, FirewallStatus};
+use crate::network::{get_network_adapters, IPConfiguration, NetworkAdapter};
+use crate::network_unified::{get_ip_configuration_unified, invalidate_all_network_cache};
+use crate::ps::run_powershell;
+
+/// IPv4 routing table entry
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RouteEntry {
+    pub destination: String,
+    pub next_hop: String,
+    pub interface_alias: String,
+    pub metric: u32,
+}
+
+/// Network location profile for a connected interface
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConnectionProfile {
+    pub name: String,
+    pub interface_alias: String,
+    /// Public, Private or DomainAuthenticated
+    pub category: String,
+    pub ipv4_connectivity: String,
+}
+
+/// Network state at one point in time
+/// Collections are keyed by identity so a diff lines up the same item across snapshots
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NetworkSnapshot {
+    /// Unix timestamp (seconds)
+    pub captured_at: u64,
+    /// Keyed by adapter name
+    pub adapters: BTreeMap<String, NetworkAdapter>,
+    /// Keyed by adapter name
+    pub configs: BTreeMap<String, IPConfiguration>,
+    /// Keyed by "destination via interface"
+    pub routes: BTreeMap<String, RouteEntry>,
+    pub firewall: Option<FirewallStatus>,
+    /// Keyed by interface alias
+    pub connection_profiles: BTreeMap<String, ConnectionProfile>,
+}
+
+/// One difference between two snapshots
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SnapshotChange {
+    /// Dotted path, e.g. `configs.Ethernet.gateway`
+    pub path: String,
+    /// "added", "removed" or "changed"
+    pub kind: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+fn query_routes() -> Result<Vec<RouteEntry>, String> {
+    let stdout = run_powershell(
+        r#"
+        $routes = @(Get-NetRoute -AddressFamily IPv4 -ErrorAction SilentlyContinue | ForEach-Object {
+            @{ destination = $_.DestinationPrefix; next_hop = $_.NextHop; interface_alias = $_.InterfaceAlias; metric = [uint32]$_.RouteMetric }
+        })
+        ConvertTo-Json -InputObject $routes -Compress
+        "#,
+    )?;
+    serde_json::from_str(stdout.trim()).map_err(|e| format!("Failed to parse JSON: {}", e))
+}
+
+fn query_connection_profiles() -> Result<Vec<ConnectionProfile>, String> {
+    let stdout = run_powershell(
+        r#"
+        $profiles = @(Get-NetConnectionProfile -ErrorAction SilentlyContinue | ForEach-Object {
+            @{ name = "$($_.Name)"; interface_alias = $_.InterfaceAlias; category = "$($_.NetworkCategory)"; ipv4_connectivity = "$($_.IPv4Connectivity)" }
+        })
+        ConvertTo-Json -InputObject $profiles -Compress
+        "#,
+    )?;
+    serde_json::from_str(stdout.trim()).map_err(|e| format!("Failed to parse JSON: {}", e))
+}
+
+/// Record every difference between `before` and `after` under `path`
+fn diff_values(path: &str, before: &Value, after: &Value, changes: &mut Vec<SnapshotChange>) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, value) in a {
+                match b.get(key) {
+                    Some(other) => diff_values(&child(key), value, other, changes),
+                    None => changes.push(SnapshotChange {
+                        path: child(key),
+                        kind: "removed".to_string(),
+                        before: Some(value.clone()),
+                        after: None,
+                    }),
+                }
+            }
+            for (key, value) in b {
+                if !a.contains_key(key) {
+                    changes.push(SnapshotChange {
+                        path: child(key),
+                        kind: "added".to_string(),
+                        before: None,
+                        after: Some(value.clone()),
+                    });
+                }
+            }
+        }
+        (a, b) if a != b => changes.push(SnapshotChange {
+            path: path.to_string(),
+            kind: "changed".to_string(),
+            before: Some(a.clone()),
+            after: Some(b.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// Capture the current network state, bypassing caches so it reflects this moment
+#[tauri::command]
+pub fn capture_network_snapshot() -> Result<NetworkSnapshot, AppError> {
+    invalidate_all_network_cache();
+    invalidate_firewall_cache();
+
+    let adapters = get_network_adapters()?;
+    let configs = adapters
+        .iter()
+        .filter_map(|adapter| {
+            get_ip_configuration_unified(adapter.name.clone(), Some(false))
+                .ok()
+                .map(|config| (adapter.name.clone(), config))
+        })
+        .collect();
+
+    let routes = query_routes()?
+        .into_iter()
+        .map(|route| (format!("{} via {}", route.destination, route.interface_alias), route))
+        .collect();
+
+    let connection_profiles = query_connection_profiles()?
+        .into_iter()
+        .map(|profile| (profile.interface_alias.clone(), profile))
+        .collect();
+
+    Ok(NetworkSnapshot {
+        captured_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        adapters: adapters.into_iter().map(|a| (a.name.clone(), a)).collect(),
+        configs,
+        routes,
+        firewall: get_firewall_status().ok(),
+        connection_profiles,
+    })
+}
+
+/// List what changed between two snapshots (capture time is ignored)
+#[tauri::command]
+pub fn diff_network_snapshots(
+    a: NetworkSnapshot,
+    b: NetworkSnapshot,
+) -> Result<Vec<SnapshotChange>, AppError> {
+    let mut before = serde_json::to_value(&a).map_err(|e| e.to_string())?;
+    let mut after = serde_json::to_value(&b).map_err(|e| e.to_string())?;
+    for value in [&mut before, &mut after] {
+        if let Value::Object(map) = value {
+            map.remove("captured_at");
+        }
+    }
+
+    let mut changes = Vec::new();
+    diff_values("", &before, &after, &mut changes);
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn diff(a: Value, b: Value) -> Vec<SnapshotChange> {
+        let mut changes = Vec::new();
+        diff_values("", &a, &b, &mut changes);
+        changes
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let v = json!({ "configs": { "Ethernet": { "gateway": "192.168.1.1" } } });
+        assert!(diff(v.clone(), v).is_empty());
+    }
+
+    #[test]
+    fn test_diff_nested_change() {
+        let changes = diff(
+            json!({ "configs": { "Ethernet": { "gateway": "192.168.1.1", "dhcp_enabled": true } } }),
+            json!({ "configs": { "Ethernet": { "gateway": "10.0.0.1", "dhcp_enabled": true } } }),
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "configs.Ethernet.gateway");
+        assert_eq!(changes[0].kind, "changed");
+        assert_eq!(changes[0].after, Some(json!("10.0.0.1")));
+    }
+
+    #[test]
+    fn test_diff_added_and_removed() {
+        let changes = diff(
+            json!({ "adapters": { "Ethernet": { "status": "Up" } } }),
+            json!({ "adapters": { "Wi-Fi": { "status": "Up" } } }),
+        );
+        let kinds: Vec<(&str, &str)> = changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.kind.as_str()))
+            .collect();
+        assert_eq!(kinds, vec![("adapters.Ethernet", "removed"), ("adapters.Wi-Fi", "added")]);
+    }
+
+    #[test]
+    fn test_diff_firewall_disappears() {
+        let changes = diff(
+            json!({ "firewall": { "domain": true, "private": true, "public": true } }),
+            json!({ "firewall": null }),
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "firewall");
+    }
+}
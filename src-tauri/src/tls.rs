@@ -0,0 +1,370 @@
+//! TLS inspection
+//! Performs a TLS handshake with a host and reports the negotiated protocol,
+//! cipher and certificate details, explaining validation failures
+
+use log::debug;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{
+    AlertDescription, ClientConfig, ClientConnection, DigitallySignedStruct, Error as TlsError,
+    RootCertStore, SignatureScheme,
+};
+use serde::{Deserialize, Serialize};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use x509_parser::prelude::*;
+
+use crate::error::AppError;
+
+/// Timeout for the TCP connect and each handshake read/write
+const TLS_TIMEOUT_MS: u64 = 5000;
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// TLS session and leaf certificate details
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TlsInfo {
+    pub host: String,
+    pub port: u16,
+    pub protocol_version: String,
+    pub cipher: String,
+    pub cert_subject: String,
+    pub cert_issuer: String,
+    pub not_before: String,
+    pub not_after: String,
+    pub san: Vec<String>,
+    /// Negative once the certificate has expired
+    pub days_until_expiry: i64,
+}
+
+/// Leaf certificate fields used for reporting and error classification
+struct LeafCert {
+    subject: String,
+    issuer: String,
+    not_before: String,
+    not_after: String,
+    not_before_ts: i64,
+    not_after_ts: i64,
+    san: Vec<String>,
+}
+
+/// Delegates to the platform-trust verifier but records its verdict instead of
+/// aborting, so certificate details can still be read to explain the failure
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    error: Mutex<Option<TlsError>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        if let Err(e) = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        ) {
+            *self.error.lock().unwrap_or_else(|e| e.into_inner()) = Some(e);
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Trust anchors from the Windows certificate store, so enterprise roots are honoured
+fn native_roots() -> RootCertStore {
+    let mut roots = RootCertStore::empty();
+    let loaded = rustls_native_certs::load_native_certs();
+    for e in &loaded.errors {
+        debug!("Skipping unreadable root certificate: {}", e);
+    }
+    roots.add_parsable_certificates(loaded.certs);
+    roots
+}
+
+/// Standard verifying client config for outbound HTTPS requests
+pub(crate) fn https_client_config() -> Result<Arc<ClientConfig>, String> {
+    let config = ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("Failed to build TLS config: {}", e))?
+        .with_root_certificates(native_roots())
+        .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+/// Match a certificate name (possibly `*.example.com`) against a host
+fn name_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.trim_end_matches('.').to_lowercase();
+    let host = host.trim_end_matches('.').to_lowercase();
+
+    match pattern.strip_prefix("*.") {
+        // A wildcard covers exactly one label
+        Some(suffix) => host
+            .split_once('.')
+            .map(|(label, rest)| !label.is_empty() && rest == suffix)
+            .unwrap_or(false),
+        None => pattern == host,
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn parse_leaf(der: &[u8]) -> Result<LeafCert, String> {
+    let (_, cert) = X509Certificate::from_der(der)
+        .map_err(|e| format!("Failed to parse server certificate: {}", e))?;
+
+    let validity = cert.validity();
+    let format_time = |t: ASN1Time| t.to_rfc2822().unwrap_or_else(|_| t.to_string());
+
+    let san = match cert.subject_alternative_name() {
+        Ok(Some(ext)) => ext
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(dns) => Some(dns.to_string()),
+                GeneralName::IPAddress(bytes) => match bytes.len() {
+                    4 => Some(
+                        std::net::Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]).to_string(),
+                    ),
+                    16 => <[u8; 16]>::try_from(*bytes)
+                        .ok()
+                        .map(|b| std::net::Ipv6Addr::from(b).to_string()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+
+    Ok(LeafCert {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        not_before: format_time(validity.not_before),
+        not_after: format_time(validity.not_after),
+        not_before_ts: validity.not_before.timestamp(),
+        not_after_ts: validity.not_after.timestamp(),
+        san,
+    })
+}
+
+/// Turn a recorded verification error into a specific explanation,
+/// preferring what the certificate itself shows over the verifier's wording
+fn describe_cert_failure(host: &str, leaf: &LeafCert, error: &TlsError, now: i64) -> String {
+    if now > leaf.not_after_ts {
+        return format!(
+            "Certificate expired on {} ({} days ago)",
+            leaf.not_after,
+            (now - leaf.not_after_ts) / SECS_PER_DAY
+        );
+    }
+    if now < leaf.not_before_ts {
+        return format!("Certificate is not valid until {}", leaf.not_before);
+    }
+    if !leaf.san.is_empty() && !leaf.san.iter().any(|name| name_matches(name, host)) {
+        return format!(
+            "Certificate name mismatch: {} is not covered by {}",
+            host,
+            leaf.san.join(", ")
+        );
+    }
+    if leaf.subject == leaf.issuer {
+        return format!(
+            "Certificate is self-signed and not trusted ({})",
+            leaf.subject
+        );
+    }
+    format!("Certificate is not trusted: {}", error)
+}
+
+/// Explain a handshake that failed before certificate details were available
+fn describe_handshake_failure(error: &TlsError) -> String {
+    match error {
+        TlsError::AlertReceived(AlertDescription::ProtocolVersion) => {
+            "Server does not support TLS 1.2 or 1.3".to_string()
+        }
+        TlsError::AlertReceived(AlertDescription::HandshakeFailure) => {
+            "Handshake failed: no cipher suite or protocol in common with the server".to_string()
+        }
+        other => format!("TLS handshake failed: {}", other),
+    }
+}
+
+/// Perform a TLS handshake and report protocol, cipher and certificate details
+#[tauri::command]
+pub fn inspect_tls(host: String, port: Option<u16>) -> Result<TlsInfo, AppError> {
+    let port = port.unwrap_or(443);
+    let timeout = Duration::from_millis(TLS_TIMEOUT_MS);
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let inner =
+        WebPkiServerVerifier::builder_with_provider(Arc::new(native_roots()), provider.clone())
+            .build()
+            .map_err(|e| format!("Failed to load trusted root certificates: {}", e))?;
+    let verifier = Arc::new(RecordingVerifier {
+        inner,
+        error: Mutex::new(None),
+    });
+
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("Failed to build TLS config: {}", e))?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+
+    let server_name =
+        ServerName::try_from(host.clone()).map_err(|_| format!("Invalid host name: {}", host))?;
+    let mut conn = ClientConnection::new(Arc::new(config), server_name)
+        .map_err(|e| format!("Failed to start TLS session: {}", e))?;
+
+    let addr = (host.as_str(), port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("No address found for {}", host))?;
+    let mut tcp = TcpStream::connect_timeout(&addr, timeout)
+        .map_err(|e| format!("Could not connect to {}:{}: {}", host, port, e))?;
+    tcp.set_read_timeout(Some(timeout)).ok();
+    tcp.set_write_timeout(Some(timeout)).ok();
+
+    while conn.is_handshaking() {
+        conn.complete_io(&mut tcp).map_err(|e| {
+            match e
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<TlsError>())
+            {
+                Some(tls_error) => describe_handshake_failure(tls_error),
+                None => format!("TLS handshake with {}:{} failed: {}", host, port, e),
+            }
+        })?;
+    }
+
+    let leaf_der = conn
+        .peer_certificates()
+        .and_then(|certs| certs.first())
+        .ok_or_else(|| "Server did not present a certificate".to_string())?;
+    let leaf = parse_leaf(leaf_der)?;
+
+    let now = unix_now();
+    if let Some(error) = verifier
+        .error
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+    {
+        return Err(describe_cert_failure(&host, &leaf, &error, now).into());
+    }
+
+    Ok(TlsInfo {
+        host,
+        port,
+        protocol_version: conn
+            .protocol_version()
+            .map(|v| format!("{:?}", v).replace('_', "."))
+            .unwrap_or_default(),
+        cipher: conn
+            .negotiated_cipher_suite()
+            .map(|s| format!("{:?}", s.suite()))
+            .unwrap_or_default(),
+        cert_subject: leaf.subject,
+        cert_issuer: leaf.issuer,
+        not_before: leaf.not_before,
+        not_after: leaf.not_after,
+        san: leaf.san,
+        days_until_expiry: (leaf.not_after_ts - now).div_euclid(SECS_PER_DAY),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(not_before_ts: i64, not_after_ts: i64, san: &[&str]) -> LeafCert {
+        LeafCert {
+            subject: "CN=example.com".to_string(),
+            issuer: "CN=Example CA".to_string(),
+            not_before: "before".to_string(),
+            not_after: "after".to_string(),
+            not_before_ts,
+            not_after_ts,
+            san: san.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_name_matches() {
+        assert!(name_matches("example.com", "EXAMPLE.com"));
+        assert!(name_matches("*.example.com", "www.example.com"));
+        assert!(!name_matches("*.example.com", "example.com"));
+        assert!(!name_matches("*.example.com", "a.b.example.com"));
+        assert!(!name_matches("example.com", "www.example.com"));
+    }
+
+    #[test]
+    fn test_describe_cert_failure() {
+        let error = TlsError::General("untrusted".to_string());
+        let now = 100 * SECS_PER_DAY;
+
+        let expired =
+            describe_cert_failure("example.com", &leaf(0, 90 * SECS_PER_DAY, &[]), &error, now);
+        assert!(expired.starts_with("Certificate expired on after (10 days ago)"));
+
+        let future =
+            describe_cert_failure("example.com", &leaf(now + 1, now * 2, &[]), &error, now);
+        assert!(future.contains("not valid until"));
+
+        let mismatch = describe_cert_failure(
+            "other.org",
+            &leaf(0, now * 2, &["example.com"]),
+            &error,
+            now,
+        );
+        assert!(mismatch.contains("name mismatch"));
+
+        let untrusted = describe_cert_failure(
+            "example.com",
+            &leaf(0, now * 2, &["example.com"]),
+            &error,
+            now,
+        );
+        assert!(untrusted.starts_with("Certificate is not trusted"));
+    }
+}
@@ -0,0 +1,403 @@
+//! UPnP Internet Gateway Device (IGD) support
+//! SSDP discovery plus the SOAP calls needed to manage router port mappings
+
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+
+/// SSDP multicast address and port
+const SSDP_ADDR: &str = "239.255.255.250:1900";
+
+/// How long to wait for gateways to answer the M-SEARCH
+const SSDP_SEARCH_TIMEOUT_MS: u64 = 3000;
+
+/// Timeout for HTTP requests to the gateway
+const HTTP_TIMEOUT_MS: u64 = 3000;
+
+/// WAN connection services that expose port mapping actions
+const WAN_SERVICE_TYPES: [&str; 3] = [
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+/// Discovered UPnP gateway
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpnpGateway {
+    /// Device description URL from the SSDP response
+    pub location: String,
+    /// Full URL used for SOAP control requests
+    pub control_url: String,
+    pub service_type: String,
+    pub external_ip: Option<String>,
+}
+
+/// Split "http://host:port/path" into (host, port, path)
+fn parse_http_url(url: &str) -> Result<(String, u16, String), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("Unsupported URL (only http:// is supported): {}", url))?;
+
+    let (authority, path) = match rest.find('/') {
+        Some(pos) => (&rest[..pos], rest[pos..].to_string()),
+        None => (rest, "/".to_string()),
+    };
+
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host.to_string(),
+            port.parse().map_err(|_| format!("Invalid port in URL: {}", url))?,
+        ),
+        None => (authority.to_string(), 80),
+    };
+
+    Ok((host, port, path))
+}
+
+/// Resolve a (possibly relative) control URL against the description URL
+fn resolve_url(base: &str, url: &str) -> Result<String, String> {
+    if url.starts_with("http://") {
+        return Ok(url.to_string());
+    }
+    let (host, port, _) = parse_http_url(base)?;
+    let path = if url.starts_with('/') {
+        url.to_string()
+    } else {
+        format!("/{}", url)
+    };
+    Ok(format!("http://{}:{}{}", host, port, path))
+}
+
+/// Text content of the first `<tag>` element, ignoring namespace prefixes
+fn xml_tag_value(xml: &str, tag: &str) -> Option<String> {
+    let mut search_from = 0;
+    while let Some(rel) = xml[search_from..].find('<') {
+        let open = search_from + rel;
+        let close = open + xml[open..].find('>')?;
+        let name = xml[open + 1..close].split_whitespace().next().unwrap_or("");
+        let local = name.rsplit(':').next().unwrap_or(name);
+
+        if local == tag {
+            let content_start = close + 1;
+            let end = content_start + xml[content_start..].find("</")?;
+            return Some(xml[content_start..end].trim().to_string());
+        }
+        search_from = close + 1;
+    }
+    None
+}
+
+/// Escape text for inclusion in an XML element
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Minimal HTTP/1.0 request, returning (status code, body)
+fn http_request(url: &str, method: &str, headers: &[(&str, String)], body: &str) -> Result<(u16, String), String> {
+    let (host, port, path) = parse_http_url(url)?;
+    let timeout = Duration::from_millis(HTTP_TIMEOUT_MS);
+
+    let addr = (host.as_str(), port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("No address for {}", host))?;
+
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)
+        .map_err(|e| format!("Failed to connect to {}: {}", addr, e))?;
+    stream.set_read_timeout(Some(timeout)).ok();
+    stream.set_write_timeout(Some(timeout)).ok();
+
+    let mut request = format!(
+        "{} {} HTTP/1.0\r\nHost: {}:{}\r\nConnection: close\r\nContent-Length: {}\r\n",
+        method,
+        path,
+        host,
+        port,
+        body.len()
+    );
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    request.push_str("\r\n");
+    request.push_str(body);
+
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+    let response = String::from_utf8_lossy(&response);
+
+    let status = response
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| "Malformed HTTP response".to_string())?;
+    let body = response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body.to_string())
+        .unwrap_or_default();
+
+    Ok((status, body))
+}
+
+/// Send an SSDP M-SEARCH and return the LOCATION of the first gateway that answers
+fn ssdp_search() -> Result<String, String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| format!("Failed to bind UDP socket: {}", e))?;
+    socket
+        .set_read_timeout(Some(Duration::from_millis(500)))
+        .map_err(|e| format!("Failed to set socket timeout: {}", e))?;
+
+    let request = "M-SEARCH * HTTP/1.1\r\n\
+                   HOST: 239.255.255.250:1900\r\n\
+                   MAN: \"ssdp:discover\"\r\n\
+                   MX: 2\r\n\
+                   ST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\r\n";
+    socket
+        .send_to(request.as_bytes(), SSDP_ADDR)
+        .map_err(|e| format!("Failed to send SSDP search: {}", e))?;
+
+    let deadline = Instant::now() + Duration::from_millis(SSDP_SEARCH_TIMEOUT_MS);
+    let mut buf = [0u8; 2048];
+    while Instant::now() < deadline {
+        let n = match socket.recv_from(&mut buf) {
+            Ok((n, _)) => n,
+            Err(_) => continue,
+        };
+        let response = String::from_utf8_lossy(&buf[..n]);
+        for line in response.lines() {
+            if let Some((name, value)) = line.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("location") {
+                    return Ok(value.trim().to_string());
+                }
+            }
+        }
+    }
+
+    Err("No UPnP gateway found (router may not support UPnP or it is disabled)".to_string())
+}
+
+/// Find the WAN connection service and its control URL in a device description
+fn find_wan_service(description: &str) -> Option<(String, String)> {
+    for block in description.split("<service>").skip(1) {
+        let block = block.split("</service>").next().unwrap_or(block);
+        let service_type = match xml_tag_value(block, "serviceType") {
+            Some(t) => t,
+            None => continue,
+        };
+        if WAN_SERVICE_TYPES.contains(&service_type.as_str()) {
+            if let Some(control_url) = xml_tag_value(block, "controlURL") {
+                return Some((service_type, control_url));
+            }
+        }
+    }
+    None
+}
+
+/// Invoke a SOAP action on the gateway, returning the response body
+fn soap_call(gateway: &UpnpGateway, action: &str, args: &[(&str, String)]) -> Result<String, String> {
+    let arguments: String = args
+        .iter()
+        .map(|(name, value)| format!("<{0}>{1}</{0}>", name, xml_escape(value)))
+        .collect();
+    let body = format!(
+        "<?xml version=\"1.0\"?>\
+         <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+         s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+         <s:Body><u:{0} xmlns:u=\"{1}\">{2}</u:{0}></s:Body></s:Envelope>",
+        action, gateway.service_type, arguments
+    );
+
+    let headers = [
+        ("Content-Type", "text/xml; charset=\"utf-8\"".to_string()),
+        ("SOAPAction", format!("\"{}#{}\"", gateway.service_type, action)),
+    ];
+    let (status, response) = http_request(&gateway.control_url, "POST", &headers, &body)?;
+
+    if status == 200 {
+        return Ok(response);
+    }
+
+    let code = xml_tag_value(&response, "errorCode").unwrap_or_default();
+    let description = xml_tag_value(&response, "errorDescription").unwrap_or_default();
+    Err(format!(
+        "{} failed (HTTP {}): UPnP error {} {}",
+        action, status, code, description
+    )
+    .trim()
+    .to_string())
+}
+
+/// Discover the gateway and its WAN connection service
+fn find_gateway() -> Result<UpnpGateway, String> {
+    let location = ssdp_search()?;
+    let (status, description) = http_request(&location, "GET", &[], "")?;
+    if status != 200 {
+        return Err(format!("Failed to fetch gateway description (HTTP {})", status));
+    }
+
+    let (service_type, control_path) = find_wan_service(&description)
+        .ok_or_else(|| "Gateway does not expose a WAN connection service".to_string())?;
+    let base = xml_tag_value(&description, "URLBase")
+        .filter(|base| !base.is_empty())
+        .unwrap_or_else(|| location.clone());
+
+    Ok(UpnpGateway {
+        control_url: resolve_url(&base, &control_path)?,
+        location,
+        service_type,
+        external_ip: None,
+    })
+}
+
+/// Local address used to reach the gateway, needed as the mapping target
+fn local_ip_for(gateway: &UpnpGateway) -> Result<String, String> {
+    let (host, port, _) = parse_http_url(&gateway.control_url)?;
+    let target: SocketAddr = (host.as_str(), port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve gateway: {}", e))?
+        .next()
+        .ok_or_else(|| "No address for gateway".to_string())?;
+
+    let socket = UdpSocket::bind("0.0.0.0:0").map_err(|e| format!("Failed to bind UDP socket: {}", e))?;
+    socket
+        .connect(target)
+        .map_err(|e| format!("Failed to route to gateway: {}", e))?;
+    socket
+        .local_addr()
+        .map(|addr| addr.ip().to_string())
+        .map_err(|e| format!("Failed to read local address: {}", e))
+}
+
+fn normalize_protocol(protocol: &str) -> Result<String, String> {
+    match protocol.to_uppercase().as_str() {
+        "TCP" => Ok("TCP".to_string()),
+        "UDP" => Ok("UDP".to_string()),
+        _ => Err(format!("Invalid protocol '{}': expected TCP or UDP", protocol)),
+    }
+}
+
+/// Discover a UPnP gateway on the LAN and report its external IP
+#[tauri::command]
+pub fn discover_upnp_gateway() -> Result<UpnpGateway, AppError> {
+    let mut gateway = find_gateway()?;
+    gateway.external_ip = soap_call(&gateway, "GetExternalIPAddress", &[])
+        .ok()
+        .and_then(|response| xml_tag_value(&response, "NewExternalIPAddress"))
+        .filter(|ip| !ip.is_empty());
+    Ok(gateway)
+}
+
+/// Forward an external port on the router to this machine
+#[tauri::command]
+pub fn add_upnp_port_mapping(
+    external_port: u16,
+    internal_port: u16,
+    protocol: String,
+    description: String,
+) -> Result<String, AppError> {
+    if external_port == 0 || internal_port == 0 {
+        return Err("Ports must be between 1 and 65535".into());
+    }
+    let protocol = normalize_protocol(&protocol)?;
+    let gateway = find_gateway()?;
+    let internal_client = local_ip_for(&gateway)?;
+
+    soap_call(
+        &gateway,
+        "AddPortMapping",
+        &[
+            ("NewRemoteHost", String::new()),
+            ("NewExternalPort", external_port.to_string()),
+            ("NewProtocol", protocol.clone()),
+            ("NewInternalPort", internal_port.to_string()),
+            ("NewInternalClient", internal_client.clone()),
+            ("NewEnabled", "1".to_string()),
+            ("NewPortMappingDescription", description),
+            ("NewLeaseDuration", "0".to_string()),
+        ],
+    )?;
+
+    Ok(format!(
+        "Mapped external {} port {} to {}:{}",
+        protocol, external_port, internal_client, internal_port
+    ))
+}
+
+/// Remove a port mapping from the router
+#[tauri::command]
+pub fn remove_upnp_port_mapping(external_port: u16, protocol: String) -> Result<String, AppError> {
+    let protocol = normalize_protocol(&protocol)?;
+    let gateway = find_gateway()?;
+
+    soap_call(
+        &gateway,
+        "DeletePortMapping",
+        &[
+            ("NewRemoteHost", String::new()),
+            ("NewExternalPort", external_port.to_string()),
+            ("NewProtocol", protocol.clone()),
+        ],
+    )?;
+
+    Ok(format!("Removed {} port mapping {}", protocol, external_port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_http_url() {
+        let (host, port, path) = parse_http_url("http://192.168.1.1:5000/rootDesc.xml").unwrap();
+        assert_eq!(host, "192.168.1.1");
+        assert_eq!(port, 5000);
+        assert_eq!(path, "/rootDesc.xml");
+
+        let (_, port, path) = parse_http_url("http://router").unwrap();
+        assert_eq!(port, 80);
+        assert_eq!(path, "/");
+    }
+
+    #[test]
+    fn test_resolve_relative_control_url() {
+        let url = resolve_url("http://192.168.1.1:5000/rootDesc.xml", "ctl/IPConn").unwrap();
+        assert_eq!(url, "http://192.168.1.1:5000/ctl/IPConn");
+    }
+
+    #[test]
+    fn test_find_wan_service() {
+        let description = "<root><device><serviceList>\
+            <service><serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>\
+            <controlURL>/ctl/L3F</controlURL></service>\
+            <service><serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>\
+            <controlURL>/ctl/IPConn</controlURL></service>\
+            </serviceList></device></root>";
+
+        let (service_type, control_url) = find_wan_service(description).unwrap();
+        assert_eq!(service_type, "urn:schemas-upnp-org:service:WANIPConnection:1");
+        assert_eq!(control_url, "/ctl/IPConn");
+    }
+
+    #[test]
+    fn test_xml_tag_value_with_namespace() {
+        let response = "<s:Envelope><s:Body><u:GetExternalIPAddressResponse>\
+            <NewExternalIPAddress>203.0.113.7</NewExternalIPAddress>\
+            </u:GetExternalIPAddressResponse></s:Body></s:Envelope>";
+        assert_eq!(
+            xml_tag_value(response, "NewExternalIPAddress").as_deref(),
+            Some("203.0.113.7")
+        );
+        assert!(xml_tag_value(response, "errorCode").is_none());
+    }
+}
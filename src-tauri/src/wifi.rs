@@ -0,0 +1,423 @@
+//! Wi-Fi support
+//! Reads wireless link details, scans for networks and connects via `netsh wlan`
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+use crate::ps::run_cmd;
+
+/// How long `connect_wifi` waits for the interface to report "connected"
+const WIFI_CONNECT_TIMEOUT_SECS: u64 = 20;
+
+/// Poll interval while waiting for the connection
+const WIFI_CONNECT_POLL_MS: u64 = 500;
+
+/// Makes each temporary profile file name unique within the process
+static PROFILE_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Printed by `netsh wlan` when the WLAN AutoConfig service isn't running
+const WLAN_SERVICE_STOPPED: &str = "wlansvc";
+
+/// Wireless link details for a connected Wi-Fi adapter
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WifiInfo {
+    pub ssid: String,
+    pub signal_percent: u8,
+    /// e.g. "802.11ax"
+    pub radio_type: String,
+    pub channel: u32,
+    pub rx_rate_mbps: f64,
+    pub tx_rate_mbps: f64,
+    /// e.g. "WPA2-Personal"
+    pub authentication: String,
+}
+
+/// Run `netsh wlan` with the given arguments, turning a stopped WLAN service into a clear error
+fn run_netsh_wlan(args: &[&str]) -> Result<String, String> {
+    let mut netsh_args = vec!["wlan"];
+    netsh_args.extend_from_slice(args);
+    let output = run_cmd("netsh", &netsh_args)?;
+
+    let stdout = output.stdout;
+    if stdout.to_lowercase().contains(WLAN_SERVICE_STOPPED) {
+        return Err("Wi-Fi is unavailable: the WLAN AutoConfig service is not running. Turn on Wi-Fi and try again.".to_string());
+    }
+    if !output.success {
+        return Err(stdout.trim().to_string());
+    }
+    Ok(stdout)
+}
+
+/// Split a `netsh wlan` line like "    Signal   : 95%" into ("Signal", "95%")
+fn wlan_field(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    Some((key.trim(), value.trim()))
+}
+
+/// Find `adapter_name` in `netsh wlan show interfaces` output and read its link details
+fn parse_wlan_interface(output: &str, adapter_name: &str) -> Result<WifiInfo, String> {
+    let mut fields: Option<BTreeMap<&str, &str>> = None;
+    for line in output.lines() {
+        let Some((key, value)) = wlan_field(line) else { continue };
+        if key == "Name" {
+            if fields.is_some() {
+                break;
+            }
+            if value.eq_ignore_ascii_case(adapter_name) {
+                fields = Some(BTreeMap::new());
+            }
+        } else if let Some(fields) = fields.as_mut() {
+            fields.insert(key, value);
+        }
+    }
+
+    let fields = fields.ok_or_else(|| format!("{} is not a wireless adapter", adapter_name))?;
+    let ssid = fields.get("SSID").copied().unwrap_or_default();
+    if ssid.is_empty() {
+        return Err(format!("{} is not connected to a wireless network", adapter_name));
+    }
+
+    let text = |key: &str| fields.get(key).copied().unwrap_or_default().to_string();
+    let number = |key: &str| fields.get(key).and_then(|v| v.trim_end_matches('%').trim().parse::<f64>().ok());
+
+    Ok(WifiInfo {
+        ssid: ssid.to_string(),
+        signal_percent: number("Signal").unwrap_or(0.0) as u8,
+        radio_type: text("Radio type"),
+        channel: number("Channel").unwrap_or(0.0) as u32,
+        rx_rate_mbps: number("Receive rate (Mbps)").unwrap_or(0.0),
+        tx_rate_mbps: number("Transmit rate (Mbps)").unwrap_or(0.0),
+        authentication: text("Authentication"),
+    })
+}
+
+/// Signal, SSID and link rates for a Wi-Fi adapter
+#[tauri::command]
+pub fn get_wifi_info(adapter_name: String) -> Result<WifiInfo, AppError> {
+    let output = run_netsh_wlan(&["show", "interfaces"])?;
+    Ok(parse_wlan_interface(&output, &adapter_name)?)
+}
+
+/// Wi-Fi network visible in a scan (strongest access point for the SSID)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WifiNetwork {
+    pub ssid: String,
+    pub signal_percent: u8,
+    pub authentication: String,
+    pub encryption: String,
+    pub channel: u32,
+}
+
+/// Parse `netsh wlan show networks mode=bssid` into one entry per SSID,
+/// keeping the strongest BSSID, sorted by signal (strongest first)
+fn parse_wlan_networks(output: &str) -> Vec<WifiNetwork> {
+    let mut access_points: Vec<WifiNetwork> = Vec::new();
+    let mut network: Option<WifiNetwork> = None;
+
+    for line in output.lines() {
+        let Some((key, value)) = wlan_field(line) else { continue };
+        if key.starts_with("SSID ") {
+            network = Some(WifiNetwork {
+                ssid: value.to_string(),
+                signal_percent: 0,
+                authentication: String::new(),
+                encryption: String::new(),
+                channel: 0,
+            });
+            continue;
+        }
+        let Some(network) = network.as_mut() else { continue };
+        match key {
+            "Authentication" => network.authentication = value.to_string(),
+            "Encryption" => network.encryption = value.to_string(),
+            _ if key.starts_with("BSSID ") => access_points.push(network.clone()),
+            "Signal" | "Channel" => {
+                if let Some(ap) = access_points.last_mut().filter(|ap| ap.ssid == network.ssid) {
+                    let number = value.trim_end_matches('%').trim().parse().unwrap_or(0);
+                    if key == "Signal" {
+                        ap.signal_percent = number.min(100) as u8;
+                    } else {
+                        ap.channel = number;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Hidden networks have no SSID to connect to, so leave them out
+    let mut strongest: BTreeMap<String, WifiNetwork> = BTreeMap::new();
+    for ap in access_points.into_iter().filter(|ap| !ap.ssid.is_empty()) {
+        match strongest.get(&ap.ssid) {
+            Some(existing) if existing.signal_percent >= ap.signal_percent => {}
+            _ => {
+                strongest.insert(ap.ssid.clone(), ap);
+            }
+        }
+    }
+
+    let mut networks: Vec<WifiNetwork> = strongest.into_values().collect();
+    networks.sort_by_key(|n| std::cmp::Reverse(n.signal_percent));
+    networks
+}
+
+/// Scan for visible Wi-Fi networks, strongest first
+#[tauri::command]
+pub fn scan_wifi_networks() -> Result<Vec<WifiNetwork>, AppError> {
+    let output = run_netsh_wlan(&["show", "networks", "mode=bssid"])?;
+    Ok(parse_wlan_networks(&output))
+}
+
+/// Names of the networks interfaces are currently connected to, from `netsh wlan show interfaces`
+fn connected_ssids(output: &str) -> Vec<String> {
+    let mut ssids = Vec::new();
+    let mut state = "";
+    let mut ssid = "";
+    let mut flush = |state: &str, ssid: &str| {
+        if state.eq_ignore_ascii_case("connected") && !ssid.is_empty() {
+            ssids.push(ssid.to_string());
+        }
+    };
+
+    for line in output.lines() {
+        let Some((key, value)) = wlan_field(line) else { continue };
+        match key {
+            "Name" => {
+                flush(state, ssid);
+                state = "";
+                ssid = "";
+            }
+            "State" => state = value,
+            "SSID" => ssid = value,
+            _ => {}
+        }
+    }
+    flush(state, ssid);
+    ssids
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Build a WLAN profile for `ssid` from the authentication/encryption reported by a scan
+fn wlan_profile_xml(ssid: &str, authentication: &str, encryption: &str, password: &str) -> Result<String, String> {
+    let auth = authentication.to_ascii_uppercase();
+    let (auth, cipher) = if auth.contains("ENTERPRISE") {
+        return Err(format!("{} uses enterprise (802.1X) authentication, which isn't supported", ssid));
+    } else if auth == "OPEN" {
+        ("open", "none")
+    } else if auth.contains("WPA3") {
+        ("WPA3SAE", "AES")
+    } else if auth.starts_with("WPA-") {
+        ("WPAPSK", if encryption.eq_ignore_ascii_case("TKIP") { "TKIP" } else { "AES" })
+    } else {
+        // WPA2-Personal, or a network the scan didn't see
+        ("WPA2PSK", "AES")
+    };
+
+    let shared_key = if auth == "open" {
+        String::new()
+    } else {
+        if !(8..=63).contains(&password.chars().count()) {
+            return Err("Wi-Fi password must be 8-63 characters".to_string());
+        }
+        // `protected` must be false for a plaintext passphrase; Windows encrypts the
+        // key when it imports the profile, and the XML file itself is deleted afterwards
+        format!(
+            "<sharedKey><keyType>passPhrase</keyType><protected>false</protected><keyMaterial>{}</keyMaterial></sharedKey>",
+            xml_escape(password)
+        )
+    };
+
+    let name = xml_escape(ssid);
+    Ok(format!(
+        r#"<?xml version="1.0"?>
+<WLANProfile xmlns="http://www.microsoft.com/networking/WLAN/profile/v1">
+    <name>{name}</name>
+    <SSIDConfig><SSID><name>{name}</name></SSID></SSIDConfig>
+    <connectionType>ESS</connectionType>
+    <connectionMode>auto</connectionMode>
+    <MSM>
+        <security>
+            <authEncryption><authentication>{auth}</authentication><encryption>{cipher}</encryption><useOneX>false</useOneX></authEncryption>
+            {shared_key}
+        </security>
+    </MSM>
+</WLANProfile>
+"#
+    ))
+}
+
+/// Profile XML written for `netsh wlan add profile`, deleted on drop since it holds the password
+struct TempProfileFile(PathBuf);
+
+impl Drop for TempProfileFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Connect to a Wi-Fi network. With a password, a profile is created (or replaced)
+/// first; without one, an existing saved profile is used. Succeeds only once an
+/// interface reports it is connected to `ssid`; runs off the main thread since
+/// that can take up to `WIFI_CONNECT_TIMEOUT_SECS`.
+#[tauri::command(async)]
+pub fn connect_wifi(ssid: String, password: Option<String>) -> Result<String, AppError> {
+    if ssid.trim().is_empty() {
+        return Err("SSID cannot be empty".into());
+    }
+
+    if let Some(password) = password {
+        let scanned = scan_wifi_networks()?.into_iter().find(|n| n.ssid == ssid);
+        let (authentication, encryption) = scanned
+            .map(|n| (n.authentication, n.encryption))
+            .unwrap_or_default();
+        let xml = wlan_profile_xml(&ssid, &authentication, &encryption, &password)?;
+
+        let file = TempProfileFile(std::env::temp_dir().join(format!(
+            "wlan-profile-{}-{}.xml",
+            std::process::id(),
+            PROFILE_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        )));
+        std::fs::write(&file.0, xml).map_err(|e| format!("Failed to write Wi-Fi profile: {}", e))?;
+        run_netsh_wlan(&["add", "profile", &format!("filename={}", file.0.display()), "user=current"])
+            .map_err(|e| format!("Failed to add Wi-Fi profile: {}", e))?;
+    }
+
+    run_netsh_wlan(&["connect", &format!("name={}", ssid)])
+        .map_err(|e| format!("Failed to connect to {}: {}", ssid, e))?;
+
+    let deadline = Instant::now() + Duration::from_secs(WIFI_CONNECT_TIMEOUT_SECS);
+    while Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(WIFI_CONNECT_POLL_MS));
+        let output = run_netsh_wlan(&["show", "interfaces"])?;
+        if connected_ssids(&output).iter().any(|s| s == &ssid) {
+            return Ok(format!("Connected to {}", ssid));
+        }
+    }
+
+    Err(AppError::Timeout(format!(
+        "Timed out after {}s waiting to connect to {}; check the password and signal",
+        WIFI_CONNECT_TIMEOUT_SECS, ssid
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WLAN_INTERFACES: &str = "
+There are 2 interfaces on the system:
+
+    Name                   : Wi-Fi
+    Description            : Intel(R) Wi-Fi 6 AX201 160MHz
+    Physical address       : a4:b1:c1:d2:e3:f4
+    State                  : connected
+    SSID                   : HomeNet
+    BSSID                  : 10:20:30:40:50:60
+    Radio type             : 802.11ax
+    Authentication         : WPA2-Personal
+    Channel                : 36
+    Receive rate (Mbps)    : 866.7
+    Transmit rate (Mbps)   : 1201
+    Signal                 : 95%
+
+    Name                   : Wi-Fi 2
+    Description            : USB Wireless
+    State                  : disconnected
+";
+
+    #[test]
+    fn test_parse_wlan_interface() {
+        let info = parse_wlan_interface(WLAN_INTERFACES, "wi-fi").unwrap();
+        assert_eq!(info.ssid, "HomeNet");
+        assert_eq!(info.signal_percent, 95);
+        assert_eq!(info.radio_type, "802.11ax");
+        assert_eq!(info.channel, 36);
+        assert_eq!(info.rx_rate_mbps, 866.7);
+        assert_eq!(info.tx_rate_mbps, 1201.0);
+        assert_eq!(info.authentication, "WPA2-Personal");
+
+        assert!(parse_wlan_interface(WLAN_INTERFACES, "Wi-Fi 2").unwrap_err().contains("not connected"));
+        assert!(parse_wlan_interface(WLAN_INTERFACES, "Ethernet").unwrap_err().contains("not a wireless"));
+    }
+
+    #[test]
+    fn test_parse_wlan_networks() {
+        let output = "
+Interface name : Wi-Fi
+There are 3 networks currently visible.
+
+SSID 1 : HomeNet
+    Network type            : Infrastructure
+    Authentication          : WPA2-Personal
+    Encryption              : CCMP
+    BSSID 1                 : 10:20:30:40:50:60
+         Signal             : 60%
+         Radio type         : 802.11n
+         Channel            : 6
+    BSSID 2                 : 10:20:30:40:50:61
+         Signal             : 92%
+         Radio type         : 802.11ax
+         Channel            : 36
+
+SSID 2 : Cafe
+    Network type            : Infrastructure
+    Authentication          : Open
+    Encryption              : None
+    BSSID 1                 : 70:80:90:a0:b0:c0
+         Signal             : 75%
+         Channel            : 11
+
+SSID 3 :
+    Network type            : Infrastructure
+    Authentication          : WPA2-Personal
+    Encryption              : CCMP
+    BSSID 1                 : 01:02:03:04:05:06
+         Signal             : 99%
+         Channel            : 1
+";
+        let networks = parse_wlan_networks(output);
+        let summary: Vec<(&str, u8, u32)> = networks
+            .iter()
+            .map(|n| (n.ssid.as_str(), n.signal_percent, n.channel))
+            .collect();
+        assert_eq!(summary, vec![("HomeNet", 92, 36), ("Cafe", 75, 11)]);
+        assert_eq!(networks[1].authentication, "Open");
+        assert_eq!(networks[0].encryption, "CCMP");
+    }
+
+    #[test]
+    fn test_connected_ssids() {
+        assert_eq!(connected_ssids(WLAN_INTERFACES), vec!["HomeNet".to_string()]);
+        assert!(connected_ssids("There is 0 interface on the system:").is_empty());
+    }
+
+    #[test]
+    fn test_wlan_profile_xml() {
+        let xml = wlan_profile_xml("Tom & Jerry", "WPA2-Personal", "CCMP", "p<ss>word").unwrap();
+        assert!(xml.contains("<name>Tom &amp; Jerry</name>"));
+        assert!(xml.contains("<authentication>WPA2PSK</authentication><encryption>AES</encryption>"));
+        assert!(xml.contains("<keyMaterial>p&lt;ss&gt;word</keyMaterial>"));
+
+        let open = wlan_profile_xml("Cafe", "Open", "None", "").unwrap();
+        assert!(open.contains("<authentication>open</authentication><encryption>none</encryption>"));
+        assert!(!open.contains("sharedKey"));
+
+        assert!(wlan_profile_xml("Home", "WPA2-Personal", "CCMP", "short").is_err());
+        assert!(wlan_profile_xml("Corp", "WPA2-Enterprise", "CCMP", "password1").is_err());
+        assert!(wlan_profile_xml("Home", "WPA3-Personal", "GCMP", "password1")
+            .unwrap()
+            .contains("WPA3SAE"));
+    }
+}
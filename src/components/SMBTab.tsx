@@ -10,12 +10,15 @@ import {
     setSMBGuestAuth,
     setSMBClientSignature,
     setSMBServerSignature,
+    setSMB1Enabled,
     resetSMBSettings,
     restartSMBService,
     testSMBConnection,
     listSMBShares,
     openSmbPath,
     openAdvancedSharing,
+    requestElevation,
+    AppError,
     type SMBSettings,
     type SmbShare
 } from '../lib/tauri';
@@ -121,6 +124,21 @@ export function SMBTab() {
         }
     };
 
+    // Settings writes need admin rights; offer to relaunch elevated instead of only reporting it
+    const showSaveError = async (error: unknown) => {
+        if (error instanceof AppError && error.kind === 'not_elevated') {
+            if (confirm(`${error.message}. Restart as administrator now?`)) {
+                try {
+                    await requestElevation();
+                } catch (elevationError) {
+                    toast.error(`${elevationError}`);
+                }
+            }
+            return;
+        }
+        toast.error(`${error}`);
+    };
+
     const handleGuestAuthToggle = async (enabled: boolean) => {
         if (!settings) return;
 
@@ -130,7 +148,7 @@ export function SMBTab() {
             setSettings(prev => prev ? { ...prev, guest_auth_enabled: enabled } : null);
             toast.success(message);
         } catch (error) {
-            toast.error(`${error}`);
+            await showSaveError(error);
         } finally {
             setSaving(null);
         }
@@ -145,7 +163,7 @@ export function SMBTab() {
             setSettings(prev => prev ? { ...prev, client_signature_required: enabled } : null);
             toast.success(message);
         } catch (error) {
-            toast.error(`${error}`);
+            await showSaveError(error);
         } finally {
             setSaving(null);
         }
@@ -160,7 +178,25 @@ export function SMBTab() {
             setSettings(prev => prev ? { ...prev, server_signature_required: enabled } : null);
             toast.success(message);
         } catch (error) {
-            toast.error(`${error}`);
+            await showSaveError(error);
+        } finally {
+            setSaving(null);
+        }
+    };
+
+    const handleSmb1Toggle = async (enabled: boolean) => {
+        if (!settings) return;
+
+        try {
+            setSaving('smb1');
+            const result = await setSMB1Enabled(enabled);
+            setSettings(prev => prev ? { ...prev, smb1_enabled: result.enabled } : null);
+            toast.success(
+                `SMB1 ${result.enabled ? 'enabled' : 'disabled'}` +
+                (result.restart_required ? ' (restart the SMB service to apply)' : '')
+            );
+        } catch (error) {
+            await showSaveError(error);
         } finally {
             setSaving(null);
         }
@@ -173,7 +209,7 @@ export function SMBTab() {
             await loadSettings(); // Reload settings
             toast.success(message);
         } catch (error) {
-            toast.error(`${error}`);
+            await showSaveError(error);
         } finally {
             setSaving(null);
         }
@@ -210,9 +246,9 @@ export function SMBTab() {
         try {
             setTesting(true);
             setConnectionTested(false);
-            const msg = await testSMBConnection(testHost);
+            const result = await testSMBConnection(testHost);
             setConnectionTested(true);
-            toast.success(msg);
+            toast.success(result.message);
         } catch (error) {
             setConnectionTested(false);
             toast.error(`${error}`);
@@ -323,6 +359,13 @@ export function SMBTab() {
             {/* Settings Card */}
             <Card title="Security Settings">
                 <div className="space-y-4">
+                    {settings.policy_override && (
+                        <div className="flex items-center gap-2 text-sm text-amber-400">
+                            <AlertTriangle className="w-4 h-4" />
+                            <span>Group Policy enforces a different guest logon setting; local changes have no effect.</span>
+                        </div>
+                    )}
+
                     {/* Guest Auth Toggle */}
                     <div className="setting-row">
                         <div className="setting-info">
@@ -376,6 +419,32 @@ export function SMBTab() {
                             disabled={saving === 'serverSig'}
                         />
                     </div>
+
+                    {settings.smb1_enabled !== null && (
+                        <>
+                            <div className="border-t border-white/10" />
+
+                            {/* SMB1 Protocol Toggle */}
+                            <div className="setting-row">
+                                <div className="setting-info">
+                                    <div className="flex items-center gap-2">
+                                        <span className="setting-title">SMB1 Protocol</span>
+                                        {settings.smb1_enabled && (
+                                            <AlertTriangle className="w-5 h-5 text-amber-500" />
+                                        )}
+                                    </div>
+                                    <p className="setting-description">
+                                        Allow the obsolete SMB1 protocol on this server (not recommended for security)
+                                    </p>
+                                </div>
+                                <ToggleSwitch
+                                    checked={settings.smb1_enabled}
+                                    onChange={handleSmb1Toggle}
+                                    disabled={saving === 'smb1'}
+                                />
+                            </div>
+                        </>
+                    )}
                 </div>
 
                 {/* Action Buttons */}
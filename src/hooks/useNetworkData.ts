@@ -8,8 +8,7 @@
  */
 
 import { useState, useEffect, useRef, useCallback } from 'react';
-import { invoke } from '@tauri-apps/api/core';
-import { type IPConfiguration, type NetworkAdapter } from '../lib/tauri';
+import { invoke, type IPConfiguration, type NetworkAdapter } from '../lib/tauri';
 import { useNetworkCache, networkCacheStore } from './useNetworkCache';
 
 // Debounce delay in milliseconds
@@ -8,8 +8,7 @@
  * - Prefetch effectiveness
  */
 
-import { invoke } from '@tauri-apps/api/core';
-import { type IPConfiguration } from './tauri';
+import { invoke, type IPConfiguration } from './tauri';
 import { networkCacheStore } from '../hooks/useNetworkCache';
 
 // Storage key for benchmark results
@@ -1,4 +1,48 @@
-import { invoke } from '@tauri-apps/api/core';
+import { invoke as tauriInvoke, type InvokeArgs } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+// ============== Errors ==============
+
+export type AppErrorKind =
+    | 'not_elevated'
+    | 'command_failed'
+    | 'powershell_unavailable'
+    | 'parse_error'
+    | 'not_found'
+    | 'timeout'
+    | 'other';
+
+/** Error thrown by command wrappers; branch on `kind` (e.g. offer elevation on `not_elevated`) */
+export class AppError extends Error {
+    kind: AppErrorKind;
+
+    constructor(kind: AppErrorKind, message: string) {
+        super(message);
+        this.name = 'AppError';
+        this.kind = kind;
+    }
+
+    /** Keep `${error}` in toasts showing just the message */
+    toString(): string {
+        return this.message;
+    }
+}
+
+function isSerializedAppError(value: unknown): value is { kind: AppErrorKind; message: string } {
+    return typeof value === 'object' && value !== null && 'kind' in value && 'message' in value;
+}
+
+/** `invoke` that rethrows the backend's `{ kind, message }` errors as `AppError` */
+export async function invoke<T>(cmd: string, args?: InvokeArgs): Promise<T> {
+    try {
+        return await tauriInvoke<T>(cmd, args);
+    } catch (error) {
+        if (isSerializedAppError(error)) {
+            throw new AppError(error.kind, error.message);
+        }
+        throw error;
+    }
+}
 
 // ============== SMB Types & Commands ==============
 
@@ -6,6 +50,9 @@ export interface SMBSettings {
     guest_auth_enabled: boolean;
     client_signature_required: boolean;
     server_signature_required: boolean;
+    smb1_enabled: boolean | null;
+    /** Group Policy sets one of these values, so local changes will be reverted */
+    policy_override: boolean;
 }
 
 export async function getSMBSettings(): Promise<SMBSettings> {
@@ -24,6 +71,23 @@ export async function setSMBServerSignature(enabled: boolean): Promise<string> {
     return await invoke<string>('set_smb_server_signature', { enabled });
 }
 
+export async function isSMB1Enabled(): Promise<boolean> {
+    return await invoke<boolean>('is_smb1_enabled');
+}
+
+export interface Smb1Update {
+    enabled: boolean;
+    restart_required: boolean;
+}
+
+/**
+ * Enable or disable the SMB1 server protocol (requires administrator)
+ * The change fully applies after restarting the SMB service
+ */
+export async function setSMB1Enabled(enabled: boolean): Promise<Smb1Update> {
+    return await invoke<Smb1Update>('set_smb1_enabled', { enabled });
+}
+
 export async function resetSMBSettings(): Promise<string> {
     return await invoke<string>('reset_smb_settings');
 }
@@ -36,36 +100,250 @@ export async function openAdvancedSharing(): Promise<string> {
     return await invoke<string>('open_advanced_sharing');
 }
 
+export interface SmbFavorite {
+    name: string;
+    server: string;
+    share: string | null;
+}
+
+export async function saveSmbFavorite(name: string, server: string, share?: string): Promise<SmbFavorite> {
+    return await invoke<SmbFavorite>('save_smb_favorite', { name, server, share });
+}
+
+export async function listSmbFavorites(): Promise<SmbFavorite[]> {
+    return await invoke<SmbFavorite[]>('list_smb_favorites');
+}
+
+export async function removeSmbFavorite(name: string): Promise<string> {
+    return await invoke<string>('remove_smb_favorite', { name });
+}
+
+export interface SmbDurabilitySettings {
+    session_timeout_secs: number;
+    client_oplocks_enabled: boolean;
+    server_oplocks_enabled: boolean;
+    durable_handle_timeout_secs: number;
+    auto_disconnect_minutes: number;
+}
+
+export interface SmbDurabilityUpdate {
+    effective: SmbDurabilitySettings;
+    restart_required: boolean;
+}
+
+export async function getSMBDurabilitySettings(): Promise<SmbDurabilitySettings> {
+    return await invoke<SmbDurabilitySettings>('get_smb_durability_settings');
+}
+
+export async function setSMBDurabilitySettings(
+    settings: Partial<SmbDurabilitySettings>
+): Promise<SmbDurabilityUpdate> {
+    return await invoke<SmbDurabilityUpdate>('set_smb_durability_settings', {
+        sessionTimeoutSecs: settings.session_timeout_secs,
+        clientOplocksEnabled: settings.client_oplocks_enabled,
+        serverOplocksEnabled: settings.server_oplocks_enabled,
+        durableHandleTimeoutSecs: settings.durable_handle_timeout_secs,
+        autoDisconnectMinutes: settings.auto_disconnect_minutes,
+    });
+}
+
 export interface SmbShare {
     name: string;
     path: string;
     description: string;
 }
 
-export async function testSMBConnection(host: string): Promise<string> {
-    return await invoke('test_smb_connection', { host });
+export interface SmbConnectionTest {
+    host: string;
+    port: number;
+    latency_ms: number;
+    message: string;
+}
+
+/**
+ * Test a TCP connection to the host's SMB port (445 unless `port` is given)
+ */
+export async function testSMBConnection(host: string, timeoutMs?: number, port?: number): Promise<SmbConnectionTest> {
+    return await invoke<SmbConnectionTest>('test_smb_connection', { host, timeoutMs, port });
+}
+
+export interface SmbSecurityProbe {
+    host: string;
+    dialect: string;
+    signing_required: boolean;
+    encryption_supported: boolean;
+}
+
+export async function probeSMBSecurity(host: string): Promise<SmbSecurityProbe> {
+    return await invoke<SmbSecurityProbe>('probe_smb_security', { host });
+}
+
+export interface SmbConnectionInfo {
+    share: string;
+    /** e.g. "SMB 3.1.1"; "SMB 1" means the obsolete protocol is in use */
+    dialect: string;
+    signed: boolean;
+    encrypted: boolean;
+}
+
+export async function getSMBConnectionInfo(host: string): Promise<SmbConnectionInfo[]> {
+    return await invoke<SmbConnectionInfo[]>('get_smb_connection_info', { host });
 }
 
 export async function listSMBShares(host: string, username?: string, password?: string): Promise<SmbShare[]> {
     return await invoke('list_smb_shares', { host, username, password });
 }
 
-export async function mapNetworkDrive(driveLetter: string, path: string, username?: string, password?: string): Promise<string> {
-    return await invoke('map_network_drive', { drive_letter: driveLetter, path, username, password });
+export async function saveSMBCredential(target: string, username: string, password: string): Promise<string> {
+    return await invoke<string>('save_smb_credential', { target, username, password });
+}
+
+export async function deleteSMBCredential(target: string): Promise<string> {
+    return await invoke<string>('delete_smb_credential', { target });
+}
+
+/**
+ * Create a local SMB share (requires administrator); resolves to its UNC path
+ */
+export async function createSMBShare(name: string, path: string, fullAccess: string[] = [], readAccess: string[] = []): Promise<string> {
+    return await invoke<string>('create_smb_share', { name, path, fullAccess, readAccess });
+}
+
+/**
+ * Remove a local SMB share (requires administrator); the folder is kept
+ */
+export async function removeSMBShare(name: string): Promise<string> {
+    return await invoke<string>('remove_smb_share', { name });
+}
+
+export interface SharePermission {
+    account: string;
+    access_right: string;
+    access_control_type: string;
+}
+
+/**
+ * List the accounts allowed or denied access to a local share
+ */
+export async function getSharePermissions(shareName: string): Promise<SharePermission[]> {
+    return await invoke<SharePermission[]>('get_share_permissions', { shareName });
+}
+
+/**
+ * Map a drive letter to a share
+ * Persistent mappings survive reboots but may prompt for credentials on reconnect
+ */
+export async function mapNetworkDrive(driveLetter: string, path: string, username?: string, password?: string, persistent = false): Promise<string> {
+    return await invoke('map_network_drive', { drive_letter: driveLetter, path, username, password, persistent });
 }
 
 export async function unmapNetworkDrive(driveLetter: string): Promise<string> {
     return await invoke('unmap_network_drive', { drive_letter: driveLetter });
 }
 
+export interface MappedDrive {
+    local_path: string;
+    remote_path: string;
+    status: string;
+}
+
+export interface DeadMappingCleanup {
+    dry_run: boolean;
+    removed: MappedDrive[];
+    failed: [MappedDrive, string][];
+    kept: number;
+}
+
+export async function listMappedDrives(): Promise<MappedDrive[]> {
+    return await invoke<MappedDrive[]>('list_mapped_drives');
+}
+
+export async function cleanupDeadMappings(dryRun: boolean): Promise<DeadMappingCleanup> {
+    return await invoke<DeadMappingCleanup>('cleanup_dead_mappings', { dryRun });
+}
+
+// ============== PowerShell Commands ==============
+
+export interface PowerShellStatus {
+    available: boolean;
+    version: string | null;
+    error: string | null;
+}
+
+export async function checkPowerShellAvailable(): Promise<PowerShellStatus> {
+    return await invoke<PowerShellStatus>('check_powershell_available');
+}
+
+export type PowerShellPreference = 'auto' | 'pwsh' | 'powershell';
+
+export interface PowerShellEdition {
+    executable: string | null;
+    edition: 'Core' | 'Desktop' | null;
+    version: string | null;
+    preference: PowerShellPreference;
+}
+
+export async function getPowerShellEdition(): Promise<PowerShellEdition> {
+    return await invoke<PowerShellEdition>('get_powershell_edition');
+}
+
+export async function setPowerShellPreference(preference: PowerShellPreference): Promise<PowerShellEdition> {
+    return await invoke<PowerShellEdition>('set_powershell_preference', { preference });
+}
+
 // ============== Admin Commands ==============
 
 export async function isAdmin(): Promise<boolean> {
     return await invoke<boolean>('is_admin');
 }
 
-export async function requestElevation(): Promise<string> {
-    return await invoke<string>('request_elevation');
+export type ElevationCapability = 'already_elevated' | 'available' | 'uac_disabled' | 'not_administrator';
+
+/**
+ * Check whether the app can be elevated (use to hide "Run as admin" when it can't)
+ */
+export async function canElevate(): Promise<ElevationCapability> {
+    return await invoke<ElevationCapability>('can_elevate');
+}
+
+/**
+ * Restart the app as administrator
+ * Emits `elevation-restarting` and waits `delayMs` before exiting; rejects if UAC is cancelled
+ */
+export async function requestElevation(delayMs?: number): Promise<string> {
+    return await invoke<string>('request_elevation', { delayMs });
+}
+
+export interface ElevationRestarting {
+    delay_ms: number;
+}
+
+export async function onElevationRestarting(handler: (event: ElevationRestarting) => void): Promise<UnlistenFn> {
+    return await listen<ElevationRestarting>('elevation-restarting', (event) => handler(event.payload));
+}
+
+export interface ElevatedOutput {
+    exit_code: number;
+    stdout: string;
+    stderr: string;
+}
+
+/** Privileged operations `runElevated` can perform; the command line is built by the backend */
+export type ElevatedAction =
+    | { type: 'set_firewall_profile'; profile: 'domain' | 'private' | 'public' | 'all'; enabled: boolean }
+    | { type: 'set_dns'; adapter: string; servers: string[] }
+    | { type: 'flush_dns' }
+    | { type: 'release_ip'; adapter: string }
+    | { type: 'renew_ip'; adapter: string }
+    | { type: 'reset_winsock' }
+    | { type: 'reset_tcp_ip' };
+
+/**
+ * Run one privileged action elevated after a UAC prompt, without restarting
+ * the app as administrator
+ */
+export async function runElevated(action: ElevatedAction): Promise<ElevatedOutput> {
+    return await invoke<ElevatedOutput>('run_elevated', { action });
 }
 
 // ============== Network Types & Commands ==============
@@ -84,12 +362,26 @@ export interface IPConfiguration {
     primary_dns: string;
     secondary_dns: string;
     dhcp_enabled: boolean;
+    ipv6_address: string;
+    ipv6_prefix_length: number;
+    ipv6_gateway: string;
 }
 
 export async function getNetworkAdapters(): Promise<NetworkAdapter[]> {
     return await invoke<NetworkAdapter[]>('get_network_adapters');
 }
 
+export interface NicTeam {
+    team_name: string;
+    members: string[];
+    mode: string;
+    status: string;
+}
+
+export async function getNicTeams(): Promise<NicTeam[]> {
+    return await invoke<NicTeam[]>('get_nic_teams');
+}
+
 export async function getIPConfiguration(adapterName: string): Promise<IPConfiguration> {
     return await invoke<IPConfiguration>('get_ip_configuration', { adapterName });
 }
@@ -98,13 +390,89 @@ export async function applyDHCP(adapterName: string): Promise<string> {
     return await invoke<string>('apply_dhcp', { adapterName });
 }
 
+export interface DhcpVerifyResult {
+    success: boolean;
+    address: string | null;
+    got_apipa: boolean;
+}
+
+export async function applyDHCPVerified(adapterName: string, waitSecs: number = 15): Promise<DhcpVerifyResult> {
+    return await invoke<DhcpVerifyResult>('apply_dhcp_verified', { adapterName, waitSecs });
+}
+
+export interface DhcpOption {
+    option_code: number;
+    name: string;
+    value: string;
+}
+
+export async function getDhcpOptionDetails(adapterName: string): Promise<DhcpOption[]> {
+    return await invoke<DhcpOption[]>('get_dhcp_option_details', { adapterName });
+}
+
+/** All fields are null when the adapter uses a static configuration */
+export interface DhcpLease {
+    server: string | null;
+    /** Unix timestamp (seconds) */
+    lease_obtained: number | null;
+    /** Unix timestamp (seconds) */
+    lease_expires: number | null;
+    dhcp_server_ip: string | null;
+}
+
+export async function getDhcpLease(adapterName: string): Promise<DhcpLease> {
+    return await invoke<DhcpLease>('get_dhcp_lease', { adapterName });
+}
+
+export interface WifiInfo {
+    ssid: string;
+    signal_percent: number;
+    radio_type: string;
+    channel: number;
+    rx_rate_mbps: number;
+    tx_rate_mbps: number;
+    authentication: string;
+}
+
+/**
+ * Wireless link details for a connected Wi-Fi adapter
+ * Rejects if the adapter isn't wireless or isn't connected
+ */
+export async function getWifiInfo(adapterName: string): Promise<WifiInfo> {
+    return await invoke<WifiInfo>('get_wifi_info', { adapterName });
+}
+
+export interface WifiNetwork {
+    ssid: string;
+    signal_percent: number;
+    authentication: string;
+    encryption: string;
+    channel: number;
+}
+
+/**
+ * Scan for visible Wi-Fi networks (one entry per SSID, strongest first)
+ */
+export async function scanWifiNetworks(): Promise<WifiNetwork[]> {
+    return await invoke<WifiNetwork[]>('scan_wifi_networks');
+}
+
+/**
+ * Connect to a Wi-Fi network, waiting until the connection is confirmed
+ * With a password a profile is created first; without one a saved profile is used
+ */
+export async function connectWifi(ssid: string, password?: string): Promise<string> {
+    return await invoke<string>('connect_wifi', { ssid, password });
+}
+
 export async function applyStaticIP(
     adapterName: string,
     ipAddress: string,
     subnetMask: string,
     gateway: string,
     primaryDns: string,
-    secondaryDns: string
+    secondaryDns: string,
+    checkConflict: boolean = false
 ): Promise<string> {
     return await invoke<string>('apply_static_ip', {
         adapterName,
@@ -113,138 +481,997 @@ export async function applyStaticIP(
         gateway,
         primaryDns,
         secondaryDns,
+        checkConflict,
     });
 }
 
-export async function runIpconfig(all: boolean = false): Promise<string> {
-    return await invoke<string>('run_ipconfig', { all });
+export async function applyStaticIPv6(
+    adapterName: string,
+    ipv6Address: string,
+    prefixLength: number,
+    gateway: string,
+    primaryDns: string,
+    secondaryDns: string
+): Promise<string> {
+    return await invoke<string>('apply_static_ipv6', {
+        adapterName,
+        ipv6Address,
+        prefixLength,
+        gateway,
+        primaryDns,
+        secondaryDns,
+    });
 }
 
-export async function releaseIP(): Promise<string> {
-    return await invoke<string>('release_ip');
+export async function enableAdapter(adapterName: string): Promise<string> {
+    return await invoke<string>('enable_adapter', { adapterName });
 }
 
-export async function renewIP(): Promise<string> {
-    return await invoke<string>('renew_ip');
+export async function disableAdapter(adapterName: string): Promise<string> {
+    return await invoke<string>('disable_adapter', { adapterName });
 }
 
-export async function flushDNS(): Promise<string> {
-    return await invoke<string>('flush_dns');
+export async function renameAdapter(currentName: string, newName: string): Promise<string> {
+    return await invoke<string>('rename_adapter', { currentName, newName });
 }
 
-export async function displayDNS(): Promise<string> {
-    return await invoke<string>('display_dns');
+/**
+ * All IPv4 addresses on an adapter as [address, prefixLength] pairs
+ */
+export async function getAllIpAddresses(adapterName: string): Promise<[string, number][]> {
+    return await invoke<[string, number][]>('get_all_ip_addresses', { adapterName });
 }
 
-export async function openNetworkConnections(): Promise<string> {
-    return await invoke<string>('open_network_connections');
+/**
+ * Add another IPv4 address to an adapter without removing existing ones (requires admin)
+ */
+export async function addSecondaryIp(adapterName: string, ipAddress: string, subnetMask: string): Promise<string> {
+    return await invoke<string>('add_secondary_ip', { adapterName, ipAddress, subnetMask });
 }
 
-export async function openNetworkSettings(): Promise<string> {
-    return await invoke<string>('open_network_settings');
+/**
+ * Remove one IPv4 address from an adapter (requires admin)
+ */
+export async function removeIpAddress(adapterName: string, ipAddress: string): Promise<string> {
+    return await invoke<string>('remove_ip_address', { adapterName, ipAddress });
 }
 
-// ============== Firewall Types & Commands ==============
+/**
+ * Set an ordered list of DNS servers (IPv4 and/or IPv6) on an adapter (requires admin)
+ */
+export async function setDnsServers(adapterName: string, servers: string[]): Promise<string> {
+    return await invoke<string>('set_dns_servers', { adapterName, servers });
+}
 
-export interface FirewallStatus {
-    domain: boolean;
-    private: boolean;
-    public: boolean;
+export type DnsPreset = 'google' | 'cloudflare' | 'quad9' | 'opendns' | 'adguard';
+
+/**
+ * Apply a public DNS resolver preset to an adapter (requires admin)
+ */
+export async function applyDnsPreset(adapterName: string, preset: DnsPreset): Promise<string> {
+    return await invoke<string>('apply_dns_preset', { adapterName, preset });
 }
 
-export async function getFirewallStatus(): Promise<FirewallStatus> {
-    return await invoke<FirewallStatus>('get_firewall_status');
+export interface LinkInfo {
+    link_speed_mbps: number;
+    full_duplex: boolean;
+    media_type: string;
+    mtu: number;
 }
 
-export async function setFirewallProfile(profile: string, enabled: boolean): Promise<string> {
-    return await invoke<string>('set_firewall_profile', { profile, enabled });
+/**
+ * Negotiated link speed, duplex and MTU (rejects if the adapter is disconnected)
+ */
+export async function getAdapterLinkInfo(adapterName: string): Promise<LinkInfo> {
+    return await invoke<LinkInfo>('get_adapter_link_info', { adapterName });
 }
 
-export async function enableAllFirewall(): Promise<string> {
-    return await invoke<string>('enable_all_firewall');
+/**
+ * Current IPv4 MTU of an adapter
+ */
+export async function getMtu(adapterName: string): Promise<number> {
+    return await invoke<number>('get_mtu', { adapterName });
 }
 
-export async function disableAllFirewall(): Promise<string> {
-    return await invoke<string>('disable_all_firewall');
+/**
+ * Set the IPv4 MTU of an adapter (576-9000, requires admin)
+ * The message includes the previous value
+ */
+export async function setMtu(adapterName: string, mtu: number): Promise<string> {
+    return await invoke<string>('set_mtu', { adapterName, mtu });
 }
 
-export async function openFirewallSettings(): Promise<string> {
-    return await invoke<string>('open_firewall_settings');
+export interface MacAddressChange {
+    mac_address: string;
+    applied: boolean;
+    note: string | null;
 }
 
-export async function openAdvancedFirewall(): Promise<string> {
-    return await invoke<string>('open_advanced_firewall');
+export async function setMacAddress(adapterName: string, mac: string): Promise<MacAddressChange> {
+    return await invoke<MacAddressChange>('set_mac_address', { adapterName, mac });
 }
 
-// ============== Diagnostic Commands ==============
+export async function resetMacAddress(adapterName: string): Promise<MacAddressChange> {
+    return await invoke<MacAddressChange>('reset_mac_address', { adapterName });
+}
 
-export async function runPing(host: string, count: number = 4): Promise<string> {
-    return await invoke<string>('run_ping', { host, count });
+export async function getWolEnabled(adapterName: string): Promise<boolean> {
+    return await invoke<boolean>('get_wol_enabled', { adapterName });
 }
 
-export async function runTracert(host: string): Promise<string> {
-    return await invoke<string>('run_tracert', { host });
+export async function setWolEnabled(adapterName: string, enabled: boolean): Promise<boolean> {
+    return await invoke<boolean>('set_wol_enabled', { adapterName, enabled });
 }
 
-export async function runNslookup(host: string): Promise<string> {
-    return await invoke<string>('run_nslookup', { host });
+export interface AdapterStats {
+    bytes_sent: number;
+    bytes_received: number;
+    packets_sent: number;
+    packets_received: number;
+    errors_in: number;
+    errors_out: number;
 }
 
-export async function runNetstat(option: string = 'all'): Promise<string> {
-    return await invoke<string>('run_netstat', { option });
+export interface AdapterThroughput {
+    stats: AdapterStats;
+    /** null on the first call for an adapter */
+    bytes_sent_per_sec: number | null;
+    bytes_received_per_sec: number | null;
+    interval_ms: number | null;
 }
 
-export async function getHostname(): Promise<string> {
-    return await invoke<string>('get_hostname');
+/**
+ * Cumulative traffic counters of an adapter
+ */
+export async function getAdapterStatistics(adapterName: string): Promise<AdapterStats> {
+    return await invoke<AdapterStats>('get_adapter_statistics', { adapterName });
 }
 
-export async function getNetworkInfo(): Promise<string> {
-    return await invoke<string>('get_network_info');
+/**
+ * Counters plus send/receive rates since the previous call (poll this for a live indicator)
+ */
+export async function getAdapterThroughput(adapterName: string): Promise<AdapterThroughput> {
+    return await invoke<AdapterThroughput>('get_adapter_throughput', { adapterName });
 }
 
-export async function checkInternet(): Promise<boolean> {
-    return await invoke<boolean>('check_internet');
+export interface AdapterConfigBackup {
+    adapter_name: string;
+    captured_at: number;
+    config: IPConfiguration;
 }
 
-// ============== File Manager Commands ==============
+export async function backupAdapterConfig(adapterName: string, persist: boolean = false): Promise<AdapterConfigBackup> {
+    return await invoke<AdapterConfigBackup>('backup_adapter_config', { adapterName, persist });
+}
 
-export async function openInFileExplorer(paths: string[]): Promise<void> {
-    return await invoke<void>('open_in_file_explorer', { paths });
+export async function listAdapterBackups(): Promise<AdapterConfigBackup[]> {
+    return await invoke<AdapterConfigBackup[]>('list_adapter_backups');
 }
 
-export async function openSmbPath(server: string, share?: string): Promise<void> {
-    return await invoke<void>('open_smb_path', { server, share });
+export async function restoreAdapterConfig(backup: AdapterConfigBackup): Promise<string> {
+    return await invoke<string>('restore_adapter_config', { backup });
 }
 
-// ============== Optimized Network Commands ==============
+export async function runIpconfig(all: boolean = false): Promise<string> {
+    return await invoke<string>('run_ipconfig', { all });
+}
 
-/**
- * Get IP configuration using the unified/optimized Tauri command
- * This combines all 4 PowerShell queries into a single call with caching
- *
- * @param adapterName - Name of the network adapter
- * @returns IP configuration for the adapter
- */
-export async function getIPConfigurationUnified(adapterName: string): Promise<IPConfiguration> {
-    return await invoke<IPConfiguration>('get_ip_configuration_unified', { adapterName });
+export interface AdapterIpInfo {
+    name: string;
+    description: string;
+    physical_address: string | null;
+    dhcp_enabled: boolean;
+    ipv4_addresses: string[];
+    subnet_masks: string[];
+    default_gateways: string[];
+    dns_servers: string[];
+    dhcp_server: string | null;
+    lease_obtained: string | null;
+    lease_expires: string | null;
 }
 
-/**
- * Invalidate the Rust-side cache for a specific adapter
- * Call this after applying configuration changes
- *
- * @param adapterName - Name of the adapter to invalidate cache for
- */
-export async function invalidateAdapterCache(adapterName: string): Promise<void> {
-    return await invoke<void>('invalidate_adapter_cache', { adapterName });
+export async function getIpconfigStructured(): Promise<AdapterIpInfo[]> {
+    return await invoke<AdapterIpInfo[]>('get_ipconfig_structured');
 }
 
-/**
- * Invalidate all network-related caches on the Rust side
- * Call this for a full refresh
- */
-export async function invalidateAllNetworkCache(): Promise<void> {
-    return await invoke<void>('invalidate_all_network_cache');
+export async function releaseIP(): Promise<string> {
+    return await invoke<string>('release_ip');
+}
+
+export async function renewIP(): Promise<string> {
+    return await invoke<string>('renew_ip');
+}
+
+export async function releaseIPAdapter(adapterName: string): Promise<string> {
+    return await invoke<string>('release_ip_adapter', { adapterName });
+}
+
+export async function renewIPAdapter(adapterName: string): Promise<string> {
+    return await invoke<string>('renew_ip_adapter', { adapterName });
+}
+
+export async function flushDNS(): Promise<string> {
+    return await invoke<string>('flush_dns');
+}
+
+export interface FlushStepResult {
+    step: string;
+    success: boolean;
+    detail: string;
+    requires_admin: boolean;
+}
+
+export async function flushAllCaches(): Promise<FlushStepResult[]> {
+    return await invoke<FlushStepResult[]>('flush_all_caches');
+}
+
+/**
+ * Flush the DNS cache and re-register DNS names (ipconfig /flushdns + /registerdns)
+ * Rejects with a clear message when not elevated
+ */
+export async function resetDnsRegistration(): Promise<string> {
+    return await invoke<string>('reset_dns_registration');
+}
+
+export async function displayDNS(): Promise<string> {
+    return await invoke<string>('display_dns');
+}
+
+export interface HostsEntry {
+    ip: string;
+    hostnames: string[];
+    enabled: boolean;
+    comment: string | null;
+}
+
+export async function readHostsFile(): Promise<HostsEntry[]> {
+    return await invoke<HostsEntry[]>('read_hosts_file');
+}
+
+export async function writeHostsEntry(ip: string, hostname: string): Promise<HostsEntry[]> {
+    return await invoke<HostsEntry[]>('write_hosts_entry', { ip, hostname });
+}
+
+export async function removeHostsEntry(hostname: string): Promise<HostsEntry[]> {
+    return await invoke<HostsEntry[]>('remove_hosts_entry', { hostname });
+}
+
+export async function openNetworkConnections(): Promise<string> {
+    return await invoke<string>('open_network_connections');
+}
+
+export async function openNetworkSettings(): Promise<string> {
+    return await invoke<string>('open_network_settings');
+}
+
+export interface WpadStatus {
+    pac_url: string | null;
+    auto_detect_enabled: boolean;
+    discovered_via: string | null;
+}
+
+export async function detectWpad(probe: boolean = false): Promise<WpadStatus> {
+    return await invoke<WpadStatus>('detect_wpad', { probe });
+}
+
+export interface ProxyConfig {
+    enabled: boolean;
+    server: string | null;
+    port: number | null;
+    bypass_list: string[];
+}
+
+export async function getProxySettings(): Promise<ProxyConfig> {
+    return await invoke<ProxyConfig>('get_proxy_settings');
+}
+
+export async function setProxySettings(
+    enabled: boolean,
+    server: string,
+    port: number,
+    bypassList: string[] = []
+): Promise<ProxyConfig> {
+    return await invoke<ProxyConfig>('set_proxy_settings', { enabled, server, port, bypassList });
+}
+
+export interface TransitionTechState {
+    state: string;
+    server: string | null;
+}
+
+export interface Ipv6TransitionStatus {
+    teredo: TransitionTechState;
+    sixtofour: TransitionTechState;
+    isatap: TransitionTechState;
+}
+
+export type Ipv6Transition = 'teredo' | 'sixtofour' | 'isatap';
+
+export async function getIpv6TransitionStatus(): Promise<Ipv6TransitionStatus> {
+    return await invoke<Ipv6TransitionStatus>('get_ipv6_transition_status');
+}
+
+export async function setIpv6TransitionEnabled(technology: Ipv6Transition, enabled: boolean): Promise<string> {
+    return await invoke<string>('set_ipv6_transition_enabled', { technology, enabled });
+}
+
+export interface IpRange {
+    first_ip: string;
+    last_ip: string;
+}
+
+export async function cidrToRange(cidr: string): Promise<IpRange> {
+    return await invoke<IpRange>('cidr_to_range', { cidr });
+}
+
+export async function rangeToCidrs(firstIp: string, lastIp: string): Promise<string[]> {
+    return await invoke<string[]>('range_to_cidrs', { firstIp, lastIp });
+}
+
+export interface Route {
+    address_family: 'IPv4' | 'IPv6';
+    destination: string;
+    netmask: string;
+    prefix_length: number;
+    gateway: string;
+    interface: string;
+    interface_index: number;
+    metric: number;
+    interface_metric: number | null;
+}
+
+export async function getRouteTable(): Promise<Route[]> {
+    return await invoke<Route[]>('get_route_table');
+}
+
+export async function addRoute(destinationPrefix: string, nextHop: string, interfaceName: string, metric?: number): Promise<string> {
+    return await invoke<string>('add_route', { destinationPrefix, nextHop, interfaceName, metric });
+}
+
+export async function deleteRoute(destinationPrefix: string, interfaceName: string): Promise<string> {
+    return await invoke<string>('delete_route', { destinationPrefix, interfaceName });
+}
+
+// ============== Firewall Types & Commands ==============
+
+export interface FirewallStatus {
+    domain: boolean;
+    private: boolean;
+    public: boolean;
+}
+
+export async function getFirewallStatus(): Promise<FirewallStatus> {
+    return await invoke<FirewallStatus>('get_firewall_status');
+}
+
+export async function invalidateFirewallCache(): Promise<void> {
+    return await invoke<void>('invalidate_firewall_cache');
+}
+
+export interface FirewallRule {
+    name: string;
+    display_name: string;
+    direction: string;
+    action: string;
+    enabled: boolean;
+    protocol: string;
+    local_port: string;
+    remote_port: string;
+}
+
+export async function listFirewallRules(direction?: 'Inbound' | 'Outbound'): Promise<FirewallRule[]> {
+    return await invoke<FirewallRule[]>('list_firewall_rules', { direction });
+}
+
+/** Prefix of the error returned when a rule with the same name already exists */
+export const FIREWALL_RULE_EXISTS = 'RuleExists';
+
+export async function addFirewallRule(
+    name: string,
+    direction: 'Inbound' | 'Outbound',
+    protocol: 'TCP' | 'UDP',
+    port: number,
+    action: 'Allow' | 'Block'
+): Promise<string> {
+    return await invoke<string>('add_firewall_rule', { name, direction, protocol, port, action });
+}
+
+export async function removeFirewallRule(name: string): Promise<string> {
+    return await invoke<string>('remove_firewall_rule', { name });
+}
+
+export interface FirewallLogging {
+    profile: string;
+    log_dropped: boolean;
+    log_allowed: boolean;
+    max_size_kb: number;
+    log_file_path: string;
+}
+
+export async function getFirewallLogging(profile: string): Promise<FirewallLogging> {
+    return await invoke<FirewallLogging>('get_firewall_logging', { profile });
+}
+
+export async function setFirewallLogging(
+    profile: string,
+    logDropped: boolean,
+    logAllowed: boolean,
+    maxSizeKb: number
+): Promise<FirewallLogging> {
+    return await invoke<FirewallLogging>('set_firewall_logging', { profile, logDropped, logAllowed, maxSizeKb });
+}
+
+export async function allowAppThroughFirewall(name: string, exePath: string): Promise<string[]> {
+    return await invoke<string[]>('allow_app_through_firewall', { name, exePath });
+}
+
+export async function blockApp(name: string, exePath: string): Promise<string[]> {
+    return await invoke<string[]>('block_app', { name, exePath });
+}
+
+export async function setFirewallProfile(profile: string, enabled: boolean): Promise<string> {
+    return await invoke<string>('set_firewall_profile', { profile, enabled });
+}
+
+export async function enableAllFirewall(): Promise<string> {
+    return await invoke<string>('enable_all_firewall');
+}
+
+export async function disableAllFirewall(): Promise<string> {
+    return await invoke<string>('disable_all_firewall');
+}
+
+export async function openFirewallSettings(): Promise<string> {
+    return await invoke<string>('open_firewall_settings');
+}
+
+export async function openAdvancedFirewall(): Promise<string> {
+    return await invoke<string>('open_advanced_firewall');
+}
+
+export async function exportFirewallRules(path: string): Promise<string> {
+    return await invoke<string>('export_firewall_rules', { path });
+}
+
+export async function importFirewallRules(path: string): Promise<string> {
+    return await invoke<string>('import_firewall_rules', { path });
+}
+
+export async function startFirewallWatch(intervalMs?: number): Promise<string> {
+    return await invoke<string>('start_firewall_watch', { intervalMs });
+}
+
+export async function stopFirewallWatch(): Promise<void> {
+    return await invoke<void>('stop_firewall_watch');
+}
+
+export async function onFirewallChanged(handler: (status: FirewallStatus) => void): Promise<UnlistenFn> {
+    return await listen<FirewallStatus>('firewall-changed', (event) => handler(event.payload));
+}
+
+// ============== Diagnostic Commands ==============
+
+export async function runPing(host: string, count: number = 4): Promise<string> {
+    return await invoke<string>('run_ping', { host, count });
+}
+
+export interface PingReply {
+    bytes: number | null;
+    time_ms: number | null;
+    ttl: number | null;
+    timed_out: boolean;
+}
+
+export interface PingResult {
+    replies: PingReply[];
+    packets_sent: number;
+    packets_received: number;
+    packets_lost: number;
+    min_ms: number | null;
+    max_ms: number | null;
+    avg_ms: number | null;
+}
+
+export async function runPingStructured(host: string, count: number = 4): Promise<PingResult> {
+    return await invoke<PingResult>('run_ping_structured', { host, count });
+}
+
+export interface PingStreamReply {
+    host: string;
+    seq: number;
+    time_ms: number | null;
+    ttl: number | null;
+    timed_out: boolean;
+}
+
+export async function startPingStream(host: string, intervalMs: number = 1000): Promise<string> {
+    return await invoke<string>('start_ping_stream', { host, intervalMs });
+}
+
+export async function stopPingStream(host: string): Promise<void> {
+    return await invoke<void>('stop_ping_stream', { host });
+}
+
+export async function onPingReply(handler: (reply: PingStreamReply) => void): Promise<UnlistenFn> {
+    return await listen<PingStreamReply>('ping-reply', (event) => handler(event.payload));
+}
+
+export async function runTracert(host: string): Promise<string> {
+    return await invoke<string>('run_tracert', { host });
+}
+
+export interface TraceHop {
+    hop_number: number;
+    probes: [number | null, number | null, number | null];
+    address: string | null;
+}
+
+export async function runTracertStructured(host: string, maxHops: number = 30): Promise<TraceHop[]> {
+    return await invoke<TraceHop[]>('run_tracert_structured', { host, maxHops });
+}
+
+export type DnsRecordType = 'A' | 'AAAA' | 'MX' | 'TXT' | 'CNAME' | 'NS';
+
+export interface DnsRecord {
+    record_type: string;
+    value: string;
+    ttl: number | null;
+}
+
+export interface NslookupResult {
+    record_type: string;
+    records: DnsRecord[];
+    note: string | null;
+}
+
+export async function runNslookup(host: string, recordType?: DnsRecordType, server?: string): Promise<NslookupResult> {
+    return await invoke<NslookupResult>('run_nslookup', { host, recordType, server });
+}
+
+export interface PortStatus {
+    port: number;
+    open: boolean;
+    latency_ms: number | null;
+}
+
+export async function scanPorts(host: string, ports: number[], timeoutMs: number = 1000): Promise<PortStatus[]> {
+    return await invoke<PortStatus[]>('scan_ports', { host, ports, timeoutMs });
+}
+
+export interface HostStatus {
+    ip: string;
+    alive: boolean;
+    latency_ms: number | null;
+    hostname: string | null;
+}
+
+export async function pingSweep(cidr: string, resolveNames: boolean = false): Promise<HostStatus[]> {
+    return await invoke<HostStatus[]>('ping_sweep', { cidr, resolveNames });
+}
+
+export interface ReverseDnsEntry {
+    ip: string;
+    hostname: string | null;
+}
+
+export async function reverseDns(ip: string): Promise<string | null> {
+    return await invoke<string | null>('reverse_dns', { ip });
+}
+
+export async function reverseDnsMany(ips: string[]): Promise<ReverseDnsEntry[]> {
+    return await invoke<ReverseDnsEntry[]>('reverse_dns_many', { ips });
+}
+
+export interface PathMtuResult {
+    host: string;
+    mtu: number;
+    probes: number;
+    /** false if the search stopped early; mtu is then a lower bound */
+    complete: boolean;
+}
+
+/**
+ * Discover the path MTU to a host using don't-fragment pings
+ */
+export async function discoverPathMtu(host: string): Promise<PathMtuResult> {
+    return await invoke<PathMtuResult>('discover_path_mtu', { host });
+}
+
+export interface WhoisResult {
+    query: string;
+    server: string;
+    raw: string;
+    registrar: string | null;
+    creation_date: string | null;
+    expiry_date: string | null;
+    name_servers: string[];
+}
+
+/**
+ * Whois lookup for a domain or IP address (IANA referral, then the authoritative server)
+ */
+export async function runWhois(query: string): Promise<WhoisResult> {
+    return await invoke<WhoisResult>('run_whois', { query });
+}
+
+export interface ArpEntry {
+    ip_address: string;
+    mac_address: string;
+    entry_type: string;
+    interface: string;
+}
+
+export async function getArpTable(): Promise<ArpEntry[]> {
+    return await invoke<ArpEntry[]>('get_arp_table');
+}
+
+export async function runNetstat(option: string = 'all'): Promise<string> {
+    return await invoke<string>('run_netstat', { option });
+}
+
+export interface Connection {
+    protocol: 'TCP' | 'UDP';
+    local_addr: string;
+    remote_addr: string;
+    state: string | null;
+    pid: number;
+    process_name: string | null;
+}
+
+export async function getConnectionsWithPid(): Promise<Connection[]> {
+    return await invoke<Connection[]>('get_connections_with_pid');
+}
+
+export async function killProcess(pid: number): Promise<string> {
+    return await invoke<string>('kill_process', { pid });
+}
+
+export async function getHostname(): Promise<string> {
+    return await invoke<string>('get_hostname');
+}
+
+export async function getNetworkInfo(): Promise<string> {
+    return await invoke<string>('get_network_info');
+}
+
+export async function checkInternet(): Promise<boolean> {
+    return await invoke<boolean>('check_internet');
+}
+
+export type HttpFailure = 'dns' | 'tls' | 'timeout' | 'connection' | 'too_many_redirects' | 'other';
+
+export interface HttpCheck {
+    url: string;
+    reachable: boolean;
+    status_code: number | null;
+    elapsed_ms: number;
+    final_url: string | null;
+    failure: HttpFailure | null;
+    error: string | null;
+}
+
+export async function checkHttp(url: string, timeoutMs?: number): Promise<HttpCheck> {
+    return await invoke<HttpCheck>('check_http', { url, timeoutMs });
+}
+
+export interface CaptivePortalStatus {
+    behind_portal: boolean;
+    redirect_url: string | null;
+}
+
+export async function detectCaptivePortal(): Promise<CaptivePortalStatus> {
+    return await invoke<CaptivePortalStatus>('detect_captive_portal');
+}
+
+export interface TargetStatus {
+    target: string;
+    alive: boolean;
+    latency_ms: number | null;
+}
+
+export interface ConnectivityStatus {
+    online: boolean;
+    latency_ms: number | null;
+    targets: TargetStatus[];
+    consecutive_failures: number;
+    offline_for_ms: number | null;
+}
+
+export async function startConnectivityMonitor(intervalMs: number = 5000, targets?: string[]): Promise<string> {
+    return await invoke<string>('start_connectivity_monitor', { intervalMs, targets });
+}
+
+export async function stopConnectivityMonitor(): Promise<string> {
+    return await invoke<string>('stop_connectivity_monitor');
+}
+
+export async function onConnectivityStatus(handler: (status: ConnectivityStatus) => void): Promise<UnlistenFn> {
+    return await listen<ConnectivityStatus>('connectivity-status', (event) => handler(event.payload));
+}
+
+export interface AdapterDnsResult {
+    adapter: string;
+    dns_server: string;
+    resolved_addresses: string[];
+    latency_ms: number;
+    error: string | null;
+}
+
+export async function compareDnsAcrossAdapters(testDomain: string): Promise<AdapterDnsResult[]> {
+    return await invoke<AdapterDnsResult[]>('compare_dns_across_adapters', { testDomain });
+}
+
+export type DnsOutcome = 'resolved' | 'nx_domain' | 'no_answer' | 'timeout' | 'server_error';
+
+export interface DnsTiming {
+    hostname: string;
+    resolved_ip: string | null;
+    elapsed_ms: number;
+    server_used: string;
+    outcome: DnsOutcome;
+}
+
+export async function measureDnsResolution(hostname: string, server?: string): Promise<DnsTiming> {
+    return await invoke<DnsTiming>('measure_dns_resolution', { hostname, server });
+}
+
+export interface TimeSyncStatus {
+    source: string;
+    stratum: number | null;
+    last_sync: string;
+    offset_ms: number | null;
+}
+
+export async function getTimeSyncStatus(): Promise<TimeSyncStatus> {
+    return await invoke<TimeSyncStatus>('get_time_sync_status');
+}
+
+export async function resyncTime(): Promise<string> {
+    return await invoke<string>('resync_time');
+}
+
+export interface RouterInfo {
+    gateway_ip: string;
+    mac: string | null;
+    vendor: string | null;
+    likely_model: string | null;
+}
+
+export async function identifyRouter(probeHttp: boolean = false): Promise<RouterInfo> {
+    return await invoke<RouterInfo>('identify_router', { probeHttp });
+}
+
+export interface TlsInfo {
+    host: string;
+    port: number;
+    protocol_version: string;
+    cipher: string;
+    cert_subject: string;
+    cert_issuer: string;
+    not_before: string;
+    not_after: string;
+    san: string[];
+    days_until_expiry: number;
+}
+
+export async function inspectTls(host: string, port: number = 443): Promise<TlsInfo> {
+    return await invoke<TlsInfo>('inspect_tls', { host, port });
+}
+
+export interface BufferbloatResult {
+    host: string;
+    idle_ms: number;
+    loaded_ms: number;
+    increase_ms: number;
+    rating: string;
+    load_mbps: number;
+}
+
+export async function testBufferbloat(host: string, durationSecs?: number): Promise<BufferbloatResult> {
+    return await invoke<BufferbloatResult>('test_bufferbloat', { host, durationSecs });
+}
+
+// ============== Printer Commands ==============
+
+export interface NetworkPrinter {
+    name: string;
+    port: string;
+    driver: string;
+    shared_name: string;
+}
+
+export interface PrinterConnectionTest {
+    server: string;
+    printer: string;
+    reachable: boolean;
+    printer_found: boolean;
+    latency_ms: number | null;
+}
+
+export async function listNetworkPrinters(): Promise<NetworkPrinter[]> {
+    return await invoke<NetworkPrinter[]>('list_network_printers');
+}
+
+export async function testPrinterConnection(uncPrinterPath: string): Promise<PrinterConnectionTest> {
+    return await invoke<PrinterConnectionTest>('test_printer_connection', { uncPrinterPath });
+}
+
+// ============== Security Commands ==============
+
+export type Severity = 'critical' | 'high' | 'medium' | 'low';
+
+export interface SecurityFinding {
+    severity: Severity;
+    title: string;
+    recommendation: string;
+    remediation_command: string | null;
+}
+
+export interface SecurityPosture {
+    score: number;
+    findings: SecurityFinding[];
+}
+
+export async function getSecurityPosture(): Promise<SecurityPosture> {
+    return await invoke<SecurityPosture>('get_security_posture');
+}
+
+// ============== Snapshot Commands ==============
+
+export interface RouteEntry {
+    destination: string;
+    next_hop: string;
+    interface_alias: string;
+    metric: number;
+}
+
+export interface ConnectionProfile {
+    name: string;
+    interface_alias: string;
+    category: string;
+    ipv4_connectivity: string;
+}
+
+export interface NetworkSnapshot {
+    captured_at: number;
+    adapters: Record<string, NetworkAdapter>;
+    configs: Record<string, IPConfiguration>;
+    routes: Record<string, RouteEntry>;
+    firewall: FirewallStatus | null;
+    connection_profiles: Record<string, ConnectionProfile>;
+}
+
+export interface SnapshotChange {
+    path: string;
+    kind: 'added' | 'removed' | 'changed';
+    before: unknown;
+    after: unknown;
+}
+
+export async function captureNetworkSnapshot(): Promise<NetworkSnapshot> {
+    return await invoke<NetworkSnapshot>('capture_network_snapshot');
+}
+
+export async function diffNetworkSnapshots(a: NetworkSnapshot, b: NetworkSnapshot): Promise<SnapshotChange[]> {
+    return await invoke<SnapshotChange[]>('diff_network_snapshots', { a, b });
+}
+
+/**
+ * Write a network diagnostics report (adapters, IP configs, firewall, SMB, ipconfig /all)
+ * Uses Markdown when the path ends in .md, JSON otherwise; resolves to the written path
+ */
+export async function exportNetworkReport(path: string): Promise<string> {
+    return await invoke<string>('export_network_report', { path });
+}
+
+// ============== UPnP Commands ==============
+
+export interface UpnpGateway {
+    location: string;
+    control_url: string;
+    service_type: string;
+    external_ip: string | null;
+}
+
+export async function discoverUpnpGateway(): Promise<UpnpGateway> {
+    return await invoke<UpnpGateway>('discover_upnp_gateway');
+}
+
+export async function addUpnpPortMapping(
+    externalPort: number,
+    internalPort: number,
+    protocol: 'TCP' | 'UDP',
+    description: string
+): Promise<string> {
+    return await invoke<string>('add_upnp_port_mapping', { externalPort, internalPort, protocol, description });
+}
+
+export async function removeUpnpPortMapping(externalPort: number, protocol: 'TCP' | 'UDP'): Promise<string> {
+    return await invoke<string>('remove_upnp_port_mapping', { externalPort, protocol });
+}
+
+// ============== File Manager Commands ==============
+
+export async function openInFileExplorer(paths: string[]): Promise<void> {
+    return await invoke<void>('open_in_file_explorer', { paths });
+}
+
+/**
+ * Open a file with its default program, or with the given application executable
+ */
+export async function openWith(path: string, application?: string): Promise<void> {
+    return await invoke<void>('open_with', { path, application });
+}
+
+export async function openSmbPath(server: string, share?: string): Promise<void> {
+    return await invoke<void>('open_smb_path', { server, share });
+}
+
+/**
+ * Open a currently mapped network drive (e.g. "Z:") in Explorer
+ */
+export async function openMappedDrive(driveLetter: string): Promise<void> {
+    return await invoke<void>('open_mapped_drive', { driveLetter });
+}
+
+/**
+ * Copy a path to the clipboard; resolves to the exact text copied
+ */
+export async function copyPathToClipboard(path: string): Promise<string> {
+    return await invoke<string>('copy_path_to_clipboard', { path });
+}
+
+/**
+ * Copy the UNC path \\server\share to the clipboard; resolves to the exact text copied
+ */
+export async function copySmbPath(server: string, share?: string): Promise<string> {
+    return await invoke<string>('copy_smb_path', { server, share });
+}
+
+// ============== Optimized Network Commands ==============
+
+/**
+ * Get IP configuration using the unified/optimized Tauri command
+ * This combines all 4 PowerShell queries into a single call with caching
+ *
+ * @param adapterName - Name of the network adapter
+ * @param useStale - Return an expired cached entry immediately and refresh it in the background
+ * @returns IP configuration for the adapter
+ */
+export async function getIPConfigurationUnified(adapterName: string, useStale = true): Promise<IPConfiguration> {
+    return await invoke<IPConfiguration>('get_ip_configuration_unified', { adapterName, useStale });
+}
+
+export interface AdapterIPConfiguration {
+    adapter_name: string;
+    config: IPConfiguration | null;
+    error: string | null;
+}
+
+/**
+ * Get the IP configuration of every adapter at once
+ * Adapters that couldn't be read come back with `error` set instead of failing the batch
+ */
+export async function getAllIPConfigurations(): Promise<AdapterIPConfiguration[]> {
+    return await invoke<AdapterIPConfiguration[]>('get_all_ip_configurations');
+}
+
+/**
+ * Invalidate the Rust-side cache for a specific adapter
+ * Call this after applying configuration changes
+ *
+ * @param adapterName - Name of the adapter to invalidate cache for
+ */
+export async function invalidateAdapterCache(adapterName: string): Promise<void> {
+    return await invoke<void>('invalidate_adapter_cache', { adapterName });
+}
+
+/**
+ * Invalidate all network-related caches on the Rust side
+ * Call this for a full refresh
+ */
+export async function invalidateAllNetworkCache(): Promise<void> {
+    return await invoke<void>('invalidate_all_network_cache');
 }
 
 /**
@@ -252,7 +1479,12 @@ export async function invalidateAllNetworkCache(): Promise<void> {
  */
 export interface CacheStats {
     cached_configs: number;
-    has_adapter_list: boolean;
+    hits: number;
+    misses: number;
+    stale_served: number;
+    evictions: number;
+    firewall_cached: number;
+    firewall_cache_hits: number;
 }
 
 /**
@@ -260,5 +1492,45 @@ export interface CacheStats {
  * Useful for debugging and monitoring cache effectiveness
  */
 export async function getCacheStats(): Promise<CacheStats> {
-    return await invoke<CacheStats>('get_cache_stats');
+    return await invoke<CacheStats>('get_network_cache_stats');
+}
+
+/**
+ * Reset the cumulative cache hit/miss/eviction counters
+ */
+export async function resetCacheStats(): Promise<void> {
+    return await invoke<void>('reset_cache_stats');
+}
+
+/**
+ * Set the TTL (1-3600 seconds) for newly cached network configurations
+ * Entries already in the cache keep their original TTL
+ */
+export async function setCacheTtl(seconds: number): Promise<void> {
+    return await invoke<void>('set_cache_ttl', { seconds });
+}
+
+/**
+ * Get the TTL (seconds) used for newly cached network configurations
+ */
+export async function getCacheTtl(): Promise<number> {
+    return await invoke<number>('get_cache_ttl');
+}
+
+export interface StressReport {
+    total_ops: number;
+    lock_timeouts: number;
+    poison_errors: number;
+    p50_us: number;
+    p95_us: number;
+    p99_us: number;
+    max_us: number;
+    elapsed_ms: number;
+}
+
+/**
+ * Stress-test the Rust cache lock (only available in builds with the `cache-stress` feature)
+ */
+export async function stressCache(threads: number, opsPerThread: number): Promise<StressReport> {
+    return await invoke<StressReport>('stress_cache', { threads, opsPerThread });
 }